@@ -2,19 +2,17 @@
 
 use remitwise_common::{
     clamp_limit, EventCategory, EventPriority, RemitwiseEvents, ARCHIVE_BUMP_AMOUNT,
-    ARCHIVE_LIFETIME_THRESHOLD, CONTRACT_VERSION, DEFAULT_PAGE_LIMIT, INSTANCE_BUMP_AMOUNT,
-    INSTANCE_LIFETIME_THRESHOLD, MAX_BATCH_SIZE, MAX_PAGE_LIMIT,
+    ARCHIVE_LIFETIME_THRESHOLD, CONTRACT_VERSION, INSTANCE_BUMP_AMOUNT,
+    INSTANCE_LIFETIME_THRESHOLD, MAX_BATCH_SIZE,
 };
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Map, String,
-    Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, token::TokenClient, Address,
+    Env, Map, String, Symbol, Val, Vec,
 };
 
-#[derive(Clone, Debug)]
 #[contracttype]
 #[derive(Clone, Debug)]
-#[contracttype]
 pub struct Bill {
     pub id: u32,
     pub owner: Address,
@@ -31,8 +29,89 @@ pub struct Bill {
     /// Intended currency/asset for this bill (e.g. "XLM", "USDC", "NGN").
     /// Defaults to "XLM" for entries created before this field was introduced.
     pub currency: String,
+    /// Who receives the funds when the bill is paid. `None` means the bill is
+    /// settled off-chain and paying it only records the payment.
+    pub payee: Option<Address>,
+    /// Token contract used to settle the bill. Set together with `payee`.
+    pub token: Option<Address>,
+    /// Payments made towards this bill, oldest first. The bill is `paid` once
+    /// they add up to `amount` plus `late_fees`.
+    pub payments: Vec<BillPayment>,
+    /// Late-fee rule; `LateFee::None` for bills that never charge one.
+    pub late_fee_rule: LateFeeRule,
+    /// Late fees owed on top of `amount`. Fees accrue lazily: queries report
+    /// them as of the current ledger time and they are recorded on payment.
+    pub late_fees: i128,
+}
+
+/// Bill layout stored in the instance `BILLS` map before bills carried a
+/// payee, installments and late fees. Never written any more; loaded maps are
+/// migrated to [`Bill`].
+#[contracttype]
+#[derive(Clone)]
+pub struct LegacyBill {
+    pub id: u32,
+    pub owner: Address,
+    pub name: String,
+    pub external_ref: Option<String>,
+    pub amount: i128,
+    pub due_date: u64,
+    pub recurring: bool,
+    pub frequency_days: u32,
+    pub paid: bool,
+    pub created_at: u64,
+    pub paid_at: Option<u64>,
+    pub schedule_id: Option<u32>,
+    pub currency: String,
+}
+
+/// Size of a single late-fee charge.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LateFee {
+    None,
+    /// Fixed amount per charge.
+    Flat(i128),
+    /// Basis points of the bill `amount` per charge.
+    Percent(u32),
+}
+
+/// When late fees are charged on a bill.
+///
+/// The first charge applies once `grace_days` have passed after `due_date`.
+/// With `period_days == 0` that is the only charge; otherwise the fee is
+/// charged again for every further `period_days` the bill stays unpaid.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LateFeeRule {
+    pub fee: LateFee,
+    pub grace_days: u32,
+    pub period_days: u32,
+}
+
+/// A single payment made towards a bill.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BillPayment {
+    pub amount: i128,
+    pub timestamp: u64,
+    pub payer: Address,
 }
 
+/// Record of a single bill payment, published with the `paid` event.
+///
+/// `payee` and `token` are `None` when the bill was marked paid without an
+/// on-chain transfer.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentReceipt {
+    pub bill_id: u32,
+    pub payer: Address,
+    pub payee: Option<Address>,
+    pub token: Option<Address>,
+    pub amount: i128,
+    pub paid_at: u64,
+}
 
 /// Paginated result for bill queries
 #[contracttype]
@@ -55,10 +134,12 @@ pub mod pause_functions {
     pub const RESTORE: soroban_sdk::Symbol = symbol_short!("restore");
 }
 
-const CONTRACT_VERSION: u32 = 1;
-const MAX_BATCH_SIZE: u32 = 50;
 const STORAGE_UNPAID_TOTALS: Symbol = symbol_short!("UNPD_TOT");
 
+/// Most payments one bill can record. The last slot is kept for the payment
+/// that settles it, so `pay_bill` always works.
+const MAX_PAYMENTS_PER_BILL: u32 = 50;
+
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -75,25 +156,45 @@ pub enum Error {
     BatchValidationFailed = 10,
     InvalidLimit = 11,
     InvalidDueDate = 12,
-    InvalidTag = 12,
-    EmptyTags = 13,
+    InvalidTag = 13,
+    EmptyTags = 14,
+    InvalidPayee = 15,
+    TooManyPayments = 16,
 }
 
-#[contracttype]
-#[derive(Clone)]
 #[contracttype]
 #[derive(Clone)]
 pub struct ArchivedBill {
     pub id: u32,
     pub owner: Address,
     pub name: String,
+    pub external_ref: Option<String>,
     pub amount: i128,
     pub paid_at: u64,
     pub archived_at: u64,
     /// Intended currency/asset carried over from the originating `Bill`.
     pub currency: String,
+    /// Late fees paid on top of `amount`.
+    pub late_fees: i128,
+    /// Payments that settled the bill, carried over so a restored bill keeps
+    /// its installment history.
+    pub payments: Vec<BillPayment>,
 }
 
+/// Archived bill layout stored in the instance `ARCH_BILL` map before
+/// archives kept the external reference, late fees and installments. Never
+/// written any more; loaded maps are migrated to [`ArchivedBill`].
+#[contracttype]
+#[derive(Clone)]
+pub struct LegacyArchivedBill {
+    pub id: u32,
+    pub owner: Address,
+    pub name: String,
+    pub amount: i128,
+    pub paid_at: u64,
+    pub archived_at: u64,
+    pub currency: String,
+}
 
 /// Paginated result for archived bill queries
 #[contracttype]
@@ -111,6 +212,10 @@ pub enum BillEvent {
     Created,
     Paid,
     ExternalRefUpdated,
+}
+
+#[contracttype]
+#[derive(Clone)]
 pub struct StorageStats {
     pub active_bills: u32,
     pub archived_bills: u32,
@@ -124,23 +229,6 @@ pub struct BillPayments;
 
 #[contractimpl]
 impl BillPayments {
-    /// Create a new bill
-    ///
-    /// # Arguments
-    /// * `owner` - Address of the bill owner (must authorize)
-    /// * `name` - Name of the bill (e.g., "Electricity", "School Fees")
-    /// * `amount` - Amount to pay (must be positive)
-    /// * `due_date` - Due date as Unix timestamp
-    /// * `recurring` - Whether this is a recurring bill
-    /// * `frequency_days` - Frequency in days for recurring bills (must be > 0 if recurring)
-    /// * `external_ref` - Optional external system reference ID
-    ///
-    /// # Returns
-    /// The ID of the created bill
-    ///
-    /// # Errors
-    /// * `InvalidAmount` - If amount is zero or negative
-    /// * `InvalidFrequency` - If recurring is true but frequency_days is 0
     // -----------------------------------------------------------------------
     // Internal helpers
     // -----------------------------------------------------------------------
@@ -172,9 +260,6 @@ impl BillPayments {
         Ok(())
     }
 
-    /// Clamp a caller-supplied limit to [1, MAX_PAGE_LIMIT].
-    /// A value of 0 is treated as DEFAULT_PAGE_LIMIT.
-
     // -----------------------------------------------------------------------
     // Pause / upgrade
     // -----------------------------------------------------------------------
@@ -366,6 +451,23 @@ impl BillPayments {
     // Core bill operations
     // -----------------------------------------------------------------------
 
+    /// Create a new bill
+    ///
+    /// # Arguments
+    /// * `owner` - Address of the bill owner (must authorize)
+    /// * `name` - Name of the bill (e.g., "Electricity", "School Fees")
+    /// * `amount` - Amount to pay (must be positive)
+    /// * `due_date` - Due date as Unix timestamp
+    /// * `recurring` - Whether this is a recurring bill
+    /// * `frequency_days` - Frequency in days for recurring bills (must be > 0 if recurring)
+    /// * `external_ref` - Optional external system reference ID
+    ///
+    /// # Returns
+    /// The ID of the created bill
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount is zero or negative
+    /// * `InvalidFrequency` - If recurring is true but frequency_days is 0
     #[allow(clippy::too_many_arguments)]
     pub fn create_bill(
         env: Env,
@@ -401,11 +503,7 @@ impl BillPayments {
         };
 
         Self::extend_instance_ttl(&env);
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+        let mut bills = Self::load_bills(&env);
 
         let next_id = env
             .storage()
@@ -429,6 +527,11 @@ impl BillPayments {
             paid_at: None,
             schedule_id: None,
             currency: resolved_currency,
+            payee: None,
+            token: None,
+            payments: Vec::new(&env),
+            late_fee_rule: Self::no_late_fee(),
+            late_fees: 0,
         };
 
         let bill_owner = bill.owner.clone();
@@ -445,7 +548,8 @@ impl BillPayments {
         // Emit event for audit trail
         env.events().publish(
             (symbol_short!("bill"), BillEvent::Created),
-            (next_id, bill_owner, bill_external_ref),
+            (next_id, bill_owner.clone(), bill_external_ref),
+        );
         RemitwiseEvents::emit(
             &env,
             EventCategory::State,
@@ -462,11 +566,7 @@ impl BillPayments {
         Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
 
         Self::extend_instance_ttl(&env);
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+        let mut bills = Self::load_bills(&env);
 
         let mut bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
 
@@ -477,73 +577,118 @@ impl BillPayments {
             return Err(Error::BillAlreadyPaid);
         }
 
-        let current_time = env.ledger().timestamp();
-        bill.paid = true;
-        bill.paid_at = Some(current_time);
-
-        if bill.recurring {
-            let next_due_date = bill.due_date + (bill.frequency_days as u64 * 86400);
-            let next_id = env
-                .storage()
-                .instance()
-                .get(&symbol_short!("NEXT_ID"))
-                .unwrap_or(0u32)
-                + 1;
-
-            let next_bill = Bill {
-                id: next_id,
-                owner: bill.owner.clone(),
-                name: bill.name.clone(),
-                external_ref: bill.external_ref.clone(),
-                amount: bill.amount,
-                due_date: next_due_date,
-                recurring: true,
-                frequency_days: bill.frequency_days,
-                paid: false,
-                created_at: current_time,
-                paid_at: None,
-                schedule_id: bill.schedule_id,
-                currency: bill.currency.clone(),
-            };
-            bills.set(next_id, next_bill);
-            env.storage()
-                .instance()
-                .set(&symbol_short!("NEXT_ID"), &next_id);
-        }
-
         let bill_external_ref = bill.external_ref.clone();
-        let paid_amount = bill.amount;
-        let was_recurring = bill.recurring;
+        let fees_accrued = Self::accrue_late_fees(&env, &mut bill);
+        let remaining = Self::amount_remaining(&bill);
+        let (receipt, unpaid_delta) =
+            Self::apply_payment(&env, &mut bills, &mut bill, &caller, remaining);
         bills.set(bill_id, bill);
         env.storage()
             .instance()
             .set(&symbol_short!("BILLS"), &bills);
-        if !was_recurring {
-            Self::adjust_unpaid_total(&env, &caller, -paid_amount);
-        }
+        Self::adjust_unpaid_total(&env, &caller, fees_accrued.saturating_add(unpaid_delta));
 
         // Emit event for audit trail
         env.events().publish(
             (symbol_short!("bill"), BillEvent::Paid),
-            (bill_id, caller, bill_external_ref),
+            (bill_id, caller.clone(), bill_external_ref),
+        );
         RemitwiseEvents::emit(
             &env,
             EventCategory::Transaction,
             EventPriority::High,
             symbol_short!("paid"),
-            (bill_id, caller, paid_amount),
+            receipt,
         );
 
         Ok(())
     }
 
-    pub fn get_bill(env: Env, bill_id: u32) -> Option<Bill> {
-        let bills: Map<u32, Bill> = env
-            .storage()
+    /// Pay part of a bill
+    ///
+    /// Records an installment against the bill, transferring it to the payee
+    /// when one is set. The bill is marked paid (and, if recurring, the next
+    /// one is created) once its payments cover `amount`.
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the caller (must be the bill owner)
+    /// * `bill_id` - ID of the bill to pay towards
+    /// * `amount` - Installment size, at most the amount still owed including
+    ///   late fees
+    ///
+    /// # Returns
+    /// The amount still owed after this payment
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill with given ID doesn't exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill has already been paid
+    /// * `InvalidAmount` - If amount is not positive or exceeds what is owed
+    pub fn pay_bill_partial(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+
+        Self::extend_instance_ttl(&env);
+        let mut bills = Self::load_bills(&env);
+
+        let mut bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        let fees_accrued = Self::accrue_late_fees(&env, &mut bill);
+        let remaining = Self::amount_remaining(&bill);
+        if amount <= 0 || amount > remaining {
+            return Err(Error::InvalidAmount);
+        }
+        if amount < remaining && bill.payments.len() + 1 >= MAX_PAYMENTS_PER_BILL {
+            return Err(Error::TooManyPayments);
+        }
+
+        let (receipt, unpaid_delta) =
+            Self::apply_payment(&env, &mut bills, &mut bill, &caller, amount);
+        let remaining = Self::amount_remaining(&bill);
+        let action = if bill.paid {
+            symbol_short!("paid")
+        } else {
+            symbol_short!("part_paid")
+        };
+        bills.set(bill_id, bill);
+        env.storage()
             .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        bills.get(bill_id)
+            .set(&symbol_short!("BILLS"), &bills);
+        Self::adjust_unpaid_total(&env, &caller, fees_accrued.saturating_add(unpaid_delta));
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::High,
+            action,
+            receipt,
+        );
+
+        Ok(remaining)
+    }
+
+    pub fn get_bill(env: Env, bill_id: u32) -> Option<Bill> {
+        let bills = Self::load_bills(&env);
+        bills.get(bill_id).map(|mut bill| {
+            Self::accrue_late_fees(&env, &mut bill);
+            bill
+        })
+    }
+
+    /// Amount still owed on a bill: `amount` plus late fees minus its
+    /// payments, or 0 once paid.
+    pub fn get_amount_remaining(env: Env, bill_id: u32) -> Option<i128> {
+        Self::get_bill(env, bill_id).map(|bill| Self::amount_remaining(&bill))
     }
 
     // -----------------------------------------------------------------------
@@ -562,11 +707,7 @@ impl BillPayments {
     /// When `next_cursor == 0` there are no more pages.
     pub fn get_unpaid_bills(env: Env, owner: Address, cursor: u32, limit: u32) -> BillPage {
         let limit = clamp_limit(limit);
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+        let bills = Self::load_bills(&env);
 
         let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
         for (id, bill) in bills.iter() {
@@ -591,11 +732,7 @@ impl BillPayments {
     pub fn get_all_bills_for_owner(env: Env, owner: Address, cursor: u32, limit: u32) -> BillPage {
         owner.require_auth();
         let limit = clamp_limit(limit);
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+        let bills = Self::load_bills(&env);
 
         let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
         for (id, bill) in bills.iter() {
@@ -620,11 +757,7 @@ impl BillPayments {
     pub fn get_overdue_bills(env: Env, cursor: u32, limit: u32) -> BillPage {
         let limit = clamp_limit(limit);
         let current_time = env.ledger().timestamp();
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+        let bills = Self::load_bills(&env);
 
         let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
         for (id, bill) in bills.iter() {
@@ -657,11 +790,7 @@ impl BillPayments {
         }
 
         let limit = clamp_limit(limit);
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+        let bills = Self::load_bills(&env);
 
         let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
         for (id, bill) in bills.iter() {
@@ -690,7 +819,8 @@ impl BillPayments {
         let take = if has_next { n - 1 } else { n };
 
         for i in 0..take {
-            if let Some((_, bill)) = staging.get(i) {
+            if let Some((_, mut bill)) = staging.get(i) {
+                Self::accrue_late_fees(env, &mut bill);
                 items.push_back(bill);
             }
         }
@@ -732,11 +862,7 @@ impl BillPayments {
         caller.require_auth();
 
         Self::extend_instance_ttl(&env);
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+        let mut bills = Self::load_bills(&env);
 
         let mut bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
         if bill.owner != caller {
@@ -757,11 +883,123 @@ impl BillPayments {
         Ok(())
     }
 
-    /// Get all bills (paid and unpaid)
+    /// Set or clear the on-chain payee of an unpaid bill
     ///
-    /// # Returns
-    /// Vec of all Bill structs
-    pub fn get_all_bills(env: Env) -> Vec<Bill> {
+    /// Once a payee is set, paying the bill transfers `amount` of `token`
+    /// from the payer to the payee. Clearing it returns the bill to
+    /// off-chain settlement, where paying only records the payment.
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the caller (must be the bill owner)
+    /// * `bill_id` - ID of the bill to update
+    /// * `payee` - Recipient of the payment, or `None` to clear
+    /// * `token` - Token contract to pay in; must be given exactly when `payee` is
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill with given ID doesn't exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill has already been paid
+    /// * `InvalidPayee` - If only one of `payee` and `token` is given
+    pub fn set_bill_payee(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        payee: Option<Address>,
+        token: Option<Address>,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        if payee.is_some() != token.is_some() {
+            return Err(Error::InvalidPayee);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut bills = Self::load_bills(&env);
+
+        let mut bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+
+        bill.payee = payee.clone();
+        bill.token = token;
+        bills.set(bill_id, bill);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("BILLS"), &bills);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("payee"),
+            (bill_id, payee),
+        );
+
+        Ok(())
+    }
+
+    /// Set the late-fee rule of an unpaid bill
+    ///
+    /// Fees already accrued under the previous rule are kept; the new rule
+    /// only decides what is charged from now on.
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the caller (must be the bill owner)
+    /// * `bill_id` - ID of the bill to update
+    /// * `rule` - Fee size, grace period and charge period; `LateFee::None` to stop charging
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill with given ID doesn't exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill has already been paid
+    /// * `InvalidAmount` - If a flat fee is not positive or a percentage is
+    ///   outside 1..=10000 basis points
+    pub fn set_late_fee_rule(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        rule: LateFeeRule,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        match rule.fee {
+            LateFee::Flat(fee) if fee <= 0 => return Err(Error::InvalidAmount),
+            LateFee::Percent(bps) if bps == 0 || bps > 10_000 => return Err(Error::InvalidAmount),
+            _ => {}
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut bills = Self::load_bills(&env);
+
+        let mut bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+
+        let fees_accrued = Self::accrue_late_fees(&env, &mut bill);
+        bill.late_fee_rule = rule.clone();
+        bills.set(bill_id, bill);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("BILLS"), &bills);
+        Self::adjust_unpaid_total(&env, &caller, fees_accrued);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("late_fee"),
+            (bill_id, rule),
+        );
+
+        Ok(())
+    }
+
     // -----------------------------------------------------------------------
     // Backward-compat helpers
     // -----------------------------------------------------------------------
@@ -770,11 +1008,7 @@ impl BillPayments {
     /// Only safe for owners with a small number of bills. Prefer the
     /// paginated `get_unpaid_bills` for production use.
     pub fn get_all_unpaid_bills_legacy(env: Env, owner: Address) -> Vec<Bill> {
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+        let bills = Self::load_bills(&env);
         let mut result = Vec::new(&env);
         for (_, bill) in bills.iter() {
             if !bill.paid && bill.owner == owner {
@@ -796,11 +1030,7 @@ impl BillPayments {
         limit: u32,
     ) -> ArchivedBillPage {
         let limit = clamp_limit(limit);
-        let archived: Map<u32, ArchivedBill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(&env));
+        let archived = Self::load_archived_bills(&env);
 
         let mut staging: Vec<(u32, ArchivedBill)> = Vec::new(&env);
         for (id, bill) in archived.iter() {
@@ -845,11 +1075,7 @@ impl BillPayments {
     }
 
     pub fn get_archived_bill(env: Env, bill_id: u32) -> Option<ArchivedBill> {
-        let archived: Map<u32, ArchivedBill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(&env));
+        let archived = Self::load_archived_bills(&env);
         archived.get(bill_id)
     }
 
@@ -860,16 +1086,12 @@ impl BillPayments {
     pub fn cancel_bill(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
         caller.require_auth();
         Self::require_not_paused(&env, pause_functions::CANCEL_BILL)?;
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+        let mut bills = Self::load_bills(&env);
         let bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
         if bill.owner != caller {
             return Err(Error::Unauthorized);
         }
-        let removed_unpaid_amount = if bill.paid { 0 } else { bill.amount };
+        let removed_unpaid_amount = Self::amount_remaining(&bill);
         bills.remove(bill_id);
         env.storage()
             .instance()
@@ -896,16 +1118,8 @@ impl BillPayments {
         Self::require_not_paused(&env, pause_functions::ARCHIVE)?;
         Self::extend_instance_ttl(&env);
 
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let mut archived: Map<u32, ArchivedBill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(&env));
+        let mut bills = Self::load_bills(&env);
+        let mut archived = Self::load_archived_bills(&env);
 
         let current_time = env.ledger().timestamp();
         let mut archived_count = 0u32;
@@ -918,10 +1132,13 @@ impl BillPayments {
                         id: bill.id,
                         owner: bill.owner.clone(),
                         name: bill.name.clone(),
+                        external_ref: bill.external_ref.clone(),
                         amount: bill.amount,
                         paid_at,
                         archived_at: current_time,
                         currency: bill.currency.clone(),
+                        late_fees: bill.late_fees,
+                        payments: bill.payments.clone(),
                     };
                     archived.set(id, archived_bill);
                     to_remove.push_back(id);
@@ -959,27 +1176,20 @@ impl BillPayments {
         Self::require_not_paused(&env, pause_functions::RESTORE)?;
         Self::extend_instance_ttl(&env);
 
-        let mut archived: Map<u32, ArchivedBill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(&env));
+        let mut archived = Self::load_archived_bills(&env);
         let archived_bill = archived.get(bill_id).ok_or(Error::BillNotFound)?;
 
         if archived_bill.owner != caller {
             return Err(Error::Unauthorized);
         }
 
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+        let mut bills = Self::load_bills(&env);
 
         let restored_bill = Bill {
             id: archived_bill.id,
             owner: archived_bill.owner.clone(),
             name: archived_bill.name.clone(),
+            external_ref: archived_bill.external_ref.clone(),
             amount: archived_bill.amount,
             due_date: env.ledger().timestamp() + 2592000,
             recurring: false,
@@ -989,6 +1199,11 @@ impl BillPayments {
             paid_at: Some(archived_bill.paid_at),
             schedule_id: None,
             currency: archived_bill.currency.clone(),
+            payee: None,
+            token: None,
+            payments: archived_bill.payments.clone(),
+            late_fee_rule: Self::no_late_fee(),
+            late_fees: archived_bill.late_fees,
         };
 
         bills.set(bill_id, restored_bill);
@@ -1022,11 +1237,7 @@ impl BillPayments {
         Self::require_not_paused(&env, pause_functions::ARCHIVE)?;
         Self::extend_instance_ttl(&env);
 
-        let mut archived: Map<u32, ArchivedBill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(&env));
+        let mut archived = Self::load_archived_bills(&env);
         let mut deleted_count = 0u32;
         let mut to_remove: Vec<u32> = Vec::new(&env);
 
@@ -1061,11 +1272,7 @@ impl BillPayments {
         if bill_ids.len() > (MAX_BATCH_SIZE as usize).try_into().unwrap() {
             return Err(Error::BatchTooLarge);
         }
-        let bills_map: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+        let bills_map = Self::load_bills(&env);
         for id in bill_ids.iter() {
             let bill = bills_map.get(id).ok_or(Error::BillNotFound)?;
             if bill.owner != caller {
@@ -1076,17 +1283,7 @@ impl BillPayments {
             }
         }
         Self::extend_instance_ttl(&env);
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let current_time = env.ledger().timestamp();
-        let mut next_id: u32 = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("NEXT_ID"))
-            .unwrap_or(0u32);
+        let mut bills = Self::load_bills(&env);
         let mut paid_count = 0u32;
         let mut unpaid_delta = 0i128;
         for id in bill_ids.iter() {
@@ -1094,30 +1291,11 @@ impl BillPayments {
             if bill.owner != caller || bill.paid {
                 return Err(Error::BatchValidationFailed);
             }
-            let amount = bill.amount;
-            bill.paid = true;
-            bill.paid_at = Some(current_time);
-            if bill.recurring {
-                next_id = next_id.saturating_add(1);
-                let next_due_date = bill.due_date + (bill.frequency_days as u64 * 86400);
-                let next_bill = Bill {
-                    id: next_id,
-                    owner: bill.owner.clone(),
-                    name: bill.name.clone(),
-                    amount: bill.amount,
-                    due_date: next_due_date,
-                    recurring: true,
-                    frequency_days: bill.frequency_days,
-                    paid: false,
-                    created_at: current_time,
-                    paid_at: None,
-                    schedule_id: bill.schedule_id,
-                    currency: bill.currency.clone(),
-                };
-                bills.set(next_id, next_bill);
-            } else {
-                unpaid_delta = unpaid_delta.saturating_sub(amount);
-            }
+            let fees_accrued = Self::accrue_late_fees(&env, &mut bill);
+            let remaining = Self::amount_remaining(&bill);
+            let (receipt, delta) =
+                Self::apply_payment(&env, &mut bills, &mut bill, &caller, remaining);
+            unpaid_delta = unpaid_delta.saturating_add(fees_accrued.saturating_add(delta));
             bills.set(id, bill);
             paid_count += 1;
             RemitwiseEvents::emit(
@@ -1125,18 +1303,13 @@ impl BillPayments {
                 EventCategory::Transaction,
                 EventPriority::High,
                 symbol_short!("paid"),
-                (id, caller.clone(), amount),
+                receipt,
             );
         }
-        env.storage()
-            .instance()
-            .set(&symbol_short!("NEXT_ID"), &next_id);
         env.storage()
             .instance()
             .set(&symbol_short!("BILLS"), &bills);
-        if unpaid_delta != 0 {
-            Self::adjust_unpaid_total(&env, &caller, unpaid_delta);
-        }
+        Self::adjust_unpaid_total(&env, &caller, unpaid_delta);
         Self::update_storage_stats(&env);
         RemitwiseEvents::emit(
             &env,
@@ -1148,25 +1321,22 @@ impl BillPayments {
         Ok(paid_count)
     }
 
+    /// Everything `owner` still owes, including late fees charged up to now
+    /// that have not been written to the bills yet.
     pub fn get_total_unpaid(env: Env, owner: Address) -> i128 {
-        if let Some(totals) = Self::get_unpaid_totals_map(&env) {
-            if let Some(total) = totals.get(owner.clone()) {
-                return total;
-            }
-        }
-
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let mut total = 0i128;
+        let now = env.ledger().timestamp();
+        let bills = Self::load_bills(&env);
+        let mut remaining = 0i128;
+        let mut unaccrued_fees = 0i128;
         for (_, bill) in bills.iter() {
             if !bill.paid && bill.owner == owner {
-                total += bill.amount;
+                remaining += Self::amount_remaining(&bill);
+                unaccrued_fees += Self::unaccrued_late_fees(&bill, now);
             }
         }
-        total
+
+        let cached = Self::get_unpaid_totals_map(&env).and_then(|totals| totals.get(owner));
+        cached.unwrap_or(remaining) + unaccrued_fees
     }
 
     pub fn get_storage_stats(env: Env) -> StorageStats {
@@ -1203,12 +1373,8 @@ impl BillPayments {
         cursor: u32,
         limit: u32,
     ) -> BillPage {
-        let limit = Self::clamp_limit(limit);
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+        let limit = clamp_limit(limit);
+        let bills = Self::load_bills(&env);
 
         let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
         for (id, bill) in bills.iter() {
@@ -1237,12 +1403,8 @@ impl BillPayments {
         cursor: u32,
         limit: u32,
     ) -> BillPage {
-        let limit = Self::clamp_limit(limit);
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+        let limit = clamp_limit(limit);
+        let bills = Self::load_bills(&env);
 
         let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
         for (id, bill) in bills.iter() {
@@ -1261,22 +1423,20 @@ impl BillPayments {
         Self::build_page(&env, staging, limit)
     }
 
-    /// Sum of all **unpaid** bill amounts for `owner` denominated in `currency`.
+    /// Sum of all **unpaid** bill amounts for `owner` denominated in `currency`,
+    /// including late fees charged up to now.
     ///
     /// # Example
     /// ```text
     /// let usdc_owed = client.get_total_unpaid_by_currency(&owner, &String::from_str(&env, "USDC"));
     /// ```
     pub fn get_total_unpaid_by_currency(env: Env, owner: Address, currency: String) -> i128 {
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+        let bills = Self::load_bills(&env);
         let mut total = 0i128;
         for (_, bill) in bills.iter() {
             if !bill.paid && bill.owner == owner && bill.currency == currency {
-                total += bill.amount;
+                total += Self::amount_remaining(&bill)
+                    + Self::unaccrued_late_fees(&bill, env.ledger().timestamp());
             }
         }
         total
@@ -1298,46 +1458,281 @@ impl BillPayments {
             .extend_ttl(ARCHIVE_LIFETIME_THRESHOLD, ARCHIVE_BUMP_AMOUNT);
     }
 
+    /// Load the `BILLS` map, migrating a map written in the [`LegacyBill`]
+    /// layout. The stored map is replaced on the next write.
+    fn load_bills(env: &Env) -> Map<u32, Bill> {
+        let key = symbol_short!("BILLS");
+        if !Self::is_legacy_layout(env, &key, symbol_short!("payments")) {
+            return env
+                .storage()
+                .instance()
+                .get(&key)
+                .unwrap_or_else(|| Map::new(env));
+        }
+        let legacy: Map<u32, LegacyBill> = env.storage().instance().get(&key).unwrap();
+        let mut bills = Map::new(env);
+        for (id, bill) in legacy.iter() {
+            bills.set(
+                id,
+                Bill {
+                    id: bill.id,
+                    owner: bill.owner,
+                    name: bill.name,
+                    external_ref: bill.external_ref,
+                    amount: bill.amount,
+                    due_date: bill.due_date,
+                    recurring: bill.recurring,
+                    frequency_days: bill.frequency_days,
+                    paid: bill.paid,
+                    created_at: bill.created_at,
+                    paid_at: bill.paid_at,
+                    schedule_id: bill.schedule_id,
+                    currency: bill.currency,
+                    payee: None,
+                    token: None,
+                    payments: Vec::new(env),
+                    late_fee_rule: Self::no_late_fee(),
+                    late_fees: 0,
+                },
+            );
+        }
+        bills
+    }
+
+    /// Load the `ARCH_BILL` map, migrating a map written in the
+    /// [`LegacyArchivedBill`] layout. The stored map is replaced on the next
+    /// write.
+    fn load_archived_bills(env: &Env) -> Map<u32, ArchivedBill> {
+        let key = symbol_short!("ARCH_BILL");
+        if !Self::is_legacy_layout(env, &key, symbol_short!("payments")) {
+            return env
+                .storage()
+                .instance()
+                .get(&key)
+                .unwrap_or_else(|| Map::new(env));
+        }
+        let legacy: Map<u32, LegacyArchivedBill> = env.storage().instance().get(&key).unwrap();
+        let mut archived = Map::new(env);
+        for (id, bill) in legacy.iter() {
+            archived.set(
+                id,
+                ArchivedBill {
+                    id: bill.id,
+                    owner: bill.owner,
+                    name: bill.name,
+                    external_ref: None,
+                    amount: bill.amount,
+                    paid_at: bill.paid_at,
+                    archived_at: bill.archived_at,
+                    currency: bill.currency,
+                    late_fees: 0,
+                    payments: Vec::new(env),
+                },
+            );
+        }
+        archived
+    }
+
+    /// Whether the instance map under `key` holds entries without `field`.
+    /// Every write stores the whole map in the current layout, so checking
+    /// one entry is enough.
+    fn is_legacy_layout(env: &Env, key: &Symbol, field: Symbol) -> bool {
+        let stored: Option<Map<u32, Map<Symbol, Val>>> = env.storage().instance().get(key);
+        stored
+            .and_then(|entries| entries.values().first())
+            .is_some_and(|entry| !entry.contains_key(field))
+    }
+
     fn update_storage_stats(env: &Env) {
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(env));
-        let archived: Map<u32, ArchivedBill> = env
-            .storage()
+        let bills = Self::load_bills(env);
+        let archived = Self::load_archived_bills(env);
+
+        let now = env.ledger().timestamp();
+        let mut active_count = 0u32;
+        let mut unpaid_amount = 0i128;
+        for (_, bill) in bills.iter() {
+            active_count += 1;
+            if !bill.paid {
+                unpaid_amount = unpaid_amount
+                    .saturating_add(Self::amount_remaining(&bill))
+                    .saturating_add(Self::unaccrued_late_fees(&bill, now));
+            }
+        }
+
+        let mut archived_count = 0u32;
+        let mut archived_amount = 0i128;
+        for (_, bill) in archived.iter() {
+            archived_count += 1;
+            archived_amount = archived_amount
+                .saturating_add(bill.amount)
+                .saturating_add(bill.late_fees);
+        }
+
+        let stats = StorageStats {
+            active_bills: active_count,
+            archived_bills: archived_count,
+            total_unpaid_amount: unpaid_amount,
+            total_archived_amount: archived_amount,
+            last_updated: env.ledger().timestamp(),
+        };
+
+        env.storage()
             .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(env));
+            .set(&symbol_short!("STOR_STAT"), &stats);
+    }
+    fn amount_remaining(bill: &Bill) -> i128 {
+        if bill.paid {
+            return 0;
+        }
+        let mut paid = 0i128;
+        for payment in bill.payments.iter() {
+            paid = paid.saturating_add(payment.amount);
+        }
+        bill.amount
+            .saturating_add(bill.late_fees)
+            .saturating_sub(paid)
+            .max(0)
+    }
+
+    fn no_late_fee() -> LateFeeRule {
+        LateFeeRule {
+            fee: LateFee::None,
+            grace_days: 0,
+            period_days: 0,
+        }
+    }
+
+    /// Total late fees `bill`'s rule charges by `now`.
+    fn late_fees_due(bill: &Bill, now: u64) -> i128 {
+        let rule = &bill.late_fee_rule;
+        let charge = match rule.fee {
+            LateFee::None => return 0,
+            LateFee::Flat(fee) => fee,
+            LateFee::Percent(bps) => bill.amount.saturating_mul(bps as i128) / 10_000,
+        };
+        let charged_from = bill.due_date.saturating_add(rule.grace_days as u64 * 86400);
+        if now <= charged_from {
+            return 0;
+        }
+        let charges = if rule.period_days == 0 {
+            1
+        } else {
+            (now - charged_from) / (rule.period_days as u64 * 86400) + 1
+        };
+        charge.saturating_mul(charges as i128)
+    }
+
+    /// Late fees `bill`'s rule has charged by `now` beyond those already
+    /// recorded in `bill.late_fees`.
+    fn unaccrued_late_fees(bill: &Bill, now: u64) -> i128 {
+        if bill.paid {
+            return 0;
+        }
+        (Self::late_fees_due(bill, now) - bill.late_fees).max(0)
+    }
+
+    /// Bring `bill.late_fees` up to date and return how much was added.
+    /// Fees never decrease, so changing the rule keeps what was already owed.
+    fn accrue_late_fees(env: &Env, bill: &mut Bill) -> i128 {
+        if bill.paid {
+            return 0;
+        }
+        let due = Self::late_fees_due(bill, env.ledger().timestamp());
+        if due <= bill.late_fees {
+            return 0;
+        }
+        let added = due - bill.late_fees;
+        bill.late_fees = due;
+        added
+    }
+
+    /// Record a payment of `amount` on `bill`, marking it paid once nothing
+    /// remains and queueing the next bill of a recurring series into `bills`.
+    ///
+    /// Returns the receipt and the change to the owner's unpaid total. The
+    /// caller writes `bill` and `bills` back to storage.
+    fn apply_payment(
+        env: &Env,
+        bills: &mut Map<u32, Bill>,
+        bill: &mut Bill,
+        payer: &Address,
+        amount: i128,
+    ) -> (PaymentReceipt, i128) {
+        let current_time = env.ledger().timestamp();
+        let receipt = Self::settle(env, bill, payer, amount, current_time);
+        bill.payments.push_back(BillPayment {
+            amount,
+            timestamp: current_time,
+            payer: payer.clone(),
+        });
+        let mut unpaid_delta = -amount;
+
+        if Self::amount_remaining(bill) == 0 {
+            bill.paid = true;
+            bill.paid_at = Some(current_time);
 
-        let mut active_count = 0u32;
-        let mut unpaid_amount = 0i128;
-        for (_, bill) in bills.iter() {
-            active_count += 1;
-            if !bill.paid {
-                unpaid_amount = unpaid_amount.saturating_add(bill.amount);
-            }
-        }
+            if bill.recurring {
+                let next_due_date = bill.due_date + (bill.frequency_days as u64 * 86400);
+                let next_id = env
+                    .storage()
+                    .instance()
+                    .get(&symbol_short!("NEXT_ID"))
+                    .unwrap_or(0u32)
+                    + 1;
 
-        let mut archived_count = 0u32;
-        let mut archived_amount = 0i128;
-        for (_, bill) in archived.iter() {
-            archived_count += 1;
-            archived_amount = archived_amount.saturating_add(bill.amount);
+                let next_bill = Bill {
+                    id: next_id,
+                    owner: bill.owner.clone(),
+                    name: bill.name.clone(),
+                    external_ref: bill.external_ref.clone(),
+                    amount: bill.amount,
+                    due_date: next_due_date,
+                    recurring: true,
+                    frequency_days: bill.frequency_days,
+                    paid: false,
+                    created_at: current_time,
+                    paid_at: None,
+                    schedule_id: bill.schedule_id,
+                    currency: bill.currency.clone(),
+                    payee: bill.payee.clone(),
+                    token: bill.token.clone(),
+                    payments: Vec::new(env),
+                    late_fee_rule: bill.late_fee_rule.clone(),
+                    late_fees: 0,
+                };
+                bills.set(next_id, next_bill);
+                env.storage()
+                    .instance()
+                    .set(&symbol_short!("NEXT_ID"), &next_id);
+                unpaid_delta = unpaid_delta.saturating_add(bill.amount);
+            }
         }
 
-        let stats = StorageStats {
-            active_bills: active_count,
-            archived_bills: archived_count,
-            total_unpaid_amount: unpaid_amount,
-            total_archived_amount: archived_amount,
-            last_updated: env.ledger().timestamp(),
-        };
+        (receipt, unpaid_delta)
+    }
 
-        env.storage()
-            .instance()
-            .set(&symbol_short!("STOR_STAT"), &stats);
+    /// Move `amount` from `payer` to the bill's payee when one is set and
+    /// describe the payment. Bills without a payee are settled off-chain.
+    fn settle(
+        env: &Env,
+        bill: &Bill,
+        payer: &Address,
+        amount: i128,
+        paid_at: u64,
+    ) -> PaymentReceipt {
+        if let (Some(payee), Some(token)) = (&bill.payee, &bill.token) {
+            TokenClient::new(env, token).transfer(payer, payee, &amount);
+        }
+        PaymentReceipt {
+            bill_id: bill.id,
+            payer: payer.clone(),
+            payee: bill.payee.clone(),
+            token: bill.token.clone(),
+            amount,
+            paid_at,
+        }
     }
+
     fn get_unpaid_totals_map(env: &Env) -> Option<Map<Address, i128>> {
         env.storage().instance().get(&STORAGE_UNPAID_TOTALS)
     }
@@ -1351,11 +1746,17 @@ impl BillPayments {
             .instance()
             .get(&STORAGE_UNPAID_TOTALS)
             .unwrap_or_else(|| Map::new(env));
-        let current = totals.get(owner.clone()).unwrap_or(0);
-        let next = if delta >= 0 {
-            current.saturating_add(delta)
-        } else {
-            current.saturating_sub(delta.saturating_abs())
+        let next = match totals.get(owner.clone()) {
+            Some(current) if delta >= 0 => current.saturating_add(delta),
+            Some(current) => current.saturating_sub(delta.saturating_abs()),
+            // Owners whose bills predate the cache: callers have already
+            // written the change to `BILLS`, so count from there.
+            None => Self::load_bills(env)
+                .iter()
+                .filter(|(_, bill)| !bill.paid && bill.owner == *owner)
+                .fold(0i128, |total, (_, bill)| {
+                    total.saturating_add(Self::amount_remaining(&bill))
+                }),
         };
         totals.set(owner.clone(), next);
         env.storage()
@@ -1371,6 +1772,7 @@ impl BillPayments {
 mod test {
     use super::*;
     use proptest::prelude::*;
+    use remitwise_common::MAX_PAGE_LIMIT;
     use soroban_sdk::{
         testutils::{Address as _, Ledger},
         Env, String,
@@ -1397,6 +1799,7 @@ mod test {
                 &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
                 &false,
                 &0,
+                &None,
                 &String::from_str(env, "XLM"),
             );
             ids.push_back(id);
@@ -1631,6 +2034,7 @@ mod test {
                 &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
                 &false,
                 &0,
+                &None,
                 &String::from_str(&env, "XLM"),
             );
             client.create_bill(
@@ -1640,6 +2044,7 @@ mod test {
                 &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
                 &false,
                 &0,
+                &None,
                 &String::from_str(&env, "XLM"),
             );
         }
@@ -1710,9 +2115,11 @@ mod test {
                 &owner,
                 &String::from_str(&env, "Overdue Bill"),
                 &100,
-                &due_date, // 20000
+                &due_date,
+                // 20000
                 &false,
                 &0,
+                &None,
                 &String::from_str(&env, "XLM"),
             );
         }
@@ -1768,6 +2175,7 @@ mod test {
     fn test_limit_clamped_to_max() {
         let env = make_env();
         env.mock_all_auths();
+        env.budget().reset_unlimited();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
@@ -1827,8 +2235,11 @@ mod test {
             &String::from_str(&env, "Daily Bill"),
             &100,
             &base_due_date,
-            &true, // recurring
-            &1,    // frequency_days = 1
+            &true,
+            // recurring
+            &1,
+            &None,
+            // frequency_days = 1
             &String::from_str(&env, "XLM"),
         );
 
@@ -1861,8 +2272,11 @@ mod test {
             &String::from_str(&env, "Monthly Bill"),
             &500,
             &base_due_date,
-            &true, // recurring
-            &30,   // frequency_days = 30
+            &true,
+            // recurring
+            &30,
+            &None,
+            // frequency_days = 30
             &String::from_str(&env, "XLM"),
         );
 
@@ -1898,8 +2312,11 @@ mod test {
             &String::from_str(&env, "Annual Bill"),
             &1200,
             &base_due_date,
-            &true, // recurring
-            &365,  // frequency_days = 365
+            &true,
+            // recurring
+            &365,
+            &None,
+            // frequency_days = 365
             &String::from_str(&env, "XLM"),
         );
 
@@ -1941,6 +2358,7 @@ mod test {
             &base_due_date,
             &true,
             &30,
+            &None,
             &String::from_str(&env, "XLM"),
         );
 
@@ -1970,8 +2388,11 @@ mod test {
             &String::from_str(&env, "Multi-Cycle Bill"),
             &250,
             &base_due_date,
-            &true, // recurring
-            &30,   // frequency_days = 30
+            &true,
+            // recurring
+            &30,
+            &None,
+            // frequency_days = 30
             &String::from_str(&env, "XLM"),
         );
 
@@ -2019,8 +2440,11 @@ mod test {
             &String::from_str(&env, "Three-Cycle Bill"),
             &150,
             &base_due_date,
-            &true, // recurring
-            &30,   // frequency_days = 30
+            &true,
+            // recurring
+            &30,
+            &None,
+            // frequency_days = 30
             &String::from_str(&env, "XLM"),
         );
 
@@ -2065,8 +2489,11 @@ mod test {
             &String::from_str(&env, "Early Payment Test"),
             &200,
             &base_due_date,
-            &true, // recurring
-            &30,   // frequency_days = 30
+            &true,
+            // recurring
+            &30,
+            &None,
+            // frequency_days = 30
             &String::from_str(&env, "XLM"),
         );
 
@@ -2105,6 +2532,7 @@ mod test {
             &1_000_000,
             &true,
             &frequency,
+            &None,
             &String::from_str(&env, "XLM"),
         );
 
@@ -2141,6 +2569,7 @@ mod test {
             &1_000_000,
             &true,
             &30,
+            &None,
             &String::from_str(&env, "XLM"),
         );
 
@@ -2176,6 +2605,7 @@ mod test {
             &1_000_000,
             &true,
             &30,
+            &None,
             &String::from_str(&env, "XLM"),
         );
 
@@ -2216,6 +2646,7 @@ mod test {
             &base_due,
             &true,
             &freq,
+            &None,
             &String::from_str(&env, "XLM"),
         );
 
@@ -2241,7 +2672,7 @@ mod test {
             n_future in 0usize..6usize,
         ) {
             let env = make_env();
-            env.ledger().set_timestamp(now);
+            env.ledger().set_timestamp(1);
             env.mock_all_auths();
             let cid = env.register_contract(None, BillPayments);
             let client = BillPaymentsClient::new(&env, &cid);
@@ -2256,6 +2687,8 @@ mod test {
                     &(now - 1 - i as u64),
                     &false,
                     &0,
+                    &None,
+                    &String::from_str(&env, "XLM"),
                 );
             }
 
@@ -2268,9 +2701,12 @@ mod test {
                     &(now + 1 + i as u64),
                     &false,
                     &0,
+                    &None,
+                    &String::from_str(&env, "XLM"),
                 );
             }
 
+            env.ledger().set_timestamp(now);
             let page = client.get_overdue_bills(&0, &50);
             for bill in page.items.iter() {
                 prop_assert!(bill.due_date < now, "returned bill must be past due");
@@ -2298,9 +2734,12 @@ mod test {
                     &owner,
                     &String::from_str(&env, "NotOverdue"),
                     &100,
-                    &(now + i as u64), // due_date >= now — strict less-than is required to be overdue
+                    &(now + i as u64),
+                    // due_date >= now — strict less-than is required to be overdue
                     &false,
                     &0,
+                    &None,
+                    &String::from_str(&env, "XLM"),
                 );
             }
 
@@ -2325,7 +2764,7 @@ mod test {
         ) {
             let env = make_env();
             let pay_time = base_due + pay_offset;
-            env.ledger().set_timestamp(pay_time);
+            env.ledger().set_timestamp(base_due - 1);
             env.mock_all_auths();
             let cid = env.register_contract(None, BillPayments);
             let client = BillPaymentsClient::new(&env, &cid);
@@ -2338,8 +2777,11 @@ mod test {
                 &base_due,
                 &true,
                 &freq_days,
+                &None,
+                &String::from_str(&env, "XLM"),
             );
 
+            env.ledger().set_timestamp(pay_time);
             client.pay_bill(&owner, &bill_id);
 
             let next_bill = client.get_bill(&2).unwrap();
@@ -2351,6 +2793,8 @@ mod test {
             );
             prop_assert!(!next_bill.paid, "next recurring bill must be unpaid");
         }
+    }
+
     /// Issue #102 – When pay_bill is called on a recurring bill, the contract
     /// creates the next occurrence.  This test asserts every cloned field
     /// individually so that a regression in the clone logic (e.g. paid left
@@ -2379,11 +2823,27 @@ mod test {
 
         // 3. Execution: Attempt to create bills with invalid dates
         // Added '&currency' as the final argument to both calls
-        let result_past =
-            client.try_create_bill(&owner, &name, &1000, &past_due_date, &false, &0, &currency);
+        let result_past = client.try_create_bill(
+            &owner,
+            &name,
+            &1000,
+            &past_due_date,
+            &false,
+            &0,
+            &None,
+            &currency,
+        );
 
-        let result_zero =
-            client.try_create_bill(&owner, &name, &1000, &zero_due_date, &false, &0, &currency);
+        let result_zero = client.try_create_bill(
+            &owner,
+            &name,
+            &1000,
+            &zero_due_date,
+            &false,
+            &0,
+            &None,
+            &currency,
+        );
 
         // 4. Assertions
         assert!(
@@ -2435,6 +2895,7 @@ mod test {
             &due_date,
             &false,
             &0,
+            &None,
             &String::from_str(&env, "XLM"),
         );
 
@@ -2464,6 +2925,7 @@ mod test {
             &due_date,
             &false,
             &0,
+            &None,
             &String::from_str(&env, "XLM"),
         );
 
@@ -2499,6 +2961,7 @@ mod test {
             &overdue_target,
             &false,
             &0,
+            &None,
             &String::from_str(&env, "XLM"),
         );
 
@@ -2511,6 +2974,7 @@ mod test {
             &due_now_target,
             &false,
             &0,
+            &None,
             &String::from_str(&env, "XLM"),
         );
 
@@ -2545,6 +3009,7 @@ mod test {
             &due_date,
             &false,
             &0,
+            &None,
             &String::from_str(&env, "XLM"),
         );
 
@@ -2558,4 +3023,406 @@ mod test {
             "Bill must be overdue one full day past due_date"
         );
     }
+
+    #[test]
+    fn test_pay_bill_transfers_to_payee() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&owner, &1_000);
+
+        let rent = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Rent"),
+            &600,
+            &1_000_000,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "USDC"),
+        );
+        let cash = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Market"),
+            &300,
+            &1_000_000,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "USDC"),
+        );
+        client.set_bill_payee(&owner, &rent, &Some(payee.clone()), &Some(token.clone()));
+        client.pay_bill(&owner, &rent);
+        client.pay_bill(&owner, &cash);
+
+        let balances = soroban_sdk::token::TokenClient::new(&env, &token);
+        assert_eq!(balances.balance(&owner), 400);
+        assert_eq!(balances.balance(&payee), 600);
+        assert!(client.get_bill(&cash).unwrap().paid);
+    }
+
+    #[test]
+    fn test_set_bill_payee_requires_token() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "School Fees"),
+            &500,
+            &1_000_000,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "USDC"),
+        );
+        let result =
+            client.try_set_bill_payee(&owner, &bill_id, &Some(Address::generate(&env)), &None);
+        assert_eq!(result, Err(Ok(Error::InvalidPayee)));
+        assert_eq!(client.get_bill(&bill_id).unwrap().payee, None);
+    }
+
+    #[test]
+    fn test_pay_bill_partial_tracks_installments() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "School Fees"),
+            &900,
+            &1_000_000,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "USDC"),
+        );
+        assert_eq!(client.pay_bill_partial(&owner, &bill_id, &300), 600);
+        assert_eq!(client.get_total_unpaid(&owner), 600);
+        assert_eq!(
+            client.try_pay_bill_partial(&owner, &bill_id, &700),
+            Err(Ok(Error::InvalidAmount))
+        );
+
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(!bill.paid);
+        assert_eq!(bill.payments.len(), 1);
+        assert_eq!(bill.payments.get(0).unwrap().payer, owner);
+        assert_eq!(client.get_unpaid_bills(&owner, &0, &10).count, 1);
+
+        assert_eq!(client.pay_bill_partial(&owner, &bill_id, &600), 0);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(bill.paid);
+        assert_eq!(bill.payments.len(), 2);
+        assert_eq!(client.get_amount_remaining(&bill_id), Some(0));
+        assert_eq!(client.get_total_unpaid(&owner), 0);
+    }
+
+    #[test]
+    fn test_legacy_bill_maps_are_migrated_on_load() {
+        let env = make_env();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(100);
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let usdc = String::from_str(&env, "USDC");
+
+        env.as_contract(&cid, || {
+            let mut bills: Map<u32, LegacyBill> = Map::new(&env);
+            bills.set(
+                1,
+                LegacyBill {
+                    id: 1,
+                    owner: owner.clone(),
+                    name: String::from_str(&env, "Rent"),
+                    external_ref: Some(String::from_str(&env, "ACC-1")),
+                    amount: 700,
+                    due_date: 1_000,
+                    recurring: false,
+                    frequency_days: 0,
+                    paid: false,
+                    created_at: 50,
+                    paid_at: None,
+                    schedule_id: None,
+                    currency: usdc.clone(),
+                },
+            );
+            let mut archived: Map<u32, LegacyArchivedBill> = Map::new(&env);
+            archived.set(
+                2,
+                LegacyArchivedBill {
+                    id: 2,
+                    owner: owner.clone(),
+                    name: String::from_str(&env, "Power"),
+                    amount: 300,
+                    paid_at: 60,
+                    archived_at: 90,
+                    currency: usdc.clone(),
+                },
+            );
+            let storage = env.storage().instance();
+            storage.set(&symbol_short!("BILLS"), &bills);
+            storage.set(&symbol_short!("ARCH_BILL"), &archived);
+            storage.set(&symbol_short!("NEXT_ID"), &2u32);
+        });
+
+        let bill = client.get_bill(&1).unwrap();
+        assert_eq!(bill.amount, 700);
+        assert_eq!(bill.payee, None);
+        assert_eq!(bill.payments.len(), 0);
+        assert_eq!(client.get_total_unpaid(&owner), 700);
+        let archived = client.get_archived_bill(&2).unwrap();
+        assert_eq!((archived.amount, archived.late_fees), (300, 0));
+
+        // Writes store the maps in the current layout.
+        assert_eq!(client.pay_bill_partial(&owner, &1, &200), 500);
+        client.restore_bill(&owner, &2);
+        let restored = client.get_bill(&2).unwrap();
+        assert!(restored.paid);
+        assert_eq!(restored.external_ref, None);
+        env.as_contract(&cid, || {
+            let bills: Map<u32, Bill> = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("BILLS"))
+                .unwrap();
+            assert_eq!(bills.get(1).unwrap().payments.len(), 1);
+        });
+        assert_eq!(client.get_total_unpaid(&owner), 500);
+    }
+
+    #[test]
+    fn test_pay_bill_partial_caps_installments() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Water"),
+            &1_000,
+            &1_000_000,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "USDC"),
+        );
+        for _ in 0..MAX_PAYMENTS_PER_BILL - 1 {
+            client.pay_bill_partial(&owner, &bill_id, &1);
+        }
+        assert_eq!(
+            client.try_pay_bill_partial(&owner, &bill_id, &1),
+            Err(Ok(Error::TooManyPayments))
+        );
+
+        // The last slot still takes a payment that settles the bill.
+        assert_eq!(client.pay_bill_partial(&owner, &bill_id, &951), 0);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(bill.paid);
+        assert_eq!(bill.payments.len(), MAX_PAYMENTS_PER_BILL);
+    }
+
+    #[test]
+    fn test_pay_bill_settles_only_the_remainder() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&owner, &1_000);
+
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Rent"),
+            &800,
+            &1_000_000,
+            &true,
+            &30,
+            &None,
+            &String::from_str(&env, "USDC"),
+        );
+        client.set_bill_payee(&owner, &bill_id, &Some(payee.clone()), &Some(token.clone()));
+        client.pay_bill_partial(&owner, &bill_id, &200);
+        client.pay_bill(&owner, &bill_id);
+
+        let balances = soroban_sdk::token::TokenClient::new(&env, &token);
+        assert_eq!(balances.balance(&payee), 800);
+        assert_eq!(balances.balance(&owner), 200);
+        // The next rent bill is owed in full.
+        assert_eq!(client.get_total_unpaid(&owner), 800);
+    }
+
+    #[test]
+    fn test_restore_bill_keeps_payment_history() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Water"),
+            &500,
+            &1_000_000,
+            &false,
+            &0,
+            &Some(String::from_str(&env, "ACC-42")),
+            &String::from_str(&env, "XLM"),
+        );
+        client.pay_bill_partial(&owner, &bill_id, &200);
+        client.pay_bill(&owner, &bill_id);
+
+        env.ledger().set_timestamp(10);
+        client.archive_paid_bills(&owner, &10);
+        client.restore_bill(&owner, &bill_id);
+
+        let restored = client.get_bill(&bill_id).unwrap();
+        assert!(restored.paid);
+        assert_eq!(restored.payments.len(), 2);
+        assert_eq!(restored.payments.get(0).unwrap().amount, 200);
+        assert_eq!(restored.payments.get(1).unwrap().amount, 300);
+        assert_eq!(
+            restored.external_ref,
+            Some(String::from_str(&env, "ACC-42"))
+        );
+        assert_eq!(client.get_amount_remaining(&bill_id), Some(0));
+        assert_eq!(client.get_total_unpaid(&owner), 0);
+    }
+
+    #[test]
+    fn test_late_fees_accrue_per_period_after_grace() {
+        let day = 86400u64;
+        let due_date = 1_000_000u64;
+        let env = make_env();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(due_date - day);
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&owner, &1_000);
+
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Rent"),
+            &500,
+            &due_date,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "USDC"),
+        );
+        client.set_bill_payee(&owner, &bill_id, &Some(payee.clone()), &Some(token.clone()));
+        client.set_late_fee_rule(
+            &owner,
+            &bill_id,
+            &LateFeeRule {
+                fee: LateFee::Flat(25),
+                grace_days: 2,
+                period_days: 7,
+            },
+        );
+
+        env.ledger().set_timestamp(due_date + 2 * day);
+        assert_eq!(client.get_bill(&bill_id).unwrap().late_fees, 0);
+        env.ledger().set_timestamp(due_date + 2 * day + 1);
+        assert_eq!(client.get_bill(&bill_id).unwrap().late_fees, 25);
+        env.ledger().set_timestamp(due_date + 9 * day + 1);
+        assert_eq!(client.get_bill(&bill_id).unwrap().late_fees, 50);
+        assert_eq!(client.get_amount_remaining(&bill_id), Some(550));
+        assert_eq!(client.get_total_unpaid(&owner), 550);
+        assert_eq!(
+            client.get_total_unpaid_by_currency(&owner, &String::from_str(&env, "USDC")),
+            550
+        );
+
+        client.pay_bill(&owner, &bill_id);
+        let balances = soroban_sdk::token::TokenClient::new(&env, &token);
+        assert_eq!(balances.balance(&payee), 550);
+        assert_eq!(client.get_total_unpaid(&owner), 0);
+
+        // Fees stop accruing once the bill is paid.
+        env.ledger().set_timestamp(due_date + 30 * day);
+        assert_eq!(client.get_bill(&bill_id).unwrap().late_fees, 50);
+    }
+
+    #[test]
+    fn test_percent_late_fee_charged_once_and_archived() {
+        let day = 86400u64;
+        let due_date = 1_000_000u64;
+        let env = make_env();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(due_date - day);
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "School Fees"),
+            &2_000,
+            &due_date,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "USDC"),
+        );
+        let invalid = LateFeeRule {
+            fee: LateFee::Percent(0),
+            grace_days: 0,
+            period_days: 0,
+        };
+        assert_eq!(
+            client.try_set_late_fee_rule(&owner, &bill_id, &invalid),
+            Err(Ok(Error::InvalidAmount))
+        );
+        client.set_late_fee_rule(
+            &owner,
+            &bill_id,
+            &LateFeeRule {
+                fee: LateFee::Percent(500),
+                grace_days: 0,
+                period_days: 0,
+            },
+        );
+
+        env.ledger().set_timestamp(due_date + 60 * day);
+        assert_eq!(client.get_bill(&bill_id).unwrap().late_fees, 100);
+        assert_eq!(
+            client.try_pay_bill_partial(&owner, &bill_id, &2_101),
+            Err(Ok(Error::InvalidAmount))
+        );
+        assert_eq!(client.pay_bill_partial(&owner, &bill_id, &2_000), 100);
+        assert_eq!(client.get_total_unpaid(&owner), 100);
+        client.pay_bill(&owner, &bill_id);
+
+        client.archive_paid_bills(&owner, &(due_date + 61 * day));
+        let archived = client.get_archived_bill(&bill_id).unwrap();
+        assert_eq!(archived.amount, 2_000);
+        assert_eq!(archived.late_fees, 100);
+        assert_eq!(client.get_storage_stats().total_archived_amount, 2_100);
+    }
 }
@@ -55,6 +55,7 @@ fn bench_get_total_unpaid_worst_case() {
             &1_000_000u64, // Due date is 1,000,000
             &false,
             &0u32,
+            &None,
             &String::from_str(&env, "XLM"),
         );
     }
@@ -79,12 +79,25 @@ fn stress_200_bills_single_user() {
     let due_date = 2_000_000_000u64; // far future
 
     for _ in 0..200 {
-        client.create_bill(&owner, &name, &100i128, &due_date, &false, &0u32);
+        client.create_bill(
+            &owner,
+            &name,
+            &100i128,
+            &due_date,
+            &false,
+            &0u32,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
     }
 
     // Verify aggregate total
     let total = client.get_total_unpaid(&owner);
-    assert_eq!(total, 200 * 100i128, "get_total_unpaid must sum all 200 bills");
+    assert_eq!(
+        total,
+        200 * 100i128,
+        "get_total_unpaid must sum all 200 bills"
+    );
 
     // Exhaust all pages with MAX_PAGE_LIMIT (50) — should take exactly 4 pages
     let mut collected = 0u32;
@@ -122,7 +135,16 @@ fn stress_instance_ttl_valid_after_200_bills() {
     let due_date = 2_000_000_000u64;
 
     for _ in 0..200 {
-        client.create_bill(&owner, &name, &100i128, &due_date, &false, &0u32);
+        client.create_bill(
+            &owner,
+            &name,
+            &100i128,
+            &due_date,
+            &false,
+            &0u32,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
     }
 
     let ttl = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
@@ -155,7 +177,16 @@ fn stress_bills_across_10_users() {
 
     for user in &users {
         for _ in 0..BILLS_PER_USER {
-            client.create_bill(user, &name, &AMOUNT_PER_BILL, &due_date, &false, &0u32);
+            client.create_bill(
+                user,
+                &name,
+                &AMOUNT_PER_BILL,
+                &due_date,
+                &false,
+                &0u32,
+                &None,
+                &String::from_str(&env, "XLM"),
+            );
         }
     }
 
@@ -208,7 +239,16 @@ fn stress_ttl_re_bumped_after_ledger_advancement() {
 
     // Phase 1: create 50 bills — TTL is set to INSTANCE_BUMP_AMOUNT
     for _ in 0..50 {
-        client.create_bill(&owner, &name, &100i128, &due_date, &false, &0u32);
+        client.create_bill(
+            &owner,
+            &name,
+            &100i128,
+            &due_date,
+            &false,
+            &0u32,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
     }
 
     let ttl_batch1 = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
@@ -239,7 +279,16 @@ fn stress_ttl_re_bumped_after_ledger_advancement() {
     );
 
     // Phase 3: one more create_bill triggers extend_ttl → re-bumped
-    client.create_bill(&owner, &name, &100i128, &due_date, &false, &0u32);
+    client.create_bill(
+        &owner,
+        &name,
+        &100i128,
+        &due_date,
+        &false,
+        &0u32,
+        &None,
+        &String::from_str(&env, "XLM"),
+    );
 
     let ttl_rebumped = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
     assert!(
@@ -261,7 +310,16 @@ fn stress_ttl_re_bumped_by_pay_bill_after_ledger_advancement() {
     let due_date = 2_000_000_000u64;
 
     // Create one bill to initialise instance storage
-    let bill_id = client.create_bill(&owner, &name, &500i128, &due_date, &false, &0u32);
+    let bill_id = client.create_bill(
+        &owner,
+        &name,
+        &500i128,
+        &due_date,
+        &false,
+        &0u32,
+        &None,
+        &String::from_str(&env, "XLM"),
+    );
 
     // Advance ledger so TTL drops below threshold
     env.ledger().set(LedgerInfo {
@@ -307,7 +365,16 @@ fn stress_archive_100_paid_bills() {
 
     // Create 100 bills (IDs 1..=100)
     for _ in 0..100 {
-        client.create_bill(&owner, &name, &200i128, &due_date, &false, &0u32);
+        client.create_bill(
+            &owner,
+            &name,
+            &200i128,
+            &due_date,
+            &false,
+            &0u32,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
     }
 
     // Pay all 100 bills (non-recurring, so no new bills created)
@@ -328,8 +395,14 @@ fn stress_archive_100_paid_bills() {
 
     // Verify storage stats
     let stats = client.get_storage_stats();
-    assert_eq!(stats.active_bills, 0, "No active bills should remain after full archive");
-    assert_eq!(stats.archived_bills, 100, "Storage stats must show 100 archived bills");
+    assert_eq!(
+        stats.active_bills, 0,
+        "No active bills should remain after full archive"
+    );
+    assert_eq!(
+        stats.archived_bills, 100,
+        "Storage stats must show 100 archived bills"
+    );
 
     // Verify paginated access to archived bills
     let mut archived_seen = 0u32;
@@ -381,7 +454,16 @@ fn stress_archive_across_5_users() {
     for (i, user) in users.iter().enumerate() {
         let first = next_id;
         for _ in 0..BILLS_PER_USER {
-            client.create_bill(user, &name, &100i128, &due_date, &false, &0u32);
+            client.create_bill(
+                user,
+                &name,
+                &100i128,
+                &due_date,
+                &false,
+                &0u32,
+                &None,
+                &String::from_str(&env, "XLM"),
+            );
             next_id += 1;
         }
         let last = next_id - 1;
@@ -425,7 +507,16 @@ fn bench_get_unpaid_bills_first_page_of_200() {
     let due_date = 2_000_000_000u64;
 
     for _ in 0..200 {
-        client.create_bill(&owner, &name, &100i128, &due_date, &false, &0u32);
+        client.create_bill(
+            &owner,
+            &name,
+            &100i128,
+            &due_date,
+            &false,
+            &0u32,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
     }
 
     let (cpu, mem, page) = measure(&env, || client.get_unpaid_bills(&owner, &0u32, &50u32));
@@ -450,7 +541,16 @@ fn bench_get_unpaid_bills_last_page_of_200() {
     let due_date = 2_000_000_000u64;
 
     for _ in 0..200 {
-        client.create_bill(&owner, &name, &100i128, &due_date, &false, &0u32);
+        client.create_bill(
+            &owner,
+            &name,
+            &100i128,
+            &due_date,
+            &false,
+            &0u32,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
     }
 
     // Navigate to the last page cursor
@@ -481,14 +581,24 @@ fn bench_archive_paid_bills_100() {
     let due_date = 1_700_000_000u64;
 
     for _ in 0..100 {
-        client.create_bill(&owner, &name, &100i128, &due_date, &false, &0u32);
+        client.create_bill(
+            &owner,
+            &name,
+            &100i128,
+            &due_date,
+            &false,
+            &0u32,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
     }
     for id in 1u32..=100 {
         client.pay_bill(&owner, &id);
     }
 
-    let (cpu, mem, result) =
-        measure(&env, || client.archive_paid_bills(&owner, &2_000_000_000u64));
+    let (cpu, mem, result) = measure(&env, || {
+        client.archive_paid_bills(&owner, &2_000_000_000u64)
+    });
     assert_eq!(result, 100);
 
     println!(
@@ -509,7 +619,16 @@ fn bench_get_total_unpaid_200_bills() {
     let due_date = 2_000_000_000u64;
 
     for _ in 0..200 {
-        client.create_bill(&owner, &name, &100i128, &due_date, &false, &0u32);
+        client.create_bill(
+            &owner,
+            &name,
+            &100i128,
+            &due_date,
+            &false,
+            &0u32,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
     }
 
     let expected = 200i128 * 100;
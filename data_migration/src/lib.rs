@@ -8,10 +8,20 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 
+mod scval;
+mod v1;
+
 /// Current schema version for migration compatibility.
-pub const SCHEMA_VERSION: u32 = 1;
+///
+/// Version 1 hashes the payload's JSON encoding. Version 2 hashes contract
+/// payloads over their XDR encoding, matching the on-chain snapshot
+/// checksums. Version 3 adds per-goal state to savings goals payloads.
+pub const SCHEMA_VERSION: u32 = 3;
 
 /// Minimum supported schema version for import.
+///
+/// Version 1 snapshots are verified against their JSON checksum and upgraded
+/// to the current payload layout on import.
 pub const MIN_SUPPORTED_VERSION: u32 = 1;
 
 /// Versioned migration event payload meant for indexing and historical tracking.
@@ -74,13 +84,36 @@ pub enum SnapshotPayload {
 }
 
 /// Exportable remittance split config (mirrors contract SplitConfig).
+///
+/// Addresses are strkeys (`G...`/`C...`). Every contract field is kept so the
+/// checksum can be recomputed over the same XDR the contract hashed.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RemittanceSplitExport {
     pub owner: String,
-    pub spending_percent: u32,
-    pub savings_percent: u32,
-    pub bills_percent: u32,
-    pub insurance_percent: u32,
+    pub buckets: Vec<SplitBucketExport>,
+    pub remainder_policy: RemainderPolicyExport,
+    pub change_delay: u64,
+    pub timestamp: u64,
+    pub initialized: bool,
+}
+
+/// Exportable split bucket (mirrors contract SplitBucket).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplitBucketExport {
+    pub name: String,
+    pub share_bps: u32,
+    pub destination: Option<String>,
+    pub fixed_amount: Option<i64>,
+    pub min_amount: Option<i64>,
+    pub max_amount: Option<i64>,
+}
+
+/// Exportable remainder policy (mirrors contract RemainderPolicy).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RemainderPolicyExport {
+    LargestBucket,
+    NamedBucket(String),
+    LargestRemainder,
 }
 
 /// Exportable savings goals list.
@@ -88,8 +121,12 @@ pub struct RemittanceSplitExport {
 pub struct SavingsGoalsExport {
     pub next_id: u32,
     pub goals: Vec<SavingsGoalExport>,
+    /// One per goal, in the same order as `goals`.
+    #[serde(default)]
+    pub states: Vec<GoalStateExport>,
 }
 
+/// Exportable savings goal (mirrors contract SavingsGoal).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SavingsGoalExport {
     pub id: u32,
@@ -99,19 +136,118 @@ pub struct SavingsGoalExport {
     pub current_amount: i64,
     pub target_date: u64,
     pub locked: bool,
+    pub unlock_date: Option<u64>,
+    pub tags: Vec<String>,
+    /// Custody token address; `None` for bookkeeping-only goals.
+    #[serde(default)]
+    pub token: Option<String>,
+    #[serde(default)]
+    pub lock_policy: LockPolicyExport,
+}
+
+/// Exportable per-goal state (mirrors contract GoalState).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalStateExport {
+    pub goal_id: u32,
+    pub funding: GoalFundingExport,
+    pub contributions: Vec<GoalContributionExport>,
+    pub milestones: Vec<MilestoneExport>,
+    pub interest: InterestAccountExport,
+    pub completion: CompletionActionExport,
+}
+
+/// Exportable funding priority and weight (mirrors contract GoalFunding).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GoalFundingExport {
+    pub priority: u32,
+    pub weight: u32,
+}
+
+/// Exportable goal depositor (mirrors contract GoalContribution).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GoalContributionExport {
+    pub contributor: String,
+    pub approved: bool,
+    pub total: i64,
+}
+
+/// Exportable milestone (mirrors contract Milestone).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MilestoneExport {
+    pub target: MilestoneTargetExport,
+    pub reached_at: Option<u64>,
+}
+
+/// Exportable milestone target (mirrors contract MilestoneTarget).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MilestoneTargetExport {
+    Percent(u32),
+    Amount(i64),
+}
+
+/// Exportable interest accrual state (mirrors contract InterestAccount).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterestAccountExport {
+    pub index: i64,
+    pub pending: i64,
+    pub claimed: i64,
+}
+
+/// Exportable completion action (mirrors contract CompletionAction).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompletionActionExport {
+    #[default]
+    None,
+    SweepTo(u32),
+    ReturnExcess,
+    PauseSchedules,
+}
+
+/// Exportable early-withdrawal policy (mirrors contract LockPolicy).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LockPolicyExport {
+    #[default]
+    Hard,
+    Soft(u32, PenaltyDestinationExport),
+    None,
+}
+
+/// Exportable penalty destination (mirrors contract PenaltyDestination).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PenaltyDestinationExport {
+    Recipient(String),
+    Goal(u32),
 }
 
 impl ExportSnapshot {
-    /// Compute SHA256 checksum of the payload (canonical JSON).
-    pub fn compute_checksum(&self) -> String {
-        let mut hasher = Sha256::new();
-        hasher.update(serde_json::to_vec(&self.payload).expect("payload must be serializable"));
-        hex::encode(hasher.finalize().as_ref())
+    /// Compute the hex SHA-256 checksum of the payload for the header's
+    /// schema version.
+    ///
+    /// From version 2, contract payloads are hashed over their XDR encoding,
+    /// giving the same hash as the contract's `export_snapshot`; version 2
+    /// goal payloads predate per-goal state and hash without it. Generic
+    /// payloads, and every version 1 payload, are hashed over their JSON
+    /// encoding.
+    pub fn compute_checksum(&self) -> Result<String, MigrationError> {
+        let with_states = self.header.version >= 3;
+        let bytes = match &self.payload {
+            SnapshotPayload::RemittanceSplit(config) if self.header.version > v1::VERSION => {
+                scval::split_config(config)?
+            }
+            SnapshotPayload::SavingsGoals(goals) if self.header.version > v1::VERSION => {
+                scval::savings_goals(goals, with_states)?
+            }
+            _ => serde_json::to_vec(&self.payload)
+                .map_err(|e| MigrationError::InvalidFormat(e.to_string()))?,
+        };
+        Ok(sha256_hex(&bytes))
     }
 
-    /// Verify stored checksum matches payload.
+    /// Verify stored checksum matches payload. A payload that cannot be
+    /// encoded (e.g. an invalid address) never verifies.
     pub fn verify_checksum(&self) -> bool {
-        self.header.checksum == self.compute_checksum()
+        self.compute_checksum()
+            .is_ok_and(|checksum| checksum == self.header.checksum)
     }
 
     /// Check if snapshot version is supported for import.
@@ -135,7 +271,7 @@ impl ExportSnapshot {
     }
 
     /// Build a new snapshot with correct version and checksum.
-    pub fn new(payload: SnapshotPayload, format: ExportFormat) -> Self {
+    pub fn new(payload: SnapshotPayload, format: ExportFormat) -> Result<Self, MigrationError> {
         let mut snapshot = Self {
             header: SnapshotHeader {
                 version: SCHEMA_VERSION,
@@ -145,11 +281,25 @@ impl ExportSnapshot {
             },
             payload,
         };
-        snapshot.header.checksum = snapshot.compute_checksum();
-        snapshot
+        snapshot.header.checksum = snapshot.compute_checksum()?;
+        Ok(snapshot)
     }
 }
 
+/// Hex SHA-256 digest of `bytes`.
+fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    hex::encode(hasher.finalize().as_ref())
+}
+
+/// Reads just the header, so the payload can be decoded in the layout of the
+/// snapshot's schema version.
+#[derive(Deserialize)]
+struct HeaderOnly {
+    header: SnapshotHeader,
+}
+
 fn format_label(f: ExportFormat) -> String {
     match f {
         ExportFormat::Json => "json".into(),
@@ -210,6 +360,10 @@ pub fn export_to_csv(payload: &SavingsGoalsExport) -> Result<Vec<u8>, MigrationE
         "current_amount",
         "target_date",
         "locked",
+        "unlock_date",
+        "tags",
+        "token",
+        "lock_policy",
     ])
     .map_err(|e| MigrationError::InvalidFormat(e.to_string()))?;
     for g in &payload.goals {
@@ -221,6 +375,11 @@ pub fn export_to_csv(payload: &SavingsGoalsExport) -> Result<Vec<u8>, MigrationE
             g.current_amount.to_string(),
             g.target_date.to_string(),
             g.locked.to_string(),
+            g.unlock_date.map(|d| d.to_string()).unwrap_or_default(),
+            g.tags.join(CSV_TAG_SEPARATOR),
+            g.token.clone().unwrap_or_default(),
+            serde_json::to_string(&g.lock_policy)
+                .map_err(|e| MigrationError::InvalidFormat(e.to_string()))?,
         ])
         .map_err(|e| MigrationError::InvalidFormat(e.to_string()))?;
     }
@@ -243,7 +402,16 @@ pub fn import_from_encrypted_payload(encoded: &str) -> Result<Vec<u8>, Migration
 }
 
 /// Import snapshot from JSON bytes with validation.
+///
+/// Version 1 snapshots are returned upgraded to the current schema version.
 pub fn import_from_json(bytes: &[u8]) -> Result<ExportSnapshot, MigrationError> {
+    let probe: HeaderOnly = serde_json::from_slice(bytes)
+        .map_err(|e| MigrationError::DeserializeError(e.to_string()))?;
+    if probe.header.version == v1::VERSION {
+        let snapshot: v1::ExportSnapshotV1 = serde_json::from_slice(bytes)
+            .map_err(|e| MigrationError::DeserializeError(e.to_string()))?;
+        return snapshot.upgrade();
+    }
     let snapshot: ExportSnapshot = serde_json::from_slice(bytes)
         .map_err(|e| MigrationError::DeserializeError(e.to_string()))?;
     snapshot.validate_for_import()?;
@@ -251,7 +419,17 @@ pub fn import_from_json(bytes: &[u8]) -> Result<ExportSnapshot, MigrationError>
 }
 
 /// Import snapshot from binary bytes with validation.
+///
+/// Version 1 snapshots are returned upgraded to the current schema version.
 pub fn import_from_binary(bytes: &[u8]) -> Result<ExportSnapshot, MigrationError> {
+    // The header is encoded first, so it can be read on its own.
+    let header: SnapshotHeader =
+        bincode::deserialize(bytes).map_err(|e| MigrationError::DeserializeError(e.to_string()))?;
+    if header.version == v1::VERSION {
+        let snapshot: v1::ExportSnapshotV1 = bincode::deserialize(bytes)
+            .map_err(|e| MigrationError::DeserializeError(e.to_string()))?;
+        return snapshot.upgrade();
+    }
     let snapshot: ExportSnapshot =
         bincode::deserialize(bytes).map_err(|e| MigrationError::DeserializeError(e.to_string()))?;
     snapshot.validate_for_import()?;
@@ -273,6 +451,19 @@ pub fn import_goals_from_csv(bytes: &[u8]) -> Result<Vec<SavingsGoalExport>, Mig
             current_amount: record.current_amount,
             target_date: record.target_date,
             locked: record.locked,
+            unlock_date: record.unlock_date,
+            tags: record
+                .tags
+                .split(CSV_TAG_SEPARATOR)
+                .filter(|tag| !tag.is_empty())
+                .map(str::to_owned)
+                .collect(),
+            token: record.token.filter(|token| !token.is_empty()),
+            lock_policy: match record.lock_policy.as_deref() {
+                None | Some("") => LockPolicyExport::default(),
+                Some(json) => serde_json::from_str(json)
+                    .map_err(|e| MigrationError::DeserializeError(e.to_string()))?,
+            },
         });
     }
     Ok(goals)
@@ -287,11 +478,19 @@ struct CsvGoalRow {
     current_amount: i64,
     target_date: u64,
     locked: bool,
+    unlock_date: Option<u64>,
+    tags: String,
+    token: Option<String>,
+    /// JSON-encoded `LockPolicyExport`; empty means `Hard`.
+    lock_policy: Option<String>,
 }
 
+/// Joins a goal's tags into its single CSV `tags` column.
+const CSV_TAG_SEPARATOR: &str = ";";
+
 /// Version compatibility check for migration scripts.
 pub fn check_version_compatibility(version: u32) -> Result<(), MigrationError> {
-    if version >= MIN_SUPPORTED_VERSION && version <= SCHEMA_VERSION {
+    if (MIN_SUPPORTED_VERSION..=SCHEMA_VERSION).contains(&version) {
         Ok(())
     } else {
         Err(MigrationError::IncompatibleVersion {
@@ -327,16 +526,40 @@ mod hex {
 mod tests {
     use super::*;
 
+    const OWNER: &str = "GAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQDZ7H";
+    const OTHER: &str = "GABAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEJXA";
+
+    fn split(owner: &str, shares: [u32; 4]) -> RemittanceSplitExport {
+        RemittanceSplitExport {
+            owner: owner.into(),
+            buckets: vec![
+                bucket("SPENDING", shares[0]),
+                bucket("SAVINGS", shares[1]),
+                bucket("BILLS", shares[2]),
+                bucket("INSURANCE", shares[3]),
+            ],
+            remainder_policy: RemainderPolicyExport::NamedBucket("INSURANCE".into()),
+            change_delay: 0,
+            timestamp: 0,
+            initialized: true,
+        }
+    }
+
+    fn bucket(name: &str, share_bps: u32) -> SplitBucketExport {
+        SplitBucketExport {
+            name: name.into(),
+            share_bps,
+            destination: None,
+            fixed_amount: None,
+            min_amount: None,
+            max_amount: None,
+        }
+    }
+
     #[test]
     fn snapshot_checksum_roundtrip() {
-        let payload = SnapshotPayload::RemittanceSplit(RemittanceSplitExport {
-            owner: "GABC".into(),
-            spending_percent: 50,
-            savings_percent: 30,
-            bills_percent: 15,
-            insurance_percent: 5,
-        });
-        let snapshot = ExportSnapshot::new(payload, ExportFormat::Json);
+        let payload = SnapshotPayload::RemittanceSplit(split(OWNER, [5000, 3000, 1500, 500]));
+        let snapshot = ExportSnapshot::new(payload, ExportFormat::Json).unwrap();
         assert!(snapshot.verify_checksum());
         assert!(snapshot.is_version_compatible());
         assert!(snapshot.validate_for_import().is_ok());
@@ -344,14 +567,8 @@ mod tests {
 
     #[test]
     fn export_import_json() {
-        let payload = SnapshotPayload::RemittanceSplit(RemittanceSplitExport {
-            owner: "GXYZ".into(),
-            spending_percent: 40,
-            savings_percent: 40,
-            bills_percent: 10,
-            insurance_percent: 10,
-        });
-        let snapshot = ExportSnapshot::new(payload, ExportFormat::Json);
+        let payload = SnapshotPayload::RemittanceSplit(split(OTHER, [4000, 4000, 1000, 1000]));
+        let snapshot = ExportSnapshot::new(payload, ExportFormat::Json).unwrap();
         let bytes = export_to_json(&snapshot).unwrap();
         let loaded = import_from_json(&bytes).unwrap();
         assert_eq!(loaded.header.version, SCHEMA_VERSION);
@@ -360,14 +577,8 @@ mod tests {
 
     #[test]
     fn export_import_binary() {
-        let payload = SnapshotPayload::RemittanceSplit(RemittanceSplitExport {
-            owner: "GBIN".into(),
-            spending_percent: 25,
-            savings_percent: 25,
-            bills_percent: 25,
-            insurance_percent: 25,
-        });
-        let snapshot = ExportSnapshot::new(payload, ExportFormat::Binary);
+        let payload = SnapshotPayload::RemittanceSplit(split(OWNER, [2500, 2500, 2500, 2500]));
+        let snapshot = ExportSnapshot::new(payload, ExportFormat::Binary).unwrap();
         let bytes = export_to_binary(&snapshot).unwrap();
         let loaded = import_from_binary(&bytes).unwrap();
         assert!(loaded.verify_checksum());
@@ -375,46 +586,173 @@ mod tests {
 
     #[test]
     fn checksum_mismatch_fails_import() {
-        let payload = SnapshotPayload::RemittanceSplit(RemittanceSplitExport {
-            owner: "GX".into(),
-            spending_percent: 100,
-            savings_percent: 0,
-            bills_percent: 0,
-            insurance_percent: 0,
-        });
-        let mut snapshot = ExportSnapshot::new(payload, ExportFormat::Json);
+        let payload = SnapshotPayload::RemittanceSplit(split(OWNER, [10000, 0, 0, 0]));
+        let mut snapshot = ExportSnapshot::new(payload, ExportFormat::Json).unwrap();
         snapshot.header.checksum = "wrong".into();
         assert!(!snapshot.verify_checksum());
         assert!(snapshot.validate_for_import().is_err());
     }
 
+    #[test]
+    fn checksum_tracks_every_field() {
+        let original = ExportSnapshot::new(
+            SnapshotPayload::RemittanceSplit(split(OWNER, [5000, 3000, 1500, 500])),
+            ExportFormat::Json,
+        )
+        .unwrap();
+
+        // Moving shares between buckets keeps every sum the same.
+        let mut moved = original.clone();
+        moved.payload = SnapshotPayload::RemittanceSplit(split(OWNER, [4900, 3100, 1500, 500]));
+        assert!(!moved.verify_checksum());
+
+        let mut delayed = original.clone();
+        if let SnapshotPayload::RemittanceSplit(config) = &mut delayed.payload {
+            config.change_delay = 3600;
+        }
+        assert!(!delayed.verify_checksum());
+    }
+
+    #[test]
+    fn invalid_address_is_rejected() {
+        let payload = SnapshotPayload::RemittanceSplit(split("GABC", [10000, 0, 0, 0]));
+        assert!(matches!(
+            ExportSnapshot::new(payload, ExportFormat::Json),
+            Err(MigrationError::InvalidFormat(_))
+        ));
+    }
+
     #[test]
     fn version_compatibility() {
         assert!(check_version_compatibility(1).is_ok());
+        assert!(check_version_compatibility(2).is_ok());
+        assert!(check_version_compatibility(3).is_ok());
         assert!(check_version_compatibility(SCHEMA_VERSION).is_ok());
         assert!(check_version_compatibility(0).is_err());
         assert!(check_version_compatibility(SCHEMA_VERSION + 1).is_err());
     }
 
+    /// Version 1 export of a 50/30/15/5 split, checksummed over its JSON payload.
+    const V1_SPLIT_JSON: &str = r#"{
+  "header": {
+    "version": 1,
+    "checksum": "d6a5419523729a12b83e62ad18849e9493660d3c70183a27f0d8dfa5f26399f1",
+    "format": "json",
+    "created_at_ms": null
+  },
+  "payload": {
+    "RemittanceSplit": {
+      "owner": "GAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQDZ7H",
+      "spending_percent": 50,
+      "savings_percent": 30,
+      "bills_percent": 15,
+      "insurance_percent": 5
+    }
+  }
+}"#;
+
+    #[test]
+    fn v1_json_import_is_upgraded() {
+        let loaded = import_from_json(V1_SPLIT_JSON.as_bytes()).unwrap();
+        assert_eq!(loaded.header.version, SCHEMA_VERSION);
+        assert!(loaded.verify_checksum());
+        let SnapshotPayload::RemittanceSplit(config) = &loaded.payload else {
+            panic!("expected a split payload");
+        };
+        let shares: Vec<u32> = config.buckets.iter().map(|b| b.share_bps).collect();
+        assert_eq!(shares, vec![5000, 3000, 1500, 500]);
+        assert_eq!(config.buckets[0].name, "SPENDING");
+    }
+
+    #[test]
+    fn v1_import_rejects_tampered_payload() {
+        let tampered =
+            V1_SPLIT_JSON.replace(r#""spending_percent": 50"#, r#""spending_percent": 60"#);
+        assert_eq!(
+            import_from_json(tampered.as_bytes()).unwrap_err(),
+            MigrationError::ChecksumMismatch
+        );
+    }
+
+    #[test]
+    fn v1_binary_import_is_upgraded() {
+        let json: v1::ExportSnapshotV1 = serde_json::from_str(V1_SPLIT_JSON).unwrap();
+        let mut goals = v1::ExportSnapshotV1 {
+            header: SnapshotHeader {
+                checksum: String::new(),
+                format: "binary".into(),
+                ..json.header
+            },
+            payload: v1::SnapshotPayloadV1::SavingsGoals(v1::SavingsGoalsExportV1 {
+                next_id: 2,
+                goals: vec![v1::SavingsGoalExportV1 {
+                    id: 1,
+                    owner: OWNER.into(),
+                    name: "Emergency".into(),
+                    target_amount: 1000,
+                    current_amount: 500,
+                    target_date: 2000000000,
+                    locked: true,
+                }],
+            }),
+        };
+        goals.header.checksum = sha256_hex(&serde_json::to_vec(&goals.payload).unwrap());
+
+        let loaded = import_from_binary(&bincode::serialize(&goals).unwrap()).unwrap();
+        assert_eq!(loaded.header.version, SCHEMA_VERSION);
+        assert!(loaded.verify_checksum());
+        let SnapshotPayload::SavingsGoals(export) = &loaded.payload else {
+            panic!("expected a goals payload");
+        };
+        assert_eq!(export.goals[0].name, "Emergency");
+        assert_eq!(export.goals[0].lock_policy, LockPolicyExport::Hard);
+    }
+
+    #[test]
+    fn v2_goals_checksum_omits_goal_state() {
+        let mut snapshot = ExportSnapshot::new(
+            SnapshotPayload::SavingsGoals(SavingsGoalsExport {
+                next_id: 1,
+                goals: vec![],
+                states: vec![],
+            }),
+            ExportFormat::Json,
+        )
+        .unwrap();
+        snapshot.header.version = 2;
+        assert!(!snapshot.verify_checksum());
+        snapshot.header.checksum = snapshot.compute_checksum().unwrap();
+        assert!(snapshot.validate_for_import().is_ok());
+    }
+
     #[test]
     fn csv_export_import_goals() {
         let export = SavingsGoalsExport {
             next_id: 2,
             goals: vec![SavingsGoalExport {
                 id: 1,
-                owner: "G1".into(),
+                owner: OWNER.into(),
                 name: "Emergency".into(),
                 target_amount: 1000,
                 current_amount: 500,
                 target_date: 2000000000,
                 locked: true,
+                unlock_date: Some(2100000000),
+                tags: vec!["family".into(), "urgent".into()],
+                token: Some(OTHER.into()),
+                lock_policy: LockPolicyExport::Soft(250, PenaltyDestinationExport::Goal(2)),
             }],
+            states: vec![],
         };
         let csv_bytes = export_to_csv(&export).unwrap();
         let goals = import_goals_from_csv(&csv_bytes).unwrap();
         assert_eq!(goals.len(), 1);
         assert_eq!(goals[0].name, "Emergency");
         assert_eq!(goals[0].target_amount, 1000);
+        assert_eq!(goals[0].unlock_date, Some(2100000000));
+        assert_eq!(goals[0].tags, export.goals[0].tags);
+        assert_eq!(goals[0].token, export.goals[0].token);
+        assert_eq!(goals[0].lock_policy, export.goals[0].lock_policy);
     }
 
     #[test]
@@ -430,7 +768,7 @@ mod tests {
         let json = serde_json::to_string(&event).unwrap();
         assert!(json.contains(r#""V1":{"#));
         assert!(json.contains(r#""contract_id":"CABCD""#));
-        assert!(json.contains(r#""version":1"#));
+        assert!(json.contains(&format!(r#""version":{}"#, SCHEMA_VERSION)));
 
         let loaded: MigrationEvent = serde_json::from_str(&json).unwrap();
         assert_eq!(event, loaded);
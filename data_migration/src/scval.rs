@@ -0,0 +1,238 @@
+//! XDR encoding of contract payloads, matching what `#[contracttype]`
+//! produces on-chain so checksums agree with `sha256(payload.to_xdr())`.
+//!
+//! Structs are maps keyed by field name in sorted order, enum variants are
+//! vectors that start with the variant name, `None` is `Void` and amounts are
+//! `i128`.
+
+use crate::{
+    CompletionActionExport, GoalStateExport, LockPolicyExport, MigrationError,
+    MilestoneTargetExport, PenaltyDestinationExport, RemainderPolicyExport, RemittanceSplitExport,
+    SavingsGoalExport, SavingsGoalsExport, SplitBucketExport,
+};
+use std::str::FromStr;
+use stellar_xdr::curr::{
+    Int128Parts, Limits, ScAddress, ScMap, ScMapEntry, ScString, ScSymbol, ScVal, ScVec, WriteXdr,
+};
+
+/// XDR bytes of a remittance_split `SplitConfig`.
+pub(crate) fn split_config(export: &RemittanceSplitExport) -> Result<Vec<u8>, MigrationError> {
+    let buckets = export
+        .buckets
+        .iter()
+        .map(bucket)
+        .collect::<Result<Vec<_>, _>>()?;
+    let config = structure(vec![
+        ("owner", address(&export.owner)?),
+        ("buckets", list(buckets)?),
+        (
+            "remainder_policy",
+            remainder_policy(&export.remainder_policy)?,
+        ),
+        ("change_delay", ScVal::U64(export.change_delay)),
+        ("timestamp", ScVal::U64(export.timestamp)),
+        ("initialized", ScVal::Bool(export.initialized)),
+    ])?;
+    encode(&config)
+}
+
+/// XDR bytes of the savings_goals `(next_id, goals, states)` tuple, or the
+/// `(next_id, goals)` pair that schema version 2 hashed when `with_states` is
+/// false.
+pub(crate) fn savings_goals(
+    export: &SavingsGoalsExport,
+    with_states: bool,
+) -> Result<Vec<u8>, MigrationError> {
+    let goals = export
+        .goals
+        .iter()
+        .map(goal)
+        .collect::<Result<Vec<_>, _>>()?;
+    let states = export
+        .states
+        .iter()
+        .map(goal_state)
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut tuple = vec![ScVal::U32(export.next_id), list(goals)?];
+    if with_states {
+        tuple.push(list(states)?);
+    }
+    encode(&list(tuple)?)
+}
+
+fn bucket(bucket: &SplitBucketExport) -> Result<ScVal, MigrationError> {
+    structure(vec![
+        ("name", symbol(&bucket.name)?),
+        ("share_bps", ScVal::U32(bucket.share_bps)),
+        (
+            "destination",
+            optional(bucket.destination.as_deref().map(address).transpose()?),
+        ),
+        ("fixed_amount", optional(bucket.fixed_amount.map(amount))),
+        ("min_amount", optional(bucket.min_amount.map(amount))),
+        ("max_amount", optional(bucket.max_amount.map(amount))),
+    ])
+}
+
+fn remainder_policy(policy: &RemainderPolicyExport) -> Result<ScVal, MigrationError> {
+    match policy {
+        RemainderPolicyExport::LargestBucket => list(vec![symbol("LargestBucket")?]),
+        RemainderPolicyExport::NamedBucket(name) => {
+            list(vec![symbol("NamedBucket")?, symbol(name)?])
+        }
+        RemainderPolicyExport::LargestRemainder => list(vec![symbol("LargestRemainder")?]),
+    }
+}
+
+fn goal(goal: &SavingsGoalExport) -> Result<ScVal, MigrationError> {
+    let tags = goal
+        .tags
+        .iter()
+        .map(|tag| string(tag))
+        .collect::<Result<Vec<_>, _>>()?;
+    structure(vec![
+        ("id", ScVal::U32(goal.id)),
+        ("owner", address(&goal.owner)?),
+        ("name", string(&goal.name)?),
+        ("target_amount", amount(goal.target_amount)),
+        ("current_amount", amount(goal.current_amount)),
+        ("target_date", ScVal::U64(goal.target_date)),
+        ("locked", ScVal::Bool(goal.locked)),
+        ("unlock_date", optional(goal.unlock_date.map(ScVal::U64))),
+        ("tags", list(tags)?),
+        (
+            "token",
+            optional(goal.token.as_deref().map(address).transpose()?),
+        ),
+        ("lock_policy", lock_policy(&goal.lock_policy)?),
+    ])
+}
+
+fn goal_state(state: &GoalStateExport) -> Result<ScVal, MigrationError> {
+    let funding = structure(vec![
+        ("priority", ScVal::U32(state.funding.priority)),
+        ("weight", ScVal::U32(state.funding.weight)),
+    ])?;
+    let contributions = state
+        .contributions
+        .iter()
+        .map(|entry| {
+            structure(vec![
+                ("contributor", address(&entry.contributor)?),
+                ("approved", ScVal::Bool(entry.approved)),
+                ("total", amount(entry.total)),
+            ])
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let milestones = state
+        .milestones
+        .iter()
+        .map(|milestone| {
+            let target = match &milestone.target {
+                MilestoneTargetExport::Percent(percent) => {
+                    list(vec![symbol("Percent")?, ScVal::U32(*percent)])?
+                }
+                MilestoneTargetExport::Amount(value) => {
+                    list(vec![symbol("Amount")?, amount(*value)])?
+                }
+            };
+            structure(vec![
+                ("target", target),
+                ("reached_at", optional(milestone.reached_at.map(ScVal::U64))),
+            ])
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let interest = structure(vec![
+        ("index", amount(state.interest.index)),
+        ("pending", amount(state.interest.pending)),
+        ("claimed", amount(state.interest.claimed)),
+    ])?;
+    let completion = match &state.completion {
+        CompletionActionExport::None => list(vec![symbol("None")?])?,
+        CompletionActionExport::SweepTo(goal_id) => {
+            list(vec![symbol("SweepTo")?, ScVal::U32(*goal_id)])?
+        }
+        CompletionActionExport::ReturnExcess => list(vec![symbol("ReturnExcess")?])?,
+        CompletionActionExport::PauseSchedules => list(vec![symbol("PauseSchedules")?])?,
+    };
+    structure(vec![
+        ("goal_id", ScVal::U32(state.goal_id)),
+        ("funding", funding),
+        ("contributions", list(contributions)?),
+        ("milestones", list(milestones)?),
+        ("interest", interest),
+        ("completion", completion),
+    ])
+}
+
+fn lock_policy(policy: &LockPolicyExport) -> Result<ScVal, MigrationError> {
+    match policy {
+        LockPolicyExport::Hard => list(vec![symbol("Hard")?]),
+        LockPolicyExport::Soft(penalty_bps, destination) => {
+            let destination = match destination {
+                PenaltyDestinationExport::Recipient(recipient) => {
+                    list(vec![symbol("Recipient")?, address(recipient)?])?
+                }
+                PenaltyDestinationExport::Goal(goal_id) => {
+                    list(vec![symbol("Goal")?, ScVal::U32(*goal_id)])?
+                }
+            };
+            list(vec![symbol("Soft")?, ScVal::U32(*penalty_bps), destination])
+        }
+        LockPolicyExport::None => list(vec![symbol("None")?]),
+    }
+}
+
+fn structure(mut fields: Vec<(&str, ScVal)>) -> Result<ScVal, MigrationError> {
+    fields.sort_by_key(|(name, _)| *name);
+    let entries = fields
+        .into_iter()
+        .map(|(name, val)| {
+            Ok(ScMapEntry {
+                key: symbol(name)?,
+                val,
+            })
+        })
+        .collect::<Result<Vec<_>, MigrationError>>()?;
+    Ok(ScVal::Map(Some(ScMap(
+        entries.try_into().map_err(invalid)?,
+    ))))
+}
+
+fn list(items: Vec<ScVal>) -> Result<ScVal, MigrationError> {
+    Ok(ScVal::Vec(Some(ScVec(items.try_into().map_err(invalid)?))))
+}
+
+fn optional(val: Option<ScVal>) -> ScVal {
+    val.unwrap_or(ScVal::Void)
+}
+
+fn amount(value: i64) -> ScVal {
+    let value = i128::from(value);
+    ScVal::I128(Int128Parts {
+        hi: (value >> 64) as i64,
+        lo: value as u64,
+    })
+}
+
+fn address(strkey: &str) -> Result<ScVal, MigrationError> {
+    ScAddress::from_str(strkey)
+        .map(ScVal::Address)
+        .map_err(|_| MigrationError::InvalidFormat(format!("invalid address: {}", strkey)))
+}
+
+fn symbol(name: &str) -> Result<ScVal, MigrationError> {
+    Ok(ScVal::Symbol(ScSymbol(name.try_into().map_err(invalid)?)))
+}
+
+fn string(value: &str) -> Result<ScVal, MigrationError> {
+    Ok(ScVal::String(ScString(value.try_into().map_err(invalid)?)))
+}
+
+fn encode(val: &ScVal) -> Result<Vec<u8>, MigrationError> {
+    val.to_xdr(Limits::none()).map_err(invalid)
+}
+
+fn invalid(e: impl std::fmt::Display) -> MigrationError {
+    MigrationError::InvalidFormat(e.to_string())
+}
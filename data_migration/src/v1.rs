@@ -0,0 +1,135 @@
+//! Schema version 1 snapshots, written before contract payloads carried
+//! named buckets and were hashed over XDR.
+//!
+//! Their checksum is the SHA-256 of the payload's JSON encoding. Imports
+//! verify that checksum against the original layout, then upgrade the payload
+//! and stamp it with the current schema version and checksum.
+
+use crate::{
+    ExportSnapshot, LockPolicyExport, MigrationError, RemainderPolicyExport, RemittanceSplitExport,
+    SavingsGoalExport, SavingsGoalsExport, SnapshotHeader, SnapshotPayload, SplitBucketExport,
+    SCHEMA_VERSION,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Header version of snapshots read through this module.
+pub(crate) const VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ExportSnapshotV1 {
+    pub header: SnapshotHeader,
+    pub payload: SnapshotPayloadV1,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) enum SnapshotPayloadV1 {
+    RemittanceSplit(RemittanceSplitExportV1),
+    SavingsGoals(SavingsGoalsExportV1),
+    Generic(HashMap<String, serde_json::Value>),
+}
+
+/// Four whole-percentage shares, as the contract stored them before buckets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RemittanceSplitExportV1 {
+    pub owner: String,
+    pub spending_percent: u32,
+    pub savings_percent: u32,
+    pub bills_percent: u32,
+    pub insurance_percent: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SavingsGoalsExportV1 {
+    pub next_id: u32,
+    pub goals: Vec<SavingsGoalExportV1>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SavingsGoalExportV1 {
+    pub id: u32,
+    pub owner: String,
+    pub name: String,
+    pub target_amount: i64,
+    pub current_amount: i64,
+    pub target_date: u64,
+    pub locked: bool,
+}
+
+impl ExportSnapshotV1 {
+    /// Verify the JSON checksum and convert to a current-version snapshot.
+    pub(crate) fn upgrade(self) -> Result<ExportSnapshot, MigrationError> {
+        let bytes = serde_json::to_vec(&self.payload)
+            .map_err(|e| MigrationError::InvalidFormat(e.to_string()))?;
+        if crate::sha256_hex(&bytes) != self.header.checksum {
+            return Err(MigrationError::ChecksumMismatch);
+        }
+        let mut snapshot = ExportSnapshot {
+            header: SnapshotHeader {
+                version: SCHEMA_VERSION,
+                checksum: String::new(),
+                ..self.header
+            },
+            payload: match self.payload {
+                SnapshotPayloadV1::RemittanceSplit(config) => {
+                    SnapshotPayload::RemittanceSplit(split_config(config))
+                }
+                SnapshotPayloadV1::SavingsGoals(goals) => {
+                    SnapshotPayload::SavingsGoals(savings_goals(goals))
+                }
+                SnapshotPayloadV1::Generic(map) => SnapshotPayload::Generic(map),
+            },
+        };
+        snapshot.header.checksum = snapshot.compute_checksum()?;
+        Ok(snapshot)
+    }
+}
+
+/// Same buckets and remainder policy the contract gives a pre-bucket config.
+fn split_config(config: RemittanceSplitExportV1) -> RemittanceSplitExport {
+    let bucket = |name: &str, percent: u32| SplitBucketExport {
+        name: name.into(),
+        share_bps: percent * 100,
+        destination: None,
+        fixed_amount: None,
+        min_amount: None,
+        max_amount: None,
+    };
+    RemittanceSplitExport {
+        owner: config.owner,
+        buckets: vec![
+            bucket("SPENDING", config.spending_percent),
+            bucket("SAVINGS", config.savings_percent),
+            bucket("BILLS", config.bills_percent),
+            bucket("INSURANCE", config.insurance_percent),
+        ],
+        remainder_policy: RemainderPolicyExport::NamedBucket("INSURANCE".into()),
+        change_delay: 0,
+        timestamp: 0,
+        initialized: true,
+    }
+}
+
+fn savings_goals(export: SavingsGoalsExportV1) -> SavingsGoalsExport {
+    SavingsGoalsExport {
+        next_id: export.next_id,
+        goals: export
+            .goals
+            .into_iter()
+            .map(|goal| SavingsGoalExport {
+                id: goal.id,
+                owner: goal.owner,
+                name: goal.name,
+                target_amount: goal.target_amount,
+                current_amount: goal.current_amount,
+                target_date: goal.target_date,
+                locked: goal.locked,
+                unlock_date: None,
+                tags: Vec::new(),
+                token: None,
+                lock_policy: LockPolicyExport::default(),
+            })
+            .collect(),
+        states: Vec::new(),
+    }
+}
@@ -0,0 +1,224 @@
+#![cfg(test)]
+
+//! Round trip between on-chain snapshots and `data_migration`: the checksum
+//! a contract exports must equal the one computed off-chain for the same data.
+
+use data_migration::{
+    CompletionActionExport, ExportFormat, ExportSnapshot, GoalContributionExport,
+    GoalFundingExport, GoalStateExport, InterestAccountExport, LockPolicyExport, MilestoneExport,
+    MilestoneTargetExport, PenaltyDestinationExport, RemainderPolicyExport, RemittanceSplitExport,
+    SavingsGoalExport, SavingsGoalsExport, SnapshotPayload, SplitBucketExport,
+};
+use remittance_split::{RemainderPolicy, RemittanceSplit, RemittanceSplitClient};
+use savings_goals::{
+    CompletionAction, GoalState, LockPolicy, MilestoneTarget, PenaltyDestination,
+    SavingsGoalContract, SavingsGoalContractClient,
+};
+use soroban_sdk::{
+    testutils::Address as _, token::StellarAssetClient, xdr::ScAddress, Address, BytesN, Env,
+    String as SorobanString,
+};
+
+fn strkey(address: &Address) -> String {
+    ScAddress::from(address).to_string()
+}
+
+fn text(value: &SorobanString) -> String {
+    let mut buf = vec![0u8; value.len() as usize];
+    value.copy_into_slice(&mut buf);
+    String::from_utf8(buf).unwrap()
+}
+
+fn amount(value: i128) -> i64 {
+    i64::try_from(value).unwrap()
+}
+
+fn lock_policy(policy: &LockPolicy) -> LockPolicyExport {
+    match policy {
+        LockPolicy::Hard => LockPolicyExport::Hard,
+        LockPolicy::Soft(penalty_bps, destination) => LockPolicyExport::Soft(
+            *penalty_bps,
+            match destination {
+                PenaltyDestination::Recipient(recipient) => {
+                    PenaltyDestinationExport::Recipient(strkey(recipient))
+                }
+                PenaltyDestination::Goal(goal_id) => PenaltyDestinationExport::Goal(*goal_id),
+            },
+        ),
+        LockPolicy::None => LockPolicyExport::None,
+    }
+}
+
+fn goal_state(state: &GoalState) -> GoalStateExport {
+    GoalStateExport {
+        goal_id: state.goal_id,
+        funding: GoalFundingExport {
+            priority: state.funding.priority,
+            weight: state.funding.weight,
+        },
+        contributions: state
+            .contributions
+            .iter()
+            .map(|entry| GoalContributionExport {
+                contributor: strkey(&entry.contributor),
+                approved: entry.approved,
+                total: amount(entry.total),
+            })
+            .collect(),
+        milestones: state
+            .milestones
+            .iter()
+            .map(|milestone| MilestoneExport {
+                target: match milestone.target {
+                    MilestoneTarget::Percent(percent) => MilestoneTargetExport::Percent(percent),
+                    MilestoneTarget::Amount(value) => MilestoneTargetExport::Amount(amount(value)),
+                },
+                reached_at: milestone.reached_at,
+            })
+            .collect(),
+        interest: InterestAccountExport {
+            index: amount(state.interest.index),
+            pending: amount(state.interest.pending),
+            claimed: amount(state.interest.claimed),
+        },
+        completion: match state.completion {
+            CompletionAction::None => CompletionActionExport::None,
+            CompletionAction::SweepTo(goal_id) => CompletionActionExport::SweepTo(goal_id),
+            CompletionAction::ReturnExcess => CompletionActionExport::ReturnExcess,
+            CompletionAction::PauseSchedules => CompletionActionExport::PauseSchedules,
+        },
+    }
+}
+
+fn hex(checksum: &BytesN<32>) -> String {
+    checksum
+        .to_array()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[test]
+fn test_split_snapshot_checksum_matches_off_chain() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.set_remainder_policy(&owner, &1, &RemainderPolicy::LargestRemainder);
+    let snapshot = client.export_snapshot(&owner).unwrap();
+    let config = &snapshot.config;
+
+    let export = RemittanceSplitExport {
+        owner: strkey(&config.owner),
+        buckets: config
+            .buckets
+            .iter()
+            .map(|bucket| SplitBucketExport {
+                name: bucket.name.to_string(),
+                share_bps: bucket.share_bps,
+                destination: bucket.destination.as_ref().map(strkey),
+                fixed_amount: bucket.fixed_amount.map(amount),
+                min_amount: bucket.min_amount.map(amount),
+                max_amount: bucket.max_amount.map(amount),
+            })
+            .collect(),
+        remainder_policy: match &config.remainder_policy {
+            RemainderPolicy::LargestBucket => RemainderPolicyExport::LargestBucket,
+            RemainderPolicy::NamedBucket(name) => {
+                RemainderPolicyExport::NamedBucket(name.to_string())
+            }
+            RemainderPolicy::LargestRemainder => RemainderPolicyExport::LargestRemainder,
+        },
+        change_delay: config.change_delay,
+        timestamp: config.timestamp,
+        initialized: config.initialized,
+    };
+    let off_chain =
+        ExportSnapshot::new(SnapshotPayload::RemittanceSplit(export), ExportFormat::Json).unwrap();
+
+    assert_eq!(off_chain.header.checksum, hex(&snapshot.checksum));
+}
+
+#[test]
+fn test_goals_snapshot_checksum_matches_off_chain() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    let goal_id = client.create_goal(
+        &owner,
+        &SorobanString::from_str(&env, "Education Fund"),
+        &10_000,
+        &2_000_000_000,
+    );
+    client.add_to_goal(&owner, &goal_id, &2_500);
+    client.set_goal_funding(&owner, &goal_id, &1, &3);
+    client.set_goal_milestones(
+        &owner,
+        &goal_id,
+        &soroban_sdk::vec![
+            &env,
+            MilestoneTarget::Percent(20),
+            MilestoneTarget::Amount(5_000)
+        ],
+    );
+    client.add_tags_to_goal(
+        &owner,
+        &goal_id,
+        &soroban_sdk::vec![&env, SorobanString::from_str(&env, "school")],
+    );
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&owner, &1_000);
+    let held = client.create_token_goal(
+        &owner,
+        &SorobanString::from_str(&env, "Rainy Day"),
+        &5_000,
+        &2_100_000_000,
+        &token,
+    );
+    client.add_to_goal(&owner, &held, &750);
+    client.set_lock_policy(
+        &owner,
+        &goal_id,
+        &LockPolicy::Soft(500, PenaltyDestination::Recipient(Address::generate(&env))),
+    );
+    let snapshot = client.export_snapshot(&owner);
+
+    let export = SavingsGoalsExport {
+        next_id: snapshot.next_id,
+        goals: snapshot
+            .goals
+            .iter()
+            .map(|goal| SavingsGoalExport {
+                id: goal.id,
+                owner: strkey(&goal.owner),
+                name: text(&goal.name),
+                target_amount: amount(goal.target_amount),
+                current_amount: amount(goal.current_amount),
+                target_date: goal.target_date,
+                locked: goal.locked,
+                unlock_date: goal.unlock_date,
+                tags: goal.tags.iter().map(|tag| text(&tag)).collect(),
+                token: goal.token.as_ref().map(strkey),
+                lock_policy: lock_policy(&goal.lock_policy),
+            })
+            .collect(),
+        states: snapshot
+            .states
+            .iter()
+            .map(|state| goal_state(&state))
+            .collect(),
+    };
+    let off_chain =
+        ExportSnapshot::new(SnapshotPayload::SavingsGoals(export), ExportFormat::Json).unwrap();
+
+    assert_eq!(off_chain.header.checksum, hex(&snapshot.checksum));
+}
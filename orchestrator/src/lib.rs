@@ -95,19 +95,37 @@ pub trait FamilyWalletTrait {
 /// Gas estimation: ~3000 gas per split calculation
 #[contractclient(name = "RemittanceSplitClient")]
 pub trait RemittanceSplitTrait {
-    /// Calculate split amounts from a total remittance amount
+    /// Split a total remittance amount across the owner's buckets
     ///
     /// # Arguments
+    /// * `owner` - Owner whose split configuration is applied
     /// * `total_amount` - The total amount to split (must be positive)
     ///
     /// # Returns
-    /// Vec containing [spending, savings, bills, insurance] amounts
+    /// Vec of named allocations, one per bucket in the owner's configuration
     ///
     /// # Gas Estimation
     /// ~3000 gas
-    fn calculate_split(env: Env, total_amount: i128) -> Vec<i128>;
+    fn get_split_allocations(env: Env, owner: Address, total_amount: i128) -> Vec<Allocation>;
 }
 
+/// Amount assigned to one named bucket, as returned by the Remittance Split contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Allocation {
+    pub category: Symbol,
+    pub amount: i128,
+}
+
+/// Buckets the remittance flow needs from the owner's split, in the order
+/// `extract_allocations` returns them.
+const REQUIRED_BUCKETS: [Symbol; 4] = [
+    symbol_short!("SPENDING"),
+    symbol_short!("SAVINGS"),
+    symbol_short!("BILLS"),
+    symbol_short!("INSURANCE"),
+];
+
 /// Savings Goals contract client interface
 ///
 /// Manages goal-based savings with target dates.
@@ -192,6 +210,8 @@ pub enum OrchestratorError {
     InvalidContractAddress = 8,
     /// Generic cross-contract call failure
     CrossContractCallFailed = 9,
+    /// Owner's split has no SPENDING, SAVINGS, BILLS or INSURANCE bucket
+    MissingSplitBucket = 10,
 }
 
 /// Result of a complete remittance flow execution
@@ -391,11 +411,13 @@ impl Orchestrator {
     /// # Arguments
     /// * `env` - The contract environment
     /// * `remittance_split_addr` - Address of the Remittance Split contract
+    /// * `owner` - Owner whose split configuration is applied
     /// * `total_amount` - Total remittance amount to split (must be positive)
     ///
     /// # Returns
     /// Ok(Vec<i128>) containing [spending, savings, bills, insurance] amounts
-    /// Err(OrchestratorError) if validation fails or cross-contract call fails
+    /// Err(OrchestratorError) if validation fails, the cross-contract call fails,
+    /// or one of the four buckets is missing from the owner's split
     ///
     /// # Gas Estimation
     /// ~3000 gas for cross-contract split calculation
@@ -403,11 +425,12 @@ impl Orchestrator {
     /// # Cross-Contract Call Flow
     /// 1. Validate that total_amount is positive
     /// 2. Create RemittanceSplitClient instance
-    /// 3. Call calculate_split via cross-contract call
-    /// 4. Return the allocation vector
+    /// 3. Call get_split_allocations via cross-contract call
+    /// 4. Look up each required bucket by name and return the amounts
     fn extract_allocations(
         env: &Env,
         remittance_split_addr: &Address,
+        owner: &Address,
         total_amount: i128,
     ) -> Result<Vec<i128>, OrchestratorError> {
         // Validate amount is positive
@@ -419,11 +442,24 @@ impl Orchestrator {
         let split_client = RemittanceSplitClient::new(env, remittance_split_addr);
 
         // Gas estimation: ~3000 gas
-        // Call the remittance split contract to calculate allocations
-        // This returns Vec<i128> with [spending, savings, bills, insurance]
-        let allocations = split_client.calculate_split(&total_amount);
+        // Call the remittance split contract to calculate named allocations
+        let allocations = match split_client.try_get_split_allocations(owner, &total_amount) {
+            Ok(Ok(allocations)) => allocations,
+            _ => return Err(OrchestratorError::RemittanceSplitFailed),
+        };
 
-        Ok(allocations)
+        // Buckets can be reordered or extended, so match them by name
+        let mut amounts = Vec::new(env);
+        for bucket in REQUIRED_BUCKETS.iter() {
+            let amount = allocations
+                .iter()
+                .find(|allocation| allocation.category == *bucket)
+                .map(|allocation| allocation.amount)
+                .ok_or(OrchestratorError::MissingSplitBucket)?;
+            amounts.push_back(amount);
+        }
+
+        Ok(amounts)
     }
 
     // ============================================================================
@@ -978,13 +1014,20 @@ impl Orchestrator {
         )?;
 
         // Step 4: Extract allocations from remittance split
-        let allocations = Self::extract_allocations(&env, &remittance_split_addr, total_amount)
-            .map_err(|e| {
-                Self::emit_error_event(&env, &caller, symbol_short!("split"), e as u32, timestamp);
-                e
-            })?;
+        let allocations =
+            Self::extract_allocations(&env, &remittance_split_addr, &caller, total_amount)
+                .map_err(|e| {
+                    Self::emit_error_event(
+                        &env,
+                        &caller,
+                        symbol_short!("split"),
+                        e as u32,
+                        timestamp,
+                    );
+                    e
+                })?;
 
-        // Extract individual amounts
+        // Amounts were matched by bucket name and come back in REQUIRED_BUCKETS order
         let spending_amount = allocations.get(0).unwrap_or(0);
         let savings_amount = allocations.get(1).unwrap_or(0);
         let bills_amount = allocations.get(2).unwrap_or(0);
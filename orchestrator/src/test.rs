@@ -1,7 +1,9 @@
 // Integration tests for the orchestrator contract
 
-use crate::{Orchestrator, OrchestratorClient, OrchestratorError};
-use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Env, Vec};
+use crate::{Allocation, Orchestrator, OrchestratorClient, OrchestratorError};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, testutils::Address as _, Address, Env, Symbol, Vec,
+};
 
 // ============================================================================
 // Mock Contract Implementations
@@ -26,18 +28,56 @@ pub struct MockRemittanceSplit;
 
 #[contractimpl]
 impl MockRemittanceSplit {
-    /// Mock implementation of calculate_split
-    /// Returns [40%, 30%, 20%, 10%] split
-    pub fn calculate_split(env: Env, total_amount: i128) -> Vec<i128> {
-        let spending = (total_amount * 40) / 100;
-        let savings = (total_amount * 30) / 100;
-        let bills = (total_amount * 20) / 100;
-        let insurance = (total_amount * 10) / 100;
-
-        Vec::from_array(&env, [spending, savings, bills, insurance])
+    /// Mock implementation of get_split_allocations
+    /// Returns a 40% / 30% / 20% / 10% split with the buckets listed out of
+    /// their usual order, plus an extra bucket the orchestrator ignores
+    pub fn get_split_allocations(env: Env, _owner: Address, total_amount: i128) -> Vec<Allocation> {
+        Vec::from_array(
+            &env,
+            [
+                allocation(symbol_short!("INSURANCE"), (total_amount * 10) / 100),
+                allocation(symbol_short!("SAVINGS"), (total_amount * 30) / 100),
+                allocation(symbol_short!("SPENDING"), (total_amount * 40) / 100),
+                allocation(symbol_short!("BILLS"), (total_amount * 20) / 100),
+                allocation(symbol_short!("SCHOOL"), 0),
+            ],
+        )
+    }
+}
+
+/// Mock Remittance Split contract whose split has no BILLS bucket, kept in
+/// its own module so its contract functions don't clash with the mock above
+mod incomplete_split {
+    use super::allocation;
+    use crate::Allocation;
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Vec};
+
+    #[contract]
+    pub struct MockRemittanceSplitWithoutBills;
+
+    #[contractimpl]
+    impl MockRemittanceSplitWithoutBills {
+        pub fn get_split_allocations(
+            env: Env,
+            _owner: Address,
+            total_amount: i128,
+        ) -> Vec<Allocation> {
+            Vec::from_array(
+                &env,
+                [
+                    allocation(symbol_short!("SPENDING"), (total_amount * 50) / 100),
+                    allocation(symbol_short!("SAVINGS"), (total_amount * 40) / 100),
+                    allocation(symbol_short!("INSURANCE"), (total_amount * 10) / 100),
+                ],
+            )
+        }
     }
 }
 
+fn allocation(category: Symbol, amount: i128) -> Allocation {
+    Allocation { category, amount }
+}
+
 /// Mock Savings Goals contract for testing
 #[contract]
 pub struct MockSavingsGoals;
@@ -350,6 +390,42 @@ mod tests {
         assert!(flow_result.insurance_success);
     }
 
+    #[test]
+    fn test_remittance_flow_fails_when_split_bucket_missing() {
+        let (
+            env,
+            orchestrator_id,
+            family_wallet_id,
+            _remittance_split_id,
+            savings_id,
+            bills_id,
+            insurance_id,
+            user,
+        ) = setup_test_env();
+        let remittance_split_id =
+            env.register_contract(None, incomplete_split::MockRemittanceSplitWithoutBills);
+
+        let client = OrchestratorClient::new(&env, &orchestrator_id);
+
+        let result = client.try_execute_remittance_flow(
+            &user,
+            &10000,
+            &family_wallet_id,
+            &remittance_split_id,
+            &savings_id,
+            &bills_id,
+            &insurance_id,
+            &1, // goal_id
+            &1, // bill_id
+            &1, // policy_id
+        );
+
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            OrchestratorError::MissingSplitBucket
+        );
+    }
+
     #[test]
     fn test_remittance_flow_bill_payment_failure_causes_rollback() {
         let (
@@ -1,9 +1,8 @@
 #![no_std]
-mod test;
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, token::TokenClient, vec,
-    Address, Env, Map, Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, token::TokenClient,
+    xdr::ToXdr, Address, BytesN, Env, IntoVal, Map, Symbol, Val, Vec,
 };
 
 // Event topics
@@ -36,16 +35,40 @@ pub enum RemittanceSplitError {
     ChecksumMismatch = 9,
     InvalidDueDate = 10,
     ScheduleNotFound = 11,
+    /// Bucket list is empty, too long, or repeats a bucket name
+    InvalidBuckets = 12,
+    /// A bucket has no destination and none can be taken from the `AccountGroup`
+    MissingDestination = 13,
+    /// Remainder policy names a bucket that is not in the configuration
+    InvalidRemainderPolicy = 14,
+    /// Total is smaller than the bucket fixed amounts plus minimums
+    InsufficientAmount = 15,
+    /// Token is not on the owner's allow-list
+    TokenNotAllowed = 16,
+    /// Delegate is the owner itself or has a zero period
+    InvalidDelegate = 17,
+    /// Address is not a registered delegate of the owner
+    DelegateNotFound = 18,
+    /// Distribution would exceed the delegate's cap for the current period
+    DelegateCapExceeded = 19,
+    /// Owner has no configuration change waiting to take effect
+    NoPendingChange = 20,
+    /// Change delay is longer than `MAX_CHANGE_DELAY`
+    InvalidChangeDelay = 21,
+    /// No preset with this id, or it has been retired
+    PresetNotFound = 22,
+    /// Preset bucket names a destination, or the registry is full
+    InvalidPreset = 23,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct Allocation {
     pub category: Symbol,
     pub amount: i128,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct AccountGroup {
     pub spending: Address,
@@ -57,11 +80,96 @@ pub struct AccountGroup {
 // Storage TTL constants
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
 const INSTANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days
+const HISTORY_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
+const HISTORY_BUMP_AMOUNT: u32 = 2592000; // ~180 days
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
+const PERSISTENT_BUMP_AMOUNT: u32 = 518400; // ~30 days
+
+// Pagination limits
+const DEFAULT_PAGE_LIMIT: u32 = 20;
+const MAX_PAGE_LIMIT: u32 = 50;
+
+/// A named share of every remittance.
+///
+/// `destination` may be left unset for the four legacy buckets (`SPENDING`,
+/// `SAVINGS`, `BILLS`, `INSURANCE`); their recipient is then taken from the
+/// `AccountGroup` passed to `distribute_usdc`.
+///
+/// A bucket with a `fixed_amount` receives exactly that amount off the top and
+/// takes no share (`share_bps == 0`, no minimum or maximum). Other buckets get
+/// `share_bps` of what is left, kept within `min_amount` and `max_amount`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SplitBucket {
+    pub name: Symbol,
+    pub share_bps: u32,
+    pub destination: Option<Address>,
+    pub fixed_amount: Option<i128>,
+    pub min_amount: Option<i128>,
+    pub max_amount: Option<i128>,
+}
 
-/// Split configuration with owner tracking for access control
-#[derive(Clone)]
+/// Who receives the units left over after every bucket is rounded down.
+///
+/// Fixed-amount buckets and buckets already at their maximum never receive
+/// dust; it then goes to the next bucket in the policy's order of preference.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum RemainderPolicy {
+    /// The bucket with the largest share (the first one on ties).
+    LargestBucket,
+    /// The bucket with this name.
+    NamedBucket(Symbol),
+    /// Largest-remainder (Hamilton) apportionment: one unit each to the
+    /// buckets whose exact share lost the most to rounding.
+    LargestRemainder,
+}
+
+/// Split configuration with owner tracking for access control.
+///
+/// Buckets are applied in order and their shares always sum to 10_000 bps.
+/// When `change_delay` is non-zero, later changes (including to the delay
+/// itself) only take effect `change_delay` seconds after they are made.
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct SplitConfig {
+    pub owner: Address,
+    pub buckets: Vec<SplitBucket>,
+    pub remainder_policy: RemainderPolicy,
+    pub change_delay: u64,
+    pub timestamp: u64,
+    pub initialized: bool,
+}
+
+/// Configuration change waiting for the owner's change delay to pass.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PendingSplitConfig {
+    pub config: SplitConfig,
+    pub effective_at: u64,
+}
+
+/// Change queued behind an owner's configuration in force.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum PendingChange {
+    None,
+    Queued(PendingSplitConfig),
+}
+
+/// Configuration in force for an owner, plus the change queued behind it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SplitConfigView {
+    pub active: SplitConfig,
+    pub pending: PendingChange,
+}
+
+/// Four-bucket configuration stored before named buckets were introduced.
+/// Never written any more; loaded entries are migrated to [`SplitConfig`].
+#[derive(Clone)]
+#[contracttype]
+pub struct LegacySplitConfig {
     pub owner: Address,
     pub spending_percent: u32,
     pub savings_percent: u32,
@@ -75,11 +183,143 @@ pub struct SplitConfig {
 #[contracttype]
 pub struct SplitCalculatedEvent {
     pub total_amount: i128,
-    pub spending_amount: i128,
-    pub savings_amount: i128,
-    pub bills_amount: i128,
-    pub insurance_amount: i128,
+    pub allocations: Vec<Allocation>,
+    pub timestamp: u64,
+}
+
+/// Published by `distribute` and `distribute_as_delegate` with the asset
+/// that was paid out. `delegate` is set when a delegate triggered it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct DistributedEvent {
+    pub token: Address,
+    pub from: Address,
+    pub delegate: Option<Address>,
+    pub total_amount: i128,
+    pub allocations: Vec<Allocation>,
+    pub timestamp: u64,
+}
+
+/// One bucket of a [`DistributionPreview`]. `destination` is `None` when the
+/// bucket has no recipient.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct BucketPreview {
+    pub name: Symbol,
+    pub amount: i128,
+    pub destination: Option<Address>,
+}
+
+/// Problem found by `preview_distribution` before anything is signed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum PreviewIssue {
+    /// `token` is not on the owner's allow-list, so `distribute` would reject it.
+    TokenNotAllowed,
+    /// The named bucket has no destination and none can be taken from the `AccountGroup`.
+    MissingDestination(Symbol),
+    /// The named bucket would receive nothing.
+    ZeroAmountBucket(Symbol),
+    /// The named bucket would pay the owner back.
+    DestinationIsSource(Symbol),
+}
+
+/// Outcome of a distribution, computed without moving funds.
+///
+/// `allowance` is what the owner has approved this contract to pull, which
+/// scheduled remittances rely on.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct DistributionPreview {
+    pub owner: Address,
+    pub token: Address,
+    pub total_amount: i128,
+    pub buckets: Vec<BucketPreview>,
+    pub balance: i128,
+    pub allowance: i128,
+    pub balance_sufficient: bool,
+    pub allowance_sufficient: bool,
+    pub issues: Vec<PreviewIssue>,
+}
+
+/// Address allowed to distribute on an owner's behalf.
+///
+/// Within each `period` (in seconds, counted from when the delegate was
+/// registered) the delegate may move at most `cap` of `token`, the only token
+/// it can distribute. `spent` is what it has moved since `period_start`.
+/// Funds always go to the owner-registered `accounts`; the delegate cannot
+/// choose destinations.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Delegate {
+    pub delegate: Address,
+    pub token: Address,
+    pub accounts: AccountGroup,
+    pub cap: i128,
+    pub period: u64,
+    pub period_start: u64,
+    pub spent: i128,
+}
+
+/// One distribution from `owner`, kept in persistent storage.
+///
+/// Ids are per owner and start at 1.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct DistributionRecord {
+    pub id: u32,
+    pub owner: Address,
     pub timestamp: u64,
+    pub token: Address,
+    pub total_amount: i128,
+    pub allocations: Vec<Allocation>,
+}
+
+/// Paginated result for distribution history queries
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct DistributionPage {
+    pub items: Vec<DistributionRecord>,
+    /// The id to pass as `cursor` for the next page. 0 means no more pages.
+    pub next_cursor: u32,
+    pub count: u32,
+}
+
+/// Sum of one token's distributions over a period. `allocations` adds up the
+/// amounts per bucket name.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct DistributionTotals {
+    pub token: Address,
+    pub count: u32,
+    pub total_amount: i128,
+    pub allocations: Vec<Allocation>,
+}
+
+/// One page of `get_distribution_totals`. Totals from successive pages add up
+/// to the totals over the whole period.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct DistributionTotalsPage {
+    pub totals: Vec<DistributionTotals>,
+    /// The id to pass as `cursor` for the next page. 0 means no more pages.
+    pub next_cursor: u32,
+}
+
+/// Named split template published by the preset admin.
+///
+/// Every `set_preset` call publishes a new `version`; owners initialized from
+/// a preset keep the copy they received, so later versions never change
+/// existing configurations. Preset buckets carry no destinations.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SplitPreset {
+    pub id: Symbol,
+    pub version: u32,
+    pub buckets: Vec<SplitBucket>,
+    pub remainder_policy: RemainderPolicy,
+    pub updated_at: u64,
+    pub retired: bool,
 }
 
 /// Events emitted by the contract for audit trail
@@ -89,14 +329,25 @@ pub enum SplitEvent {
     Initialized,
     Updated,
     Calculated,
+    Distributed,
+    TokenAllowed,
+    TokenRemoved,
+    DelegateSet,
+    DelegateRevoked,
+    ChangeQueued,
+    ChangeCancelled,
+    PresetPublished,
+    PresetRetired,
+    PresetApplied,
 }
 
-/// Snapshot for data export/import (migration). Checksum is a simple numeric digest for on-chain verification.
+/// Snapshot for data export/import (migration). The checksum is the SHA-256
+/// of the config's XDR encoding, so `data_migration` can verify it off-chain.
 #[contracttype]
 #[derive(Clone)]
 pub struct ExportSnapshot {
     pub version: u32,
-    pub checksum: u64,
+    pub checksum: BytesN<32>,
     pub config: SplitConfig,
 }
 
@@ -110,10 +361,34 @@ pub struct AuditEntry {
     pub success: bool,
 }
 
-/// Schedule for automatic remittance splits
+/// Schedule for automatic remittance splits.
+///
+/// When due, `amount` of `token` is pulled from `owner` and split across the
+/// owner's buckets, with `accounts` supplying destinations like in
+/// `distribute_usdc`.
 #[contracttype]
 #[derive(Clone)]
 pub struct RemittanceSchedule {
+    pub id: u32,
+    pub owner: Address,
+    pub token: Address,
+    pub accounts: AccountGroup,
+    pub amount: i128,
+    pub next_due: u64,
+    pub interval: u64,
+    pub recurring: bool,
+    pub active: bool,
+    pub created_at: u64,
+    pub last_executed: Option<u64>,
+    pub missed_count: u32,
+}
+
+/// Schedule layout kept in the instance `REM_SCH` map before schedules
+/// carried a token and destination accounts. Only read by `migrate_storage`
+/// and `restore_legacy_schedule`.
+#[contracttype]
+#[derive(Clone)]
+pub struct LegacyRemittanceSchedule {
     pub id: u32,
     pub owner: Address,
     pub amount: i128,
@@ -135,17 +410,149 @@ pub enum ScheduleEvent {
     Missed,
     Modified,
     Cancelled,
+    Failed,
+}
+
+/// Why `execute_due_remittance_schedules` could not pay a due schedule.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ScheduleFailureReason {
+    /// The schedule's token is no longer on the owner's allow-list.
+    TokenNotAllowed,
+    /// The owner's balance or allowance does not cover the amount.
+    InsufficientFunds,
+    /// The split could not be paid out, e.g. a bucket has no destination.
+    SplitFailed,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduleFailure {
+    pub schedule_id: u32,
+    pub reason: ScheduleFailureReason,
+    pub timestamp: u64,
+}
+
+/// Outcome of one bounded `execute_due_remittance_schedules` call.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduleRun {
+    pub executed: Vec<u32>,
+    pub failed: Vec<ScheduleFailure>,
+    /// Pass as `cursor` to continue the sweep. 0 = no more schedules.
+    pub next_cursor: u32,
 }
 
-const SNAPSHOT_VERSION: u32 = 1;
+const SNAPSHOT_VERSION: u32 = 4;
 const MAX_AUDIT_ENTRIES: u32 = 100;
 const CONTRACT_VERSION: u32 = 1;
 
+/// Split applied to owners that have not stored a configuration of their own.
+const DEFAULT_SPENDING_PERCENT: u32 = 50;
+const DEFAULT_SAVINGS_PERCENT: u32 = 30;
+const DEFAULT_BILLS_PERCENT: u32 = 15;
+const DEFAULT_INSURANCE_PERCENT: u32 = 5;
+
+/// Longest change delay an owner can set (~30 days), so a typo cannot lock
+/// the configuration for good.
+const MAX_CHANGE_DELAY: u64 = 2_592_000;
+
+/// Basis points in a whole remittance.
+const TOTAL_BPS: u32 = 10_000;
+const MAX_BUCKETS: u32 = 10;
+const MAX_PRESETS: u32 = 20;
+
+const BUCKET_SPENDING: Symbol = symbol_short!("SPENDING");
+const BUCKET_SAVINGS: Symbol = symbol_short!("SAVINGS");
+const BUCKET_BILLS: Symbol = symbol_short!("BILLS");
+const BUCKET_INSURANCE: Symbol = symbol_short!("INSURANCE");
+
+/// Persistent `(SPLIT_CFG, owner)` holds the owner's `SplitConfig`.
+const STORAGE_SPLIT_CONFIGS: Symbol = symbol_short!("SPLIT_CFG");
+/// Persistent `(PEND_CFG, owner)` holds a `PendingSplitConfig` queued by a
+/// change delay.
+const STORAGE_PENDING_CONFIGS: Symbol = symbol_short!("PEND_CFG");
+/// Pre-bucket storage: the original single-tenant `LegacySplitConfig`.
+const STORAGE_LEGACY_CONFIG: Symbol = symbol_short!("CONFIG");
+/// Persistent `(TOKENS, owner)` holds the owner's token allow-list and
+/// `(TOK_TOT, owner, token)` the cumulative amount distributed.
+const STORAGE_ALLOWED_TOKENS: Symbol = symbol_short!("TOKENS");
+const STORAGE_TOKEN_TOTALS: Symbol = symbol_short!("TOK_TOT");
+/// Persistent `(DELEGATES, owner)` holds the owner's `Delegate`s.
+const STORAGE_DELEGATES: Symbol = symbol_short!("DELEGATES");
+/// Persistent `(REM_SCH, id)` holds a `RemittanceSchedule` and
+/// `(RSCH_OWN, owner)` the owner's schedule ids. Earlier versions kept every
+/// schedule in one instance map under `REM_SCH`; see `migrate_storage`.
+const STORAGE_SCHEDULES: Symbol = symbol_short!("REM_SCH");
+const STORAGE_OWNER_SCHEDULE_IDS: Symbol = symbol_short!("RSCH_OWN");
+const STORAGE_NEXT_SCHEDULE_ID: Symbol = symbol_short!("NEXT_RSCH");
+/// Persistent `(LEG_RSCH, id)` holds a `LegacyRemittanceSchedule` moved out
+/// of the instance map until its owner restores it, and `(LRSCH_OWN, owner)`
+/// the owner's parked ids.
+const STORAGE_LEGACY_SCHEDULES: Symbol = symbol_short!("LEG_RSCH");
+const STORAGE_OWNER_LEGACY_IDS: Symbol = symbol_short!("LRSCH_OWN");
+/// Persistent `(RSCH_FAIL, id)` holds the schedule's latest `ScheduleFailure`.
+const STORAGE_SCHEDULE_FAILURES: Symbol = symbol_short!("RSCH_FAIL");
+/// Persistent distribution history: `(DIST, owner, id)` holds a
+/// `DistributionRecord` and `(DIST_CNT, owner)` the owner's last id.
+const STORAGE_DISTRIBUTION: Symbol = symbol_short!("DIST");
+const STORAGE_DISTRIBUTION_COUNT: Symbol = symbol_short!("DIST_CNT");
+/// Latest `SplitPreset` per id. Every published version is also kept in
+/// persistent storage under `(PRESETS, id, version)`.
+const STORAGE_PRESETS: Symbol = symbol_short!("PRESETS");
+const STORAGE_PRESET_ADMIN: Symbol = symbol_short!("PRST_ADM");
+/// Contract admin fixed by `init`; makes the first pause, upgrade and preset
+/// admin assignments.
+const STORAGE_ADMIN: Symbol = symbol_short!("ADMIN");
+
 #[contract]
 pub struct RemittanceSplit;
 
 #[contractimpl]
 impl RemittanceSplit {
+    /// Initialize the contract with its admin. Only the admin can make the
+    /// first pause, upgrade and preset admin assignments.
+    ///
+    /// # Errors
+    /// * `AlreadyInitialized` - If an admin has already been set
+    pub fn init(env: Env, admin: Address) -> Result<(), RemittanceSplitError> {
+        admin.require_auth();
+
+        if env.storage().instance().has(&STORAGE_ADMIN) {
+            return Err(RemittanceSplitError::AlreadyInitialized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage().instance().set(&STORAGE_ADMIN, &admin);
+
+        Ok(())
+    }
+
+    pub fn get_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&STORAGE_ADMIN)
+    }
+
+    /// Check `caller` may set a role whose current holder is `current`: the
+    /// holder itself, or the contract admin while the role is unassigned.
+    fn require_role_admin(
+        env: &Env,
+        caller: &Address,
+        current: Option<Address>,
+    ) -> Result<(), RemittanceSplitError> {
+        let allowed = match current {
+            Some(holder) => holder,
+            None => env
+                .storage()
+                .instance()
+                .get(&STORAGE_ADMIN)
+                .ok_or(RemittanceSplitError::NotInitialized)?,
+        };
+        if allowed != *caller {
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+        Ok(())
+    }
+
     fn get_pause_admin(env: &Env) -> Option<Address> {
         env.storage().instance().get(&symbol_short!("PAUSE_ADM"))
     }
@@ -169,14 +576,7 @@ impl RemittanceSplit {
         new_admin: Address,
     ) -> Result<(), RemittanceSplitError> {
         caller.require_auth();
-        let config: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        if config.owner != caller {
-            return Err(RemittanceSplitError::Unauthorized);
-        }
+        Self::require_role_admin(&env, &caller, Self::get_pause_admin(&env))?;
         env.storage()
             .instance()
             .set(&symbol_short!("PAUSE_ADM"), &new_admin);
@@ -184,12 +584,7 @@ impl RemittanceSplit {
     }
     pub fn pause(env: Env, caller: Address) -> Result<(), RemittanceSplitError> {
         caller.require_auth();
-        let config: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        let admin = Self::get_pause_admin(&env).unwrap_or(config.owner);
+        let admin = Self::get_pause_admin(&env).ok_or(RemittanceSplitError::Unauthorized)?;
         if admin != caller {
             return Err(RemittanceSplitError::Unauthorized);
         }
@@ -202,12 +597,7 @@ impl RemittanceSplit {
     }
     pub fn unpause(env: Env, caller: Address) -> Result<(), RemittanceSplitError> {
         caller.require_auth();
-        let config: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        let admin = Self::get_pause_admin(&env).unwrap_or(config.owner);
+        let admin = Self::get_pause_admin(&env).ok_or(RemittanceSplitError::Unauthorized)?;
         if admin != caller {
             return Err(RemittanceSplitError::Unauthorized);
         }
@@ -236,14 +626,7 @@ impl RemittanceSplit {
         new_admin: Address,
     ) -> Result<(), RemittanceSplitError> {
         caller.require_auth();
-        let config: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        if config.owner != caller {
-            return Err(RemittanceSplitError::Unauthorized);
-        }
+        Self::require_role_admin(&env, &caller, Self::get_upgrade_admin(&env))?;
         env.storage()
             .instance()
             .set(&symbol_short!("UPG_ADM"), &new_admin);
@@ -255,12 +638,7 @@ impl RemittanceSplit {
         new_version: u32,
     ) -> Result<(), RemittanceSplitError> {
         caller.require_auth();
-        let config: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        let admin = Self::get_upgrade_admin(&env).unwrap_or(config.owner);
+        let admin = Self::get_upgrade_admin(&env).ok_or(RemittanceSplitError::Unauthorized)?;
         if admin != caller {
             return Err(RemittanceSplitError::Unauthorized);
         }
@@ -275,245 +653,1317 @@ impl RemittanceSplit {
         Ok(())
     }
 
-    /// Set or update the split percentages used to allocate remittances.
-    ///
-    /// # Arguments
-    /// * `owner` - Address of the split owner (must authorize)
-    /// * `nonce` - Caller's transaction nonce (must equal get_nonce(owner)) for replay protection
-    /// * `spending_percent` - Percentage for spending (0-100)
-    /// * `savings_percent` - Percentage for savings (0-100)
-    /// * `bills_percent` - Percentage for bills (0-100)
-    /// * `insurance_percent` - Percentage for insurance (0-100)
+    /// Move remittance schedules that earlier versions kept in one
+    /// instance-storage map into per-schedule persistent entries. Upgrade
+    /// admin only.
     ///
-    /// # Returns
-    /// True if initialization was successful
+    /// Those schedules carry no token or destination accounts, so they are
+    /// parked rather than made executable: each owner lists them with
+    /// `get_legacy_schedules` and brings them back with
+    /// `restore_legacy_schedule`.
     ///
-    /// # Panics
-    /// - If owner doesn't authorize the transaction
-    /// - If nonce is invalid (replay)
-    /// - If percentages don't sum to 100
-    /// - If split is already initialized (use update_split instead)
-    pub fn initialize_split(
+    /// Run once right after upgrading. Returns the number of schedules moved;
+    /// calling it again finds nothing and returns 0.
+    pub fn migrate_storage(env: Env, caller: Address) -> Result<u32, RemittanceSplitError> {
+        caller.require_auth();
+        let admin = Self::get_upgrade_admin(&env).ok_or(RemittanceSplitError::Unauthorized)?;
+        if admin != caller {
+            Self::append_audit(&env, symbol_short!("migrate"), &caller, false);
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let instance = env.storage().instance();
+        let mut moved = 0u32;
+        let schedules: Option<Map<u32, LegacyRemittanceSchedule>> =
+            instance.get(&STORAGE_SCHEDULES);
+        for (_, schedule) in schedules.unwrap_or_else(|| Map::new(&env)).iter() {
+            let key = (STORAGE_LEGACY_SCHEDULES, schedule.id);
+            env.storage().persistent().set(&key, &schedule);
+            Self::extend_persistent_ttl(&env, &key);
+
+            let index_key = (STORAGE_OWNER_LEGACY_IDS, schedule.owner.clone());
+            let mut ids = Self::owner_legacy_schedule_ids(&env, &schedule.owner);
+            ids.push_back(schedule.id);
+            env.storage().persistent().set(&index_key, &ids);
+            Self::extend_persistent_ttl(&env, &index_key);
+            moved += 1;
+        }
+        instance.remove(&STORAGE_SCHEDULES);
+
+        Self::append_audit(&env, symbol_short!("migrate"), &caller, true);
+        env.events()
+            .publish((symbol_short!("split"), symbol_short!("migrated")), moved);
+        Ok(moved)
+    }
+
+    /// `owner`'s schedules parked by `migrate_storage` and not yet restored.
+    pub fn get_legacy_schedules(env: Env, owner: Address) -> Vec<LegacyRemittanceSchedule> {
+        let mut result = Vec::new(&env);
+        for schedule_id in Self::owner_legacy_schedule_ids(&env, &owner).iter() {
+            let schedule: Option<LegacyRemittanceSchedule> = env
+                .storage()
+                .persistent()
+                .get(&(STORAGE_LEGACY_SCHEDULES, schedule_id));
+            if let Some(schedule) = schedule {
+                result.push_back(schedule);
+            }
+        }
+        result
+    }
+
+    /// Turn a schedule parked by `migrate_storage` back into a regular
+    /// schedule paying `token` to `accounts`. Keeps the schedule's id, timing
+    /// and status; `token` must be on the owner's allow-list.
+    pub fn restore_legacy_schedule(
         env: Env,
         owner: Address,
-        nonce: u64,
-        spending_percent: u32,
-        savings_percent: u32,
-        bills_percent: u32,
-        insurance_percent: u32,
+        schedule_id: u32,
+        token: Address,
+        accounts: AccountGroup,
     ) -> Result<bool, RemittanceSplitError> {
         owner.require_auth();
-        Self::require_not_paused(&env)?;
-        Self::require_nonce(&env, &owner, nonce)?;
 
-        let existing: Option<SplitConfig> = env.storage().instance().get(&symbol_short!("CONFIG"));
-        if existing.is_some() {
-            Self::append_audit(&env, symbol_short!("init"), &owner, false);
-            return Err(RemittanceSplitError::AlreadyInitialized);
+        let key = (STORAGE_LEGACY_SCHEDULES, schedule_id);
+        let legacy: LegacyRemittanceSchedule = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(RemittanceSplitError::ScheduleNotFound)?;
+        if legacy.owner != owner {
+            return Err(RemittanceSplitError::Unauthorized);
         }
-
-        let total = spending_percent + savings_percent + bills_percent + insurance_percent;
-        if total != 100 {
-            Self::append_audit(&env, symbol_short!("init"), &owner, false);
-            return Err(RemittanceSplitError::PercentagesDoNotSumTo100);
+        if !Self::token_allowed(&env, &owner, &token) {
+            return Err(RemittanceSplitError::TokenNotAllowed);
         }
 
         Self::extend_instance_ttl(&env);
 
-        let config = SplitConfig {
+        let schedule = RemittanceSchedule {
+            id: legacy.id,
             owner: owner.clone(),
-            spending_percent,
-            savings_percent,
-            bills_percent,
-            insurance_percent,
-            timestamp: env.ledger().timestamp(),
-            initialized: true,
+            token,
+            accounts,
+            amount: legacy.amount,
+            next_due: legacy.next_due,
+            interval: legacy.interval,
+            recurring: legacy.recurring,
+            active: legacy.active,
+            created_at: legacy.created_at,
+            last_executed: legacy.last_executed,
+            missed_count: legacy.missed_count,
         };
+        Self::save_schedule(&env, &schedule);
+        Self::append_owner_schedule_id(&env, &owner, schedule_id);
+
+        env.storage().persistent().remove(&key);
+        let index_key = (STORAGE_OWNER_LEGACY_IDS, owner.clone());
+        let mut ids = Self::owner_legacy_schedule_ids(&env, &owner);
+        if let Some(position) = ids.first_index_of(schedule_id) {
+            ids.remove(position);
+        }
+        if ids.is_empty() {
+            env.storage().persistent().remove(&index_key);
+        } else {
+            env.storage().persistent().set(&index_key, &ids);
+        }
 
-        env.storage()
-            .instance()
-            .set(&symbol_short!("CONFIG"), &config);
-        env.storage().instance().set(
-            &symbol_short!("SPLIT"),
-            &vec![
-                &env,
-                spending_percent,
-                savings_percent,
-                bills_percent,
-                insurance_percent,
-            ],
+        env.events().publish(
+            (symbol_short!("schedule"), ScheduleEvent::Modified),
+            (schedule_id, owner),
         );
 
-        Self::increment_nonce(&env, &owner)?;
-        Self::append_audit(&env, symbol_short!("init"), &owner, true);
-        env.events()
-            .publish((symbol_short!("split"), SplitEvent::Initialized), owner);
-
         Ok(true)
     }
 
-    pub fn update_split(
+    fn get_preset_admin(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&STORAGE_PRESET_ADMIN)
+    }
+    pub fn set_preset_admin(
         env: Env,
         caller: Address,
-        nonce: u64,
-        spending_percent: u32,
-        savings_percent: u32,
-        bills_percent: u32,
-        insurance_percent: u32,
-    ) -> Result<bool, RemittanceSplitError> {
+        new_admin: Address,
+    ) -> Result<(), RemittanceSplitError> {
         caller.require_auth();
-        Self::require_not_paused(&env)?;
-        Self::require_nonce(&env, &caller, nonce)?;
-
-        let mut config: SplitConfig = env
-            .storage()
+        Self::require_role_admin(&env, &caller, Self::get_preset_admin(&env))?;
+        env.storage()
             .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
+            .set(&STORAGE_PRESET_ADMIN, &new_admin);
+        Ok(())
+    }
 
-        if config.owner != caller {
-            Self::append_audit(&env, symbol_short!("update"), &caller, false);
+    /// Publish a new version of preset `preset_id` (version 1 when the id is
+    /// new). A retired preset is listed again.
+    ///
+    /// Buckets follow the `initialize_split_buckets` rules and must not name
+    /// destinations; owners supply their own through an `AccountGroup`.
+    ///
+    /// # Returns
+    /// The version just published
+    pub fn set_preset(
+        env: Env,
+        caller: Address,
+        preset_id: Symbol,
+        buckets: Vec<SplitBucket>,
+        remainder_policy: RemainderPolicy,
+    ) -> Result<u32, RemittanceSplitError> {
+        caller.require_auth();
+        let admin = Self::get_preset_admin(&env).ok_or(RemittanceSplitError::Unauthorized)?;
+        if admin != caller {
             return Err(RemittanceSplitError::Unauthorized);
         }
-
-        let total = spending_percent + savings_percent + bills_percent + insurance_percent;
-        if total != 100 {
-            Self::append_audit(&env, symbol_short!("update"), &caller, false);
-            return Err(RemittanceSplitError::PercentagesDoNotSumTo100);
+        Self::validate_split(&buckets, &remainder_policy)?;
+        if buckets.iter().any(|b| b.destination.is_some()) {
+            return Err(RemittanceSplitError::InvalidPreset);
         }
 
         Self::extend_instance_ttl(&env);
-
-        config.spending_percent = spending_percent;
-        config.savings_percent = savings_percent;
-        config.bills_percent = bills_percent;
-        config.insurance_percent = insurance_percent;
-
-        env.storage()
+        let mut presets: Map<Symbol, SplitPreset> = env
+            .storage()
             .instance()
-            .set(&symbol_short!("CONFIG"), &config);
-        env.storage().instance().set(
-            &symbol_short!("SPLIT"),
-            &vec![
+            .get(&STORAGE_PRESETS)
+            .unwrap_or_else(|| Map::new(&env));
+        let version = match presets.get(preset_id.clone()) {
+            Some(latest) => latest
+                .version
+                .checked_add(1)
+                .ok_or(RemittanceSplitError::Overflow)?,
+            None if presets.len() >= MAX_PRESETS => {
+                return Err(RemittanceSplitError::InvalidPreset)
+            }
+            None => 1,
+        };
+        let preset = SplitPreset {
+            id: preset_id.clone(),
+            version,
+            buckets,
+            remainder_policy,
+            updated_at: env.ledger().timestamp(),
+            retired: false,
+        };
+        presets.set(preset_id.clone(), preset.clone());
+        env.storage().instance().set(&STORAGE_PRESETS, &presets);
+
+        let key = (STORAGE_PRESETS, preset_id.clone(), version);
+        let storage = env.storage().persistent();
+        storage.set(&key, &preset);
+        storage.extend_ttl(&key, HISTORY_LIFETIME_THRESHOLD, HISTORY_BUMP_AMOUNT);
+
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::PresetPublished),
+            (preset_id, version),
+        );
+        Ok(version)
+    }
+
+    /// Hide `preset_id` from `list_presets` and stop new owners from using it.
+    /// Configurations already created from it are untouched.
+    pub fn retire_preset(
+        env: Env,
+        caller: Address,
+        preset_id: Symbol,
+    ) -> Result<(), RemittanceSplitError> {
+        caller.require_auth();
+        let admin = Self::get_preset_admin(&env).ok_or(RemittanceSplitError::Unauthorized)?;
+        if admin != caller {
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+        let mut presets: Map<Symbol, SplitPreset> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_PRESETS)
+            .unwrap_or_else(|| Map::new(&env));
+        let mut preset = match presets.get(preset_id.clone()) {
+            Some(preset) if !preset.retired => preset,
+            _ => return Err(RemittanceSplitError::PresetNotFound),
+        };
+        preset.retired = true;
+        presets.set(preset_id.clone(), preset);
+
+        Self::extend_instance_ttl(&env);
+        env.storage().instance().set(&STORAGE_PRESETS, &presets);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::PresetRetired),
+            preset_id,
+        );
+        Ok(())
+    }
+
+    /// Latest version of every preset that has not been retired.
+    pub fn list_presets(env: Env) -> Vec<SplitPreset> {
+        let presets: Map<Symbol, SplitPreset> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_PRESETS)
+            .unwrap_or_else(|| Map::new(&env));
+        let mut out = Vec::new(&env);
+        for (_, preset) in presets.iter() {
+            if !preset.retired {
+                out.push_back(preset);
+            }
+        }
+        out
+    }
+
+    /// A specific published version of `preset_id`, or the latest one when
+    /// `version` is `None`. Retired presets can still be looked up.
+    pub fn get_preset(env: Env, preset_id: Symbol, version: Option<u32>) -> Option<SplitPreset> {
+        match version {
+            Some(version) => env
+                .storage()
+                .persistent()
+                .get(&(STORAGE_PRESETS, preset_id, version)),
+            None => env
+                .storage()
+                .instance()
+                .get(&STORAGE_PRESETS)
+                .and_then(|presets: Map<Symbol, SplitPreset>| presets.get(preset_id)),
+        }
+    }
+
+    /// Set the split percentages used to allocate `owner`'s remittances.
+    ///
+    /// Every owner keeps an independent configuration, so a single deployment
+    /// can serve many senders.
+    ///
+    /// # Arguments
+    /// * `owner` - Address of the split owner (must authorize)
+    /// * `nonce` - Caller's transaction nonce (must equal get_nonce(owner)) for replay protection
+    /// * `spending_percent` - Percentage for spending (0-100)
+    /// * `savings_percent` - Percentage for savings (0-100)
+    /// * `bills_percent` - Percentage for bills (0-100)
+    /// * `insurance_percent` - Percentage for insurance (0-100)
+    ///
+    /// The percentages become the four legacy buckets `SPENDING`, `SAVINGS`,
+    /// `BILLS` and `INSURANCE`, with rounding dust going to `INSURANCE`; use
+    /// `initialize_split_buckets` for custom buckets or another remainder policy.
+    ///
+    /// # Returns
+    /// True if initialization was successful
+    ///
+    /// # Panics
+    /// - If owner doesn't authorize the transaction
+    /// - If nonce is invalid (replay)
+    /// - If percentages don't sum to 100
+    /// - If owner already has a split (use update_split instead)
+    pub fn initialize_split(
+        env: Env,
+        owner: Address,
+        nonce: u64,
+        spending_percent: u32,
+        savings_percent: u32,
+        bills_percent: u32,
+        insurance_percent: u32,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_nonce(&env, &owner, nonce)?;
+
+        if Self::load_config(&env, &owner).is_some() {
+            Self::append_audit(&env, symbol_short!("init"), &owner, false);
+            return Err(RemittanceSplitError::AlreadyInitialized);
+        }
+
+        let total = spending_percent + savings_percent + bills_percent + insurance_percent;
+        if total != 100 {
+            Self::append_audit(&env, symbol_short!("init"), &owner, false);
+            return Err(RemittanceSplitError::PercentagesDoNotSumTo100);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let config = SplitConfig {
+            owner: owner.clone(),
+            buckets: Self::legacy_buckets(
                 &env,
                 spending_percent,
                 savings_percent,
                 bills_percent,
                 insurance_percent,
-            ],
+            ),
+            remainder_policy: Self::legacy_remainder_policy(),
+            change_delay: 0,
+            timestamp: env.ledger().timestamp(),
+            initialized: true,
+        };
+        Self::save_config(&env, &config);
+
+        Self::increment_nonce(&env, &owner)?;
+        Self::append_audit(&env, symbol_short!("init"), &owner, true);
+        env.events()
+            .publish((symbol_short!("split"), SplitEvent::Initialized), owner);
+
+        Ok(true)
+    }
+
+    /// Replace `caller`'s split with the four legacy buckets. Any custom
+    /// buckets are discarded and the remainder goes to `INSURANCE` again.
+    pub fn update_split(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        spending_percent: u32,
+        savings_percent: u32,
+        bills_percent: u32,
+        insurance_percent: u32,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_nonce(&env, &caller, nonce)?;
+
+        let mut config =
+            Self::load_config(&env, &caller).ok_or(RemittanceSplitError::NotInitialized)?;
+
+        let total = spending_percent + savings_percent + bills_percent + insurance_percent;
+        if total != 100 {
+            Self::append_audit(&env, symbol_short!("update"), &caller, false);
+            return Err(RemittanceSplitError::PercentagesDoNotSumTo100);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        config.buckets = Self::legacy_buckets(
+            &env,
+            spending_percent,
+            savings_percent,
+            bills_percent,
+            insurance_percent,
+        );
+        config.remainder_policy = Self::legacy_remainder_policy();
+        let queued = Self::commit_config(&env, &config);
+
+        if queued.is_none() {
+            let event = SplitInitializedEvent {
+                spending_percent,
+                savings_percent,
+                bills_percent,
+                insurance_percent,
+                timestamp: env.ledger().timestamp(),
+            };
+            env.events().publish((SPLIT_INITIALIZED,), event);
+        }
+        Self::publish_change(&env, caller, queued);
+
+        Ok(true)
+    }
+
+    /// Configure `owner`'s split as an ordered list of named buckets.
+    ///
+    /// # Arguments
+    /// * `owner` - Address of the split owner (must authorize)
+    /// * `nonce` - Caller's transaction nonce (must equal get_nonce(owner))
+    /// * `buckets` - 1 to `MAX_BUCKETS` buckets with unique names whose
+    ///   `share_bps` sum to 10_000
+    /// * `remainder_policy` - Which bucket(s) receive rounding dust
+    pub fn initialize_split_buckets(
+        env: Env,
+        owner: Address,
+        nonce: u64,
+        buckets: Vec<SplitBucket>,
+        remainder_policy: RemainderPolicy,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_nonce(&env, &owner, nonce)?;
+
+        if Self::load_config(&env, &owner).is_some() {
+            Self::append_audit(&env, symbol_short!("init"), &owner, false);
+            return Err(RemittanceSplitError::AlreadyInitialized);
+        }
+        if let Err(e) = Self::validate_split(&buckets, &remainder_policy) {
+            Self::append_audit(&env, symbol_short!("init"), &owner, false);
+            return Err(e);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let config = SplitConfig {
+            owner: owner.clone(),
+            buckets,
+            remainder_policy,
+            change_delay: 0,
+            timestamp: env.ledger().timestamp(),
+            initialized: true,
+        };
+        Self::save_config(&env, &config);
+
+        Self::increment_nonce(&env, &owner)?;
+        Self::append_audit(&env, symbol_short!("init"), &owner, true);
+        env.events()
+            .publish((symbol_short!("split"), SplitEvent::Initialized), owner);
+
+        Ok(true)
+    }
+
+    /// Configure `owner`'s split from the latest version of a preset.
+    ///
+    /// The preset's buckets are copied into the owner's configuration, so
+    /// later versions of the preset do not change it. Publishes
+    /// `SplitEvent::PresetApplied` with `(owner, preset_id, version)`.
+    ///
+    /// # Returns
+    /// The preset version that was applied
+    pub fn initialize_split_from_preset(
+        env: Env,
+        owner: Address,
+        nonce: u64,
+        preset_id: Symbol,
+    ) -> Result<u32, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_nonce(&env, &owner, nonce)?;
+
+        if Self::load_config(&env, &owner).is_some() {
+            Self::append_audit(&env, symbol_short!("init"), &owner, false);
+            return Err(RemittanceSplitError::AlreadyInitialized);
+        }
+        let preset = match Self::get_preset(env.clone(), preset_id.clone(), None) {
+            Some(preset) if !preset.retired => preset,
+            _ => {
+                Self::append_audit(&env, symbol_short!("init"), &owner, false);
+                return Err(RemittanceSplitError::PresetNotFound);
+            }
+        };
+
+        Self::extend_instance_ttl(&env);
+
+        let config = SplitConfig {
+            owner: owner.clone(),
+            buckets: preset.buckets,
+            remainder_policy: preset.remainder_policy,
+            change_delay: 0,
+            timestamp: env.ledger().timestamp(),
+            initialized: true,
+        };
+        Self::save_config(&env, &config);
+
+        Self::increment_nonce(&env, &owner)?;
+        Self::append_audit(&env, symbol_short!("init"), &owner, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::Initialized),
+            owner.clone(),
         );
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::PresetApplied),
+            (owner, preset_id, preset.version),
+        );
+
+        Ok(preset.version)
+    }
+
+    /// Replace `caller`'s buckets and remainder policy. Same rules as
+    /// `initialize_split_buckets`.
+    pub fn update_split_buckets(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        buckets: Vec<SplitBucket>,
+        remainder_policy: RemainderPolicy,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_nonce(&env, &caller, nonce)?;
+
+        let mut config =
+            Self::load_config(&env, &caller).ok_or(RemittanceSplitError::NotInitialized)?;
+
+        if let Err(e) = Self::validate_split(&buckets, &remainder_policy) {
+            Self::append_audit(&env, symbol_short!("update"), &caller, false);
+            return Err(e);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        config.buckets = buckets;
+        config.remainder_policy = remainder_policy;
+        let queued = Self::commit_config(&env, &config);
+
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("update"), &caller, true);
+        Self::publish_change(&env, caller, queued);
+
+        Ok(true)
+    }
+
+    /// Change only `caller`'s remainder policy, keeping the buckets.
+    pub fn set_remainder_policy(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        remainder_policy: RemainderPolicy,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_nonce(&env, &caller, nonce)?;
+
+        let mut config =
+            Self::load_config(&env, &caller).ok_or(RemittanceSplitError::NotInitialized)?;
+
+        if let Err(e) = Self::validate_split(&config.buckets, &remainder_policy) {
+            Self::append_audit(&env, symbol_short!("update"), &caller, false);
+            return Err(e);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        config.remainder_policy = remainder_policy;
+        let queued = Self::commit_config(&env, &config);
+
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("update"), &caller, true);
+        Self::publish_change(&env, caller, queued);
+
+        Ok(true)
+    }
+
+    /// Set how long later changes to `caller`'s configuration wait before they
+    /// take effect (0 disables the timelock, at most `MAX_CHANGE_DELAY`).
+    ///
+    /// The new delay is itself a configuration change, so lowering or
+    /// removing an active delay has to wait out the current one.
+    pub fn set_change_delay(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        change_delay: u64,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_nonce(&env, &caller, nonce)?;
+
+        let mut config =
+            Self::load_config(&env, &caller).ok_or(RemittanceSplitError::NotInitialized)?;
+
+        if change_delay > MAX_CHANGE_DELAY {
+            Self::append_audit(&env, symbol_short!("update"), &caller, false);
+            return Err(RemittanceSplitError::InvalidChangeDelay);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        config.change_delay = change_delay;
+        let queued = Self::commit_config(&env, &config);
+
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("update"), &caller, true);
+        Self::publish_change(&env, caller, queued);
+
+        Ok(true)
+    }
+
+    /// Drop `caller`'s queued configuration change before it takes effect.
+    pub fn cancel_pending_config(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_nonce(&env, &caller, nonce)?;
+
+        let now = env.ledger().timestamp();
+        let pending = Self::load_pending_config(&env, &caller);
+        if !pending.is_some_and(|p| p.effective_at > now) {
+            Self::append_audit(&env, symbol_short!("cancel"), &caller, false);
+            return Err(RemittanceSplitError::NoPendingChange);
+        }
+
+        Self::extend_instance_ttl(&env);
+        Self::remove_pending_config(&env, &caller);
+
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("cancel"), &caller, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::ChangeCancelled),
+            caller,
+        );
+
+        Ok(true)
+    }
+
+    /// Bucket shares for `owner` in basis points, in bucket order.
+    ///
+    /// Falls back to the contract default split when `owner` has no configuration.
+    pub fn get_split(env: &Env, owner: Address) -> Vec<u32> {
+        let config = Self::resolve_config(env, &owner);
+        let mut shares = Vec::new(env);
+        for bucket in config.buckets.iter() {
+            shares.push_back(bucket.share_bps);
+        }
+        shares
+    }
+
+    /// Configuration in force for `owner` and any change still waiting on the
+    /// owner's change delay, or `None` if the owner never initialized one.
+    pub fn get_config(env: Env, owner: Address) -> Option<SplitConfigView> {
+        let active = Self::load_config(&env, &owner)?;
+        let now = env.ledger().timestamp();
+        let pending = match Self::load_pending_config(&env, &owner) {
+            Some(pending) if pending.effective_at > now => PendingChange::Queued(pending),
+            _ => PendingChange::None,
+        };
+        Some(SplitConfigView { active, pending })
+    }
+
+    /// Calculate the split of `total_amount` using `owner`'s configuration
+    /// (or the default split when `owner` has none). Amounts are in bucket order.
+    pub fn calculate_split(
+        env: Env,
+        owner: Address,
+        total_amount: i128,
+    ) -> Result<Vec<i128>, RemittanceSplitError> {
+        let config = Self::resolve_config(&env, &owner);
+        Self::calculate_split_amounts(&env, &config, total_amount, true)
+    }
 
-        let event = SplitInitializedEvent {
-            spending_percent,
-            savings_percent,
-            bills_percent,
-            insurance_percent,
+    /// Transfer `total_amount` of `usdc_contract` from `from` to each bucket,
+    /// split according to `from`'s configuration (or the default split).
+    ///
+    /// Buckets without their own destination are paid to the matching
+    /// `accounts` entry.
+    pub fn distribute_usdc(
+        env: Env,
+        usdc_contract: Address,
+        from: Address,
+        nonce: u64,
+        accounts: AccountGroup,
+        total_amount: i128,
+    ) -> Result<bool, RemittanceSplitError> {
+        if total_amount <= 0 {
+            Self::append_audit(&env, symbol_short!("distrib"), &from, false);
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+
+        from.require_auth();
+        Self::require_nonce(&env, &from, nonce)?;
+
+        Self::transfer_split(&env, &usdc_contract, &from, &accounts, total_amount, false)?;
+
+        Self::increment_nonce(&env, &from)?;
+        Self::append_audit(&env, symbol_short!("distrib"), &from, true);
+        Ok(true)
+    }
+
+    /// Like `distribute_usdc`, but only for tokens on `from`'s allow-list.
+    ///
+    /// Publishes a [`DistributedEvent`] naming the asset.
+    pub fn distribute(
+        env: Env,
+        token: Address,
+        from: Address,
+        nonce: u64,
+        accounts: AccountGroup,
+        total_amount: i128,
+    ) -> Result<bool, RemittanceSplitError> {
+        from.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_nonce(&env, &from, nonce)?;
+
+        if total_amount <= 0 {
+            Self::append_audit(&env, symbol_short!("distrib"), &from, false);
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+        if !Self::token_allowed(&env, &from, &token) {
+            Self::append_audit(&env, symbol_short!("distrib"), &from, false);
+            return Err(RemittanceSplitError::TokenNotAllowed);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let allocations =
+            Self::transfer_split(&env, &token, &from, &accounts, total_amount, false)?;
+
+        Self::increment_nonce(&env, &from)?;
+        Self::append_audit(&env, symbol_short!("distrib"), &from, true);
+
+        let event = DistributedEvent {
+            token,
+            from,
+            delegate: None,
+            total_amount,
+            allocations,
             timestamp: env.ledger().timestamp(),
         };
-        env.events().publish((SPLIT_INITIALIZED,), event);
         env.events()
-            .publish((symbol_short!("split"), SplitEvent::Updated), caller);
+            .publish((symbol_short!("split"), SplitEvent::Distributed), event);
+        Ok(true)
+    }
+
+    /// Show what distributing `total_amount` of `token` from `owner` would do,
+    /// without requiring auth, spending the nonce or moving funds.
+    ///
+    /// Fails only when the amount cannot be split at all; anything else that
+    /// would stop or surprise the owner is reported in `issues`.
+    pub fn preview_distribution(
+        env: Env,
+        owner: Address,
+        token: Address,
+        accounts: AccountGroup,
+        total_amount: i128,
+    ) -> Result<DistributionPreview, RemittanceSplitError> {
+        let config = Self::resolve_config(&env, &owner);
+        let amounts = Self::calculate_split_amounts(&env, &config, total_amount, false)?;
+
+        let mut issues = Vec::new(&env);
+        if !Self::token_allowed(&env, &owner, &token) {
+            issues.push_back(PreviewIssue::TokenNotAllowed);
+        }
+
+        let mut buckets = Vec::new(&env);
+        for (bucket, amount) in config.buckets.iter().zip(amounts.iter()) {
+            let destination = Self::bucket_destination(&bucket, &accounts).ok();
+            match &destination {
+                None => issues.push_back(PreviewIssue::MissingDestination(bucket.name.clone())),
+                Some(addr) if *addr == owner => {
+                    issues.push_back(PreviewIssue::DestinationIsSource(bucket.name.clone()))
+                }
+                Some(_) => {}
+            }
+            if amount == 0 {
+                issues.push_back(PreviewIssue::ZeroAmountBucket(bucket.name.clone()));
+            }
+            buckets.push_back(BucketPreview {
+                name: bucket.name,
+                amount,
+                destination,
+            });
+        }
+
+        let client = TokenClient::new(&env, &token);
+        let balance = client.balance(&owner);
+        let allowance = client.allowance(&owner, &env.current_contract_address());
+
+        Ok(DistributionPreview {
+            owner,
+            token,
+            total_amount,
+            buckets,
+            balance,
+            allowance,
+            balance_sufficient: balance >= total_amount,
+            allowance_sufficient: allowance >= total_amount,
+            issues,
+        })
+    }
+
+    /// Split `total_amount` of `token` and transfer it from `from` to each
+    /// bucket's recipient, then record the distribution.
+    ///
+    /// With `pull`, funds move with `transfer_from` against `from`'s allowance
+    /// to this contract instead of `from`'s own authorization.
+    fn transfer_split(
+        env: &Env,
+        token: &Address,
+        from: &Address,
+        accounts: &AccountGroup,
+        total_amount: i128,
+        pull: bool,
+    ) -> Result<Vec<Allocation>, RemittanceSplitError> {
+        Self::apply_due_config(env, from);
+        let (allocations, recipients) = Self::plan_distribution(env, from, accounts, total_amount)?;
+
+        let client = TokenClient::new(env, token);
+        let spender = env.current_contract_address();
+        for (recipient, allocation) in recipients.iter().zip(allocations.iter()) {
+            if allocation.amount <= 0 {
+                continue;
+            }
+            if pull {
+                client.transfer_from(&spender, from, &recipient, &allocation.amount);
+            } else {
+                client.transfer(from, &recipient, &allocation.amount);
+            }
+        }
+
+        Self::record_distribution(env, from, token, total_amount, &allocations);
+        Ok(allocations)
+    }
+
+    /// Let `delegate` trigger distributions of the owner's `token`, moving
+    /// at most `cap` of it per `period` seconds to `accounts`.
+    ///
+    /// Funds are pulled with `transfer_from`, so the owner must also approve
+    /// this contract as spender on `token`. Re-registering a delegate changes
+    /// its destinations, cap and period but keeps what it spent in the
+    /// current period, unless the token changes.
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_delegate(
+        env: Env,
+        owner: Address,
+        nonce: u64,
+        delegate: Address,
+        token: Address,
+        accounts: AccountGroup,
+        cap: i128,
+        period: u64,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_nonce(&env, &owner, nonce)?;
+
+        if cap <= 0 {
+            Self::append_audit(&env, symbol_short!("deleg_set"), &owner, false);
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+        if period == 0 || delegate == owner {
+            Self::append_audit(&env, symbol_short!("deleg_set"), &owner, false);
+            return Err(RemittanceSplitError::InvalidDelegate);
+        }
 
+        Self::extend_instance_ttl(&env);
+
+        let mut delegates = Self::get_delegates(env.clone(), owner.clone());
+        let entry = match delegates.iter().position(|d| d.delegate == delegate) {
+            Some(i) => {
+                let i = i as u32;
+                let mut existing = delegates.get(i).unwrap();
+                if existing.token != token {
+                    // The spend so far was counted in another token's units.
+                    existing.token = token;
+                    existing.spent = 0;
+                }
+                existing.accounts = accounts;
+                existing.cap = cap;
+                existing.period = period;
+                delegates.set(i, existing.clone());
+                existing
+            }
+            None => {
+                let entry = Delegate {
+                    delegate: delegate.clone(),
+                    token,
+                    accounts,
+                    cap,
+                    period,
+                    period_start: env.ledger().timestamp(),
+                    spent: 0,
+                };
+                delegates.push_back(entry.clone());
+                entry
+            }
+        };
+        Self::save_delegates(&env, &owner, delegates);
+
+        Self::increment_nonce(&env, &owner)?;
+        Self::append_audit(&env, symbol_short!("deleg_set"), &owner, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::DelegateSet),
+            (owner, entry),
+        );
+        Ok(true)
+    }
+
+    /// Revoke `delegate`'s right to distribute on the owner's behalf.
+    pub fn revoke_delegate(
+        env: Env,
+        owner: Address,
+        nonce: u64,
+        delegate: Address,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_nonce(&env, &owner, nonce)?;
+
+        let mut delegates = Self::get_delegates(env.clone(), owner.clone());
+        let index = match delegates.iter().position(|d| d.delegate == delegate) {
+            Some(i) => i as u32,
+            None => {
+                Self::append_audit(&env, symbol_short!("deleg_rm"), &owner, false);
+                return Err(RemittanceSplitError::DelegateNotFound);
+            }
+        };
+
+        Self::extend_instance_ttl(&env);
+
+        delegates.remove(index);
+        Self::save_delegates(&env, &owner, delegates);
+
+        Self::increment_nonce(&env, &owner)?;
+        Self::append_audit(&env, symbol_short!("deleg_rm"), &owner, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::DelegateRevoked),
+            (owner, delegate),
+        );
         Ok(true)
     }
 
-    pub fn get_split(env: &Env) -> Vec<u32> {
+    /// Delegates registered by `owner`, in registration order.
+    pub fn get_delegates(env: Env, owner: Address) -> Vec<Delegate> {
         env.storage()
-            .instance()
-            .get(&symbol_short!("SPLIT"))
-            .unwrap_or_else(|| vec![&env, 50, 30, 15, 5])
+            .persistent()
+            .get(&(STORAGE_DELEGATES, owner))
+            .unwrap_or_else(|| Vec::new(&env))
     }
 
-    pub fn get_config(env: Env) -> Option<SplitConfig> {
-        env.storage().instance().get(&symbol_short!("CONFIG"))
+    fn save_delegates(env: &Env, owner: &Address, delegates: Vec<Delegate>) {
+        let key = (STORAGE_DELEGATES, owner.clone());
+        if delegates.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &delegates);
+            Self::extend_persistent_ttl(env, &key);
+        }
     }
 
-    pub fn calculate_split(
+    /// Distribute `total_amount` of `token` from `owner` on the owner's behalf.
+    ///
+    /// Only a registered delegate may call this, for the token it was
+    /// registered with and within its cap for the current period. Funds go to
+    /// the accounts the owner registered with the delegate. The token must
+    /// still be on the owner's allow-list, and funds
+    /// are pulled with `transfer_from`, so the owner must have approved this
+    /// contract. `nonce` is the delegate's own nonce.
+    pub fn distribute_as_delegate(
         env: Env,
+        delegate: Address,
+        nonce: u64,
+        owner: Address,
+        token: Address,
         total_amount: i128,
-    ) -> Result<Vec<i128>, RemittanceSplitError> {
+    ) -> Result<bool, RemittanceSplitError> {
+        delegate.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_nonce(&env, &delegate, nonce)?;
+
         if total_amount <= 0 {
+            Self::append_audit(&env, symbol_short!("deleg_dst"), &delegate, false);
             return Err(RemittanceSplitError::InvalidAmount);
         }
 
-        let split = Self::get_split(&env);
-        let s0 = split.get(0).unwrap() as i128;
-        let s1 = split.get(1).unwrap() as i128;
-        let s2 = split.get(2).unwrap() as i128;
+        let mut delegates = Self::get_delegates(env.clone(), owner.clone());
+        let index = match delegates.iter().position(|d| d.delegate == delegate) {
+            Some(i) => i as u32,
+            None => {
+                Self::append_audit(&env, symbol_short!("deleg_dst"), &delegate, false);
+                return Err(RemittanceSplitError::Unauthorized);
+            }
+        };
+        let mut entry = delegates.get(index).unwrap();
+        if entry.token != token || !Self::token_allowed(&env, &owner, &token) {
+            Self::append_audit(&env, symbol_short!("deleg_dst"), &delegate, false);
+            return Err(RemittanceSplitError::TokenNotAllowed);
+        }
 
-        let spending = total_amount
-            .checked_mul(s0)
-            .and_then(|n| n.checked_div(100))
-            .ok_or(RemittanceSplitError::Overflow)?;
-        let savings = total_amount
-            .checked_mul(s1)
-            .and_then(|n| n.checked_div(100))
-            .ok_or(RemittanceSplitError::Overflow)?;
-        let bills = total_amount
-            .checked_mul(s2)
-            .and_then(|n| n.checked_div(100))
-            .ok_or(RemittanceSplitError::Overflow)?;
-        // Insurance gets the remainder to handle rounding
-        let insurance = total_amount
-            .checked_sub(spending)
-            .and_then(|n| n.checked_sub(savings))
-            .and_then(|n| n.checked_sub(bills))
+        let now = env.ledger().timestamp();
+        let elapsed = now.saturating_sub(entry.period_start);
+        if elapsed >= entry.period {
+            entry.period_start = now - elapsed % entry.period;
+            entry.spent = 0;
+        }
+        let spent = entry
+            .spent
+            .checked_add(total_amount)
             .ok_or(RemittanceSplitError::Overflow)?;
+        if spent > entry.cap {
+            Self::append_audit(&env, symbol_short!("deleg_dst"), &delegate, false);
+            return Err(RemittanceSplitError::DelegateCapExceeded);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let allocations =
+            Self::transfer_split(&env, &token, &owner, &entry.accounts, total_amount, true)?;
+
+        entry.spent = spent;
+        delegates.set(index, entry);
+        Self::save_delegates(&env, &owner, delegates);
 
-        // Emit SplitCalculated event
+        Self::increment_nonce(&env, &delegate)?;
+        Self::append_audit(&env, symbol_short!("deleg_dst"), &delegate, true);
 
-        let event = SplitCalculatedEvent {
+        let event = DistributedEvent {
+            token,
+            from: owner,
+            delegate: Some(delegate),
             total_amount,
-            spending_amount: spending,
-            savings_amount: savings,
-            bills_amount: bills,
-            insurance_amount: insurance,
-            timestamp: env.ledger().timestamp(),
+            allocations,
+            timestamp: now,
         };
-        env.events().publish((SPLIT_CALCULATED,), event);
+        env.events()
+            .publish((symbol_short!("split"), SplitEvent::Distributed), event);
+        Ok(true)
+    }
+
+    /// Add `token` to the caller's allow-list for `distribute`.
+    pub fn add_allowed_token(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        token: Address,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_nonce(&env, &caller, nonce)?;
+        Self::load_config(&env, &caller).ok_or(RemittanceSplitError::NotInitialized)?;
+
+        Self::extend_instance_ttl(&env);
+
+        let mut tokens = Self::get_allowed_tokens(env.clone(), caller.clone());
+        if !tokens.contains(&token) {
+            tokens.push_back(token.clone());
+            Self::save_allowed_tokens(&env, &caller, &tokens);
+        }
+
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("tok_add"), &caller, true);
         env.events().publish(
-            (symbol_short!("split"), SplitEvent::Calculated),
-            total_amount,
+            (symbol_short!("split"), SplitEvent::TokenAllowed),
+            (caller, token),
         );
-
-        Ok(vec![&env, spending, savings, bills, insurance])
+        Ok(true)
     }
 
-    pub fn distribute_usdc(
+    /// Remove `token` from the caller's allow-list. Its distribution total is kept.
+    pub fn remove_allowed_token(
         env: Env,
-        usdc_contract: Address,
-        from: Address,
+        caller: Address,
         nonce: u64,
-        accounts: AccountGroup,
-        total_amount: i128,
+        token: Address,
     ) -> Result<bool, RemittanceSplitError> {
-        if total_amount <= 0 {
-            Self::append_audit(&env, symbol_short!("distrib"), &from, false);
-            return Err(RemittanceSplitError::InvalidAmount);
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_nonce(&env, &caller, nonce)?;
+
+        let mut tokens = Self::get_allowed_tokens(env.clone(), caller.clone());
+        let index = match tokens.first_index_of(&token) {
+            Some(index) => index,
+            None => {
+                Self::append_audit(&env, symbol_short!("tok_rm"), &caller, false);
+                return Err(RemittanceSplitError::TokenNotAllowed);
+            }
+        };
+
+        Self::extend_instance_ttl(&env);
+
+        tokens.remove(index);
+        Self::save_allowed_tokens(&env, &caller, &tokens);
+
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("tok_rm"), &caller, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::TokenRemoved),
+            (caller, token),
+        );
+        Ok(true)
+    }
+
+    /// Tokens `owner` accepts in `distribute`, in the order they were added.
+    pub fn get_allowed_tokens(env: Env, owner: Address) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&(STORAGE_ALLOWED_TOKENS, owner))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Cumulative amount of `token` distributed from `owner`, across
+    /// `distribute`, `distribute_usdc` and scheduled remittances.
+    pub fn get_distributed_total(env: Env, owner: Address, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&(STORAGE_TOKEN_TOTALS, owner, token))
+            .unwrap_or(0)
+    }
+
+    fn save_allowed_tokens(env: &Env, owner: &Address, tokens: &Vec<Address>) {
+        let key = (STORAGE_ALLOWED_TOKENS, owner.clone());
+        if tokens.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, tokens);
+            Self::extend_persistent_ttl(env, &key);
         }
+    }
 
-        from.require_auth();
-        Self::require_nonce(&env, &from, nonce)?;
+    fn token_allowed(env: &Env, owner: &Address, token: &Address) -> bool {
+        Self::get_allowed_tokens(env.clone(), owner.clone()).contains(token)
+    }
+
+    /// Record a completed distribution: add it to `owner`'s running total for
+    /// `token` and append it to the owner's history. The total saturates
+    /// rather than failing, since the funds have already moved.
+    fn record_distribution(
+        env: &Env,
+        owner: &Address,
+        token: &Address,
+        total_amount: i128,
+        allocations: &Vec<Allocation>,
+    ) {
+        let total_key = (STORAGE_TOKEN_TOTALS, owner.clone(), token.clone());
+        let total = Self::get_distributed_total(env.clone(), owner.clone(), token.clone())
+            .saturating_add(total_amount);
+        env.storage().persistent().set(&total_key, &total);
+        Self::extend_persistent_ttl(env, &total_key);
+
+        let count_key = (STORAGE_DISTRIBUTION_COUNT, owner.clone());
+        let id = Self::distribution_count(env, owner) + 1;
+        let record_key = (STORAGE_DISTRIBUTION, owner.clone(), id);
+        let record = DistributionRecord {
+            id,
+            owner: owner.clone(),
+            timestamp: env.ledger().timestamp(),
+            token: token.clone(),
+            total_amount,
+            allocations: allocations.clone(),
+        };
+        let storage = env.storage().persistent();
+        storage.set(&record_key, &record);
+        storage.set(&count_key, &id);
+        storage.extend_ttl(&record_key, HISTORY_LIFETIME_THRESHOLD, HISTORY_BUMP_AMOUNT);
+        storage.extend_ttl(&count_key, HISTORY_LIFETIME_THRESHOLD, HISTORY_BUMP_AMOUNT);
+    }
+
+    fn distribution_count(env: &Env, owner: &Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&(STORAGE_DISTRIBUTION_COUNT, owner.clone()))
+            .unwrap_or(0)
+    }
+
+    fn load_distribution(env: &Env, owner: &Address, id: u32) -> Option<DistributionRecord> {
+        env.storage()
+            .persistent()
+            .get(&(STORAGE_DISTRIBUTION, owner.clone(), id))
+    }
 
-        let amounts = Self::calculate_split_amounts(&env, total_amount, false)?;
-        let token = TokenClient::new(&env, &usdc_contract);
+    /// Get a page of `owner`'s distributions, oldest first.
+    ///
+    /// # Arguments
+    /// * `owner`  – whose distributions to return
+    /// * `cursor` – start after this distribution id (pass 0 for the first page)
+    /// * `limit`  – max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
+    ///
+    /// When `next_cursor == 0` there are no more pages.
+    pub fn get_distributions(
+        env: Env,
+        owner: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> DistributionPage {
+        let limit = Self::clamp_limit(limit);
+        let last_id = Self::distribution_count(&env, &owner);
+
+        let mut items = Vec::new(&env);
+        let mut id = cursor.saturating_add(1);
+        while id <= last_id && items.len() < limit {
+            if let Some(record) = Self::load_distribution(&env, &owner, id) {
+                items.push_back(record);
+            }
+            id += 1;
+        }
 
-        if amounts[0] > 0 {
-            token.transfer(&from, &accounts.spending, &amounts[0]);
+        let next_cursor = if id <= last_id { id - 1 } else { 0 };
+        let count = items.len();
+        DistributionPage {
+            items,
+            next_cursor,
+            count,
         }
-        if amounts[1] > 0 {
-            token.transfer(&from, &accounts.savings, &amounts[1]);
+    }
+
+    /// Per-token sums of `owner`'s distributions with `from <= timestamp <= to`,
+    /// in the order each token first appears.
+    ///
+    /// Looks at no more than `limit` distributions with ids after `cursor`
+    /// (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT). When
+    /// `next_cursor == 0` there are no more pages.
+    pub fn get_distribution_totals(
+        env: Env,
+        owner: Address,
+        from: u64,
+        to: u64,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<DistributionTotalsPage, RemittanceSplitError> {
+        let last_id = Self::distribution_count(&env, &owner);
+        let end = cursor.saturating_add(Self::clamp_limit(limit)).min(last_id);
+        let mut next_cursor = if end < last_id { end } else { 0 };
+
+        let mut totals: Vec<DistributionTotals> = Vec::new(&env);
+        for id in cursor.saturating_add(1)..=end {
+            let record = match Self::load_distribution(&env, &owner, id) {
+                Some(record) => record,
+                None => continue,
+            };
+            if record.timestamp < from {
+                continue;
+            }
+            // Records are appended in ledger order, so nothing later is in range.
+            if record.timestamp > to {
+                next_cursor = 0;
+                break;
+            }
+
+            let index = totals.iter().position(|t| t.token == record.token);
+            let mut entry = match index {
+                Some(i) => totals.get(i as u32).unwrap(),
+                None => DistributionTotals {
+                    token: record.token.clone(),
+                    count: 0,
+                    total_amount: 0,
+                    allocations: Vec::new(&env),
+                },
+            };
+            entry.count += 1;
+            entry.total_amount = entry
+                .total_amount
+                .checked_add(record.total_amount)
+                .ok_or(RemittanceSplitError::Overflow)?;
+            for allocation in record.allocations.iter() {
+                Self::add_allocation(&mut entry.allocations, allocation)?;
+            }
+
+            match index {
+                Some(i) => totals.set(i as u32, entry),
+                None => totals.push_back(entry),
+            }
         }
-        if amounts[2] > 0 {
-            token.transfer(&from, &accounts.bills, &amounts[2]);
+        Ok(DistributionTotalsPage {
+            totals,
+            next_cursor,
+        })
+    }
+
+    fn add_allocation(
+        allocations: &mut Vec<Allocation>,
+        allocation: Allocation,
+    ) -> Result<(), RemittanceSplitError> {
+        for i in 0..allocations.len() {
+            let mut existing = allocations.get(i).unwrap();
+            if existing.category == allocation.category {
+                existing.amount = existing
+                    .amount
+                    .checked_add(allocation.amount)
+                    .ok_or(RemittanceSplitError::Overflow)?;
+                allocations.set(i, existing);
+                return Ok(());
+            }
         }
-        if amounts[3] > 0 {
-            token.transfer(&from, &accounts.insurance, &amounts[3]);
+        allocations.push_back(allocation);
+        Ok(())
+    }
+
+    /// Clamp a caller-supplied limit to [1, MAX_PAGE_LIMIT].
+    /// A value of 0 is treated as DEFAULT_PAGE_LIMIT.
+    fn clamp_limit(limit: u32) -> u32 {
+        if limit == 0 {
+            DEFAULT_PAGE_LIMIT
+        } else {
+            limit.min(MAX_PAGE_LIMIT)
         }
+    }
 
-        Self::increment_nonce(&env, &from)?;
-        Self::append_audit(&env, symbol_short!("distrib"), &from, true);
-        Ok(true)
+    /// Per-bucket allocations and recipients for distributing `total_amount`
+    /// from `owner`. Every recipient is resolved before any funds move.
+    fn plan_distribution(
+        env: &Env,
+        owner: &Address,
+        accounts: &AccountGroup,
+        total_amount: i128,
+    ) -> Result<(Vec<Allocation>, Vec<Address>), RemittanceSplitError> {
+        let config = Self::resolve_config(env, owner);
+        let amounts = Self::calculate_split_amounts(env, &config, total_amount, false)?;
+        let mut recipients = Vec::new(env);
+        for bucket in config.buckets.iter() {
+            recipients.push_back(Self::bucket_destination(&bucket, accounts)?);
+        }
+        Ok((Self::to_allocations(env, &config, &amounts), recipients))
     }
 
     pub fn get_usdc_balance(env: &Env, usdc_contract: Address, account: Address) -> i128 {
@@ -522,21 +1972,12 @@ impl RemittanceSplit {
 
     pub fn get_split_allocations(
         env: &Env,
+        owner: Address,
         total_amount: i128,
     ) -> Result<Vec<Allocation>, RemittanceSplitError> {
-        let amounts = Self::calculate_split(env.clone(), total_amount)?;
-        let categories = [
-            symbol_short!("SPENDING"),
-            symbol_short!("SAVINGS"),
-            symbol_short!("BILLS"),
-            symbol_short!("INSURANCE"),
-        ];
-
-        let mut result = Vec::new(env);
-        for (category, amount) in categories.into_iter().zip(amounts.into_iter()) {
-            result.push_back(Allocation { category, amount });
-        }
-        Ok(result)
+        let config = Self::resolve_config(env, &owner);
+        let amounts = Self::calculate_split_amounts(env, &config, total_amount, true)?;
+        Ok(Self::to_allocations(env, &config, &amounts))
     }
 
     pub fn get_nonce(env: Env, address: Address) -> u64 {
@@ -552,20 +1993,18 @@ impl RemittanceSplit {
             .unwrap_or(0)
     }
 
+    /// Export `owner`'s configuration. Returns `None` when the owner has no
+    /// stored configuration (the default split is not exported).
     pub fn export_snapshot(
         env: Env,
-        caller: Address,
+        owner: Address,
     ) -> Result<Option<ExportSnapshot>, RemittanceSplitError> {
-        caller.require_auth();
-        let config: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        if config.owner != caller {
-            return Err(RemittanceSplitError::Unauthorized);
-        }
-        let checksum = Self::compute_checksum(SNAPSHOT_VERSION, &config);
+        owner.require_auth();
+        let config = match Self::load_config(&env, &owner) {
+            Some(config) => config,
+            None => return Ok(None),
+        };
+        let checksum = Self::compute_checksum(&env, &config);
         Ok(Some(ExportSnapshot {
             version: SNAPSHOT_VERSION,
             checksum,
@@ -573,61 +2012,44 @@ impl RemittanceSplit {
         }))
     }
 
+    /// Restore `owner`'s configuration from a snapshot. The snapshot must have
+    /// been exported for the same owner; any existing configuration is replaced.
     pub fn import_snapshot(
         env: Env,
-        caller: Address,
+        owner: Address,
         nonce: u64,
         snapshot: ExportSnapshot,
     ) -> Result<bool, RemittanceSplitError> {
-        caller.require_auth();
-        Self::require_nonce(&env, &caller, nonce)?;
+        owner.require_auth();
+        Self::require_nonce(&env, &owner, nonce)?;
 
         if snapshot.version != SNAPSHOT_VERSION {
-            Self::append_audit(&env, symbol_short!("import"), &caller, false);
+            Self::append_audit(&env, symbol_short!("import"), &owner, false);
             return Err(RemittanceSplitError::UnsupportedVersion);
         }
-        let expected = Self::compute_checksum(snapshot.version, &snapshot.config);
+        let expected = Self::compute_checksum(&env, &snapshot.config);
         if snapshot.checksum != expected {
-            Self::append_audit(&env, symbol_short!("import"), &caller, false);
+            Self::append_audit(&env, symbol_short!("import"), &owner, false);
             return Err(RemittanceSplitError::ChecksumMismatch);
         }
 
-        let existing: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        if existing.owner != caller {
-            Self::append_audit(&env, symbol_short!("import"), &caller, false);
+        if snapshot.config.owner != owner {
+            Self::append_audit(&env, symbol_short!("import"), &owner, false);
             return Err(RemittanceSplitError::Unauthorized);
         }
 
-        let total = snapshot.config.spending_percent
-            + snapshot.config.savings_percent
-            + snapshot.config.bills_percent
-            + snapshot.config.insurance_percent;
-        if total != 100 {
-            Self::append_audit(&env, symbol_short!("import"), &caller, false);
-            return Err(RemittanceSplitError::PercentagesDoNotSumTo100);
+        let config = &snapshot.config;
+        if let Err(e) = Self::validate_split(&config.buckets, &config.remainder_policy) {
+            Self::append_audit(&env, symbol_short!("import"), &owner, false);
+            return Err(e);
         }
 
         Self::extend_instance_ttl(&env);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("CONFIG"), &snapshot.config);
-        env.storage().instance().set(
-            &symbol_short!("SPLIT"),
-            &vec![
-                &env,
-                snapshot.config.spending_percent,
-                snapshot.config.savings_percent,
-                snapshot.config.bills_percent,
-                snapshot.config.insurance_percent,
-            ],
-        );
+        let queued = Self::commit_config(&env, &snapshot.config);
 
-        Self::increment_nonce(&env, &caller)?;
-        Self::append_audit(&env, symbol_short!("import"), &caller, true);
+        Self::increment_nonce(&env, &owner)?;
+        Self::append_audit(&env, symbol_short!("import"), &owner, true);
+        Self::publish_change(&env, owner, queued);
         Ok(true)
     }
 
@@ -678,17 +2100,10 @@ impl RemittanceSplit {
         Ok(())
     }
 
-    fn compute_checksum(version: u32, config: &SplitConfig) -> u64 {
-        let v = version as u64;
-        let s = config.spending_percent as u64;
-        let g = config.savings_percent as u64;
-        let b = config.bills_percent as u64;
-        let i = config.insurance_percent as u64;
-        v.wrapping_add(s)
-            .wrapping_add(g)
-            .wrapping_add(b)
-            .wrapping_add(i)
-            .wrapping_mul(31)
+    /// SHA-256 over the XDR encoding of `config`; matches
+    /// `data_migration::ExportSnapshot::compute_checksum`.
+    fn compute_checksum(env: &Env, config: &SplitConfig) -> BytesN<32> {
+        env.crypto().sha256(&config.clone().to_xdr(env)).to_bytes()
     }
 
     fn append_audit(env: &Env, operation: Symbol, caller: &Address, success: bool) {
@@ -716,45 +2131,123 @@ impl RemittanceSplit {
         env.storage().instance().set(&symbol_short!("AUDIT"), &log);
     }
 
+    /// Split `total_amount` across `config`'s buckets, in bucket order.
+    ///
+    /// Rules are applied in this order:
+    /// 1. Fixed-amount buckets are paid first.
+    /// 2. The rest is split by `share_bps`. A bucket outside its minimum or
+    ///    maximum is clamped and the difference is shared among the remaining
+    ///    buckets in proportion to their shares, until no bound is violated.
+    /// 3. Each share is rounded down and the leftover units are handed out
+    ///    according to the config's remainder policy.
     fn calculate_split_amounts(
         env: &Env,
+        config: &SplitConfig,
         total_amount: i128,
         emit_events: bool,
-    ) -> Result<[i128; 4], RemittanceSplitError> {
+    ) -> Result<Vec<i128>, RemittanceSplitError> {
         if total_amount <= 0 {
             return Err(RemittanceSplitError::InvalidAmount);
         }
 
-        let split = Self::get_split(env);
-        let s0 = split.get(0).unwrap() as i128;
-        let s1 = split.get(1).unwrap() as i128;
-        let s2 = split.get(2).unwrap() as i128;
-
-        let spending = total_amount
-            .checked_mul(s0)
-            .and_then(|n| n.checked_div(100))
-            .ok_or(RemittanceSplitError::Overflow)?;
-        let savings = total_amount
-            .checked_mul(s1)
-            .and_then(|n| n.checked_div(100))
-            .ok_or(RemittanceSplitError::Overflow)?;
-        let bills = total_amount
-            .checked_mul(s2)
-            .and_then(|n| n.checked_div(100))
+        let mut amounts = Vec::new(env);
+        // Fractional part of each bucket's exact share, scaled by the sum of
+        // the shares it was computed from. Zero for clamped buckets.
+        let mut fractions = Vec::new(env);
+        // Buckets whose amount is final: fixed amounts and clamped buckets.
+        let mut settled = Vec::new(env);
+        let mut fixed_total: i128 = 0;
+        let mut min_total: i128 = 0;
+        for bucket in config.buckets.iter() {
+            let fixed = bucket.fixed_amount.unwrap_or(0);
+            fixed_total = fixed_total
+                .checked_add(fixed)
+                .ok_or(RemittanceSplitError::Overflow)?;
+            min_total = min_total
+                .checked_add(bucket.min_amount.unwrap_or(0))
+                .ok_or(RemittanceSplitError::Overflow)?;
+            amounts.push_back(fixed);
+            fractions.push_back(0i128);
+            settled.push_back(bucket.fixed_amount.is_some());
+        }
+        let mut pool = total_amount
+            .checked_sub(fixed_total)
             .ok_or(RemittanceSplitError::Overflow)?;
-        let insurance = total_amount
-            .checked_sub(spending)
-            .and_then(|n| n.checked_sub(savings))
-            .and_then(|n| n.checked_sub(bills))
+        if pool < min_total {
+            return Err(RemittanceSplitError::InsufficientAmount);
+        }
+
+        // Each pass either finds no violated bound or settles at least one
+        // bucket. When the amount over maximums outweighs the amount under
+        // minimums, the remaining buckets can only grow, so every bucket over
+        // its maximum stays there; otherwise they can only shrink and every
+        // bucket under its minimum stays there. Settling that side is final.
+        loop {
+            let mut shares: u32 = 0;
+            for (i, bucket) in config.buckets.iter().enumerate() {
+                if !settled.get(i as u32).unwrap_or(true) {
+                    shares += bucket.share_bps;
+                }
+            }
+
+            let mut over: i128 = 0;
+            let mut under: i128 = 0;
+            for (i, bucket) in config.buckets.iter().enumerate() {
+                let i = i as u32;
+                if settled.get(i).unwrap_or(true) {
+                    continue;
+                }
+                let (amount, fraction) = Self::share_of(pool, bucket.share_bps, shares)
+                    .ok_or(RemittanceSplitError::Overflow)?;
+                amounts.set(i, amount);
+                fractions.set(i, fraction);
+                if let Some(max) = bucket.max_amount.filter(|max| amount > *max) {
+                    over += amount - max;
+                }
+                if let Some(min) = bucket.min_amount.filter(|min| amount < *min) {
+                    under += min - amount;
+                }
+            }
+            if over == 0 && under == 0 {
+                break;
+            }
+
+            for (i, bucket) in config.buckets.iter().enumerate() {
+                let i = i as u32;
+                if settled.get(i).unwrap_or(true) {
+                    continue;
+                }
+                let amount = amounts.get(i).unwrap_or(0);
+                let bound = match (bucket.max_amount, bucket.min_amount) {
+                    (Some(max), _) if over >= under && amount > max => max,
+                    (_, Some(min)) if under >= over && amount < min => min,
+                    _ => continue,
+                };
+                amounts.set(i, bound);
+                fractions.set(i, 0);
+                settled.set(i, true);
+                pool -= bound;
+            }
+        }
+
+        let mut allocated: i128 = 0;
+        for amount in amounts.iter() {
+            allocated = allocated
+                .checked_add(amount)
+                .ok_or(RemittanceSplitError::Overflow)?;
+        }
+        let remainder = total_amount
+            .checked_sub(allocated)
             .ok_or(RemittanceSplitError::Overflow)?;
+        if remainder < 0 {
+            return Err(RemittanceSplitError::InsufficientAmount);
+        }
+        Self::assign_remainder(env, config, &mut amounts, &fractions, remainder);
 
         if emit_events {
             let event = SplitCalculatedEvent {
                 total_amount,
-                spending_amount: spending,
-                savings_amount: savings,
-                bills_amount: bills,
-                insurance_amount: insurance,
+                allocations: Self::to_allocations(env, config, &amounts),
                 timestamp: env.ledger().timestamp(),
             };
             env.events().publish((SPLIT_CALCULATED,), event);
@@ -764,7 +2257,369 @@ impl RemittanceSplit {
             );
         }
 
-        Ok([spending, savings, bills, insurance])
+        Ok(amounts)
+    }
+
+    /// `amount * share / shares` rounded down, plus the discarded fraction
+    /// scaled by `shares`. Does not overflow for amounts close to `i128::MAX`.
+    fn share_of(amount: i128, share: u32, shares: u32) -> Option<(i128, i128)> {
+        if shares == 0 {
+            return Some((0, 0));
+        }
+        let shares = shares as i128;
+        let share = share as i128;
+        let whole = (amount / shares).checked_mul(share)?;
+        let part = (amount % shares).checked_mul(share)?;
+        Some((whole.checked_add(part / shares)?, part % shares))
+    }
+
+    /// Hand out `remainder` rounding units according to the remainder policy.
+    ///
+    /// Buckets are tried in the policy's order of preference and never pushed
+    /// above their maximum. `LargestRemainder` gives one unit per bucket per
+    /// round; the other policies give each bucket as much as it can take.
+    /// Validation guarantees at least one uncapped percentage bucket, so the
+    /// whole remainder is always placed.
+    fn assign_remainder(
+        env: &Env,
+        config: &SplitConfig,
+        amounts: &mut Vec<i128>,
+        fractions: &Vec<i128>,
+        mut remainder: i128,
+    ) {
+        let order = Self::remainder_order(env, config, fractions);
+        let one_each = config.remainder_policy == RemainderPolicy::LargestRemainder;
+        while remainder > 0 {
+            for i in order.iter() {
+                if remainder == 0 {
+                    break;
+                }
+                let bucket = match config.buckets.get(i) {
+                    Some(bucket) => bucket,
+                    None => continue,
+                };
+                let amount = amounts.get(i).unwrap_or(0);
+                let room = bucket.max_amount.map_or(remainder, |max| max - amount);
+                let give = if one_each { 1 } else { remainder }.min(room);
+                if give > 0 {
+                    amounts.set(i, amount + give);
+                    remainder -= give;
+                }
+            }
+        }
+    }
+
+    /// Indices of the buckets that may receive rounding dust, most preferred
+    /// first. Ties keep bucket order.
+    fn remainder_order(env: &Env, config: &SplitConfig, fractions: &Vec<i128>) -> Vec<u32> {
+        let mut order = Vec::new(env);
+        let mut taken = Vec::new(env);
+        for bucket in config.buckets.iter() {
+            taken.push_back(bucket.fixed_amount.is_some());
+        }
+        if let RemainderPolicy::NamedBucket(name) = &config.remainder_policy {
+            for (i, bucket) in config.buckets.iter().enumerate() {
+                let i = i as u32;
+                if bucket.name == *name && !taken.get(i).unwrap_or(true) {
+                    order.push_back(i);
+                    taken.set(i, true);
+                }
+            }
+        }
+        loop {
+            let mut best: Option<(u32, i128)> = None;
+            for (i, bucket) in config.buckets.iter().enumerate() {
+                let i = i as u32;
+                if taken.get(i).unwrap_or(true) {
+                    continue;
+                }
+                let key = match config.remainder_policy {
+                    RemainderPolicy::LargestRemainder => fractions.get(i).unwrap_or(0),
+                    _ => bucket.share_bps as i128,
+                };
+                if best.is_none_or(|(_, k)| key > k) {
+                    best = Some((i, key));
+                }
+            }
+            match best {
+                Some((i, _)) => {
+                    order.push_back(i);
+                    taken.set(i, true);
+                }
+                None => break,
+            }
+        }
+        order
+    }
+
+    fn to_allocations(env: &Env, config: &SplitConfig, amounts: &Vec<i128>) -> Vec<Allocation> {
+        let mut result = Vec::new(env);
+        for (bucket, amount) in config.buckets.iter().zip(amounts.iter()) {
+            result.push_back(Allocation {
+                category: bucket.name,
+                amount,
+            });
+        }
+        result
+    }
+
+    /// Recipient for `bucket`: its own destination, or the `accounts` entry
+    /// for one of the four legacy bucket names.
+    fn bucket_destination(
+        bucket: &SplitBucket,
+        accounts: &AccountGroup,
+    ) -> Result<Address, RemittanceSplitError> {
+        if let Some(destination) = &bucket.destination {
+            return Ok(destination.clone());
+        }
+        if bucket.name == BUCKET_SPENDING {
+            Ok(accounts.spending.clone())
+        } else if bucket.name == BUCKET_SAVINGS {
+            Ok(accounts.savings.clone())
+        } else if bucket.name == BUCKET_BILLS {
+            Ok(accounts.bills.clone())
+        } else if bucket.name == BUCKET_INSURANCE {
+            Ok(accounts.insurance.clone())
+        } else {
+            Err(RemittanceSplitError::MissingDestination)
+        }
+    }
+
+    fn validate_split(
+        buckets: &Vec<SplitBucket>,
+        remainder_policy: &RemainderPolicy,
+    ) -> Result<(), RemittanceSplitError> {
+        Self::validate_buckets(buckets)?;
+        if let RemainderPolicy::NamedBucket(name) = remainder_policy {
+            if !buckets
+                .iter()
+                .any(|b| b.name == *name && b.fixed_amount.is_none())
+            {
+                return Err(RemittanceSplitError::InvalidRemainderPolicy);
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_buckets(buckets: &Vec<SplitBucket>) -> Result<(), RemittanceSplitError> {
+        if buckets.is_empty() || buckets.len() > MAX_BUCKETS {
+            return Err(RemittanceSplitError::InvalidBuckets);
+        }
+        let mut total: u32 = 0;
+        let mut has_uncapped_share = false;
+        for (i, bucket) in buckets.iter().enumerate() {
+            for other in buckets.iter().skip(i + 1) {
+                if other.name == bucket.name {
+                    return Err(RemittanceSplitError::InvalidBuckets);
+                }
+            }
+            Self::validate_bucket_rules(&bucket)?;
+            total = total
+                .checked_add(bucket.share_bps)
+                .ok_or(RemittanceSplitError::PercentagesDoNotSumTo100)?;
+            has_uncapped_share |= bucket.share_bps > 0 && bucket.max_amount.is_none();
+        }
+        if total != TOTAL_BPS {
+            return Err(RemittanceSplitError::PercentagesDoNotSumTo100);
+        }
+        // Whatever the caps leave over must have somewhere to go.
+        if !has_uncapped_share {
+            return Err(RemittanceSplitError::InvalidBuckets);
+        }
+        Ok(())
+    }
+
+    fn validate_bucket_rules(bucket: &SplitBucket) -> Result<(), RemittanceSplitError> {
+        if let Some(fixed) = bucket.fixed_amount {
+            if fixed <= 0
+                || bucket.share_bps != 0
+                || bucket.min_amount.is_some()
+                || bucket.max_amount.is_some()
+            {
+                return Err(RemittanceSplitError::InvalidBuckets);
+            }
+            return Ok(());
+        }
+        let min = bucket.min_amount.unwrap_or(0);
+        if min < 0 || bucket.max_amount.is_some_and(|max| max < min) {
+            return Err(RemittanceSplitError::InvalidBuckets);
+        }
+        Ok(())
+    }
+
+    /// Remainder policy of configs built from four whole percentages: the
+    /// dust goes to `INSURANCE`, as it always has.
+    fn legacy_remainder_policy() -> RemainderPolicy {
+        RemainderPolicy::NamedBucket(BUCKET_INSURANCE)
+    }
+
+    /// The four legacy buckets for whole-percentage shares.
+    fn legacy_buckets(
+        env: &Env,
+        spending_percent: u32,
+        savings_percent: u32,
+        bills_percent: u32,
+        insurance_percent: u32,
+    ) -> Vec<SplitBucket> {
+        let mut buckets = Vec::new(env);
+        for (name, percent) in [
+            (BUCKET_SPENDING, spending_percent),
+            (BUCKET_SAVINGS, savings_percent),
+            (BUCKET_BILLS, bills_percent),
+            (BUCKET_INSURANCE, insurance_percent),
+        ] {
+            buckets.push_back(SplitBucket {
+                name,
+                share_bps: percent * 100,
+                destination: None,
+                fixed_amount: None,
+                min_amount: None,
+                max_amount: None,
+            });
+        }
+        buckets
+    }
+
+    /// `owner`'s configuration in force: a queued change whose delay has
+    /// passed, otherwise the stored configuration.
+    fn load_config(env: &Env, owner: &Address) -> Option<SplitConfig> {
+        if let Some(pending) = Self::load_pending_config(env, owner) {
+            if pending.effective_at <= env.ledger().timestamp() {
+                return Some(pending.config);
+            }
+        }
+        Self::load_stored_config(env, owner)
+    }
+
+    fn load_stored_config(env: &Env, owner: &Address) -> Option<SplitConfig> {
+        env.storage()
+            .persistent()
+            .get(&(STORAGE_SPLIT_CONFIGS, owner.clone()))
+            .or_else(|| Self::load_legacy_config(env, owner))
+    }
+
+    /// Load the single-tenant pre-bucket configuration if it belongs to
+    /// `owner`, migrated to the bucket layout. The stored entry is replaced
+    /// on the owner's next write.
+    fn load_legacy_config(env: &Env, owner: &Address) -> Option<SplitConfig> {
+        let legacy: LegacySplitConfig = env
+            .storage()
+            .instance()
+            .get(&STORAGE_LEGACY_CONFIG)
+            .filter(|c: &LegacySplitConfig| c.owner == *owner)?;
+        Some(SplitConfig {
+            owner: legacy.owner,
+            buckets: Self::legacy_buckets(
+                env,
+                legacy.spending_percent,
+                legacy.savings_percent,
+                legacy.bills_percent,
+                legacy.insurance_percent,
+            ),
+            remainder_policy: Self::legacy_remainder_policy(),
+            change_delay: 0,
+            timestamp: legacy.timestamp,
+            initialized: legacy.initialized,
+        })
+    }
+
+    fn save_config(env: &Env, config: &SplitConfig) {
+        let key = (STORAGE_SPLIT_CONFIGS, config.owner.clone());
+        env.storage().persistent().set(&key, config);
+        Self::extend_persistent_ttl(env, &key);
+        Self::remove_legacy_config(env, &config.owner);
+        Self::remove_pending_config(env, &config.owner);
+    }
+
+    /// Apply `config` now, or queue it when the owner's configuration in force
+    /// has a change delay. A queued change replaces any earlier one that is
+    /// still waiting; one whose delay has passed is stored first.
+    ///
+    /// Returns when a queued change takes effect.
+    fn commit_config(env: &Env, config: &SplitConfig) -> Option<u64> {
+        Self::apply_due_config(env, &config.owner);
+        let delay = Self::load_config(env, &config.owner).map_or(0, |c| c.change_delay);
+        if delay == 0 {
+            Self::save_config(env, config);
+            return None;
+        }
+
+        let effective_at = env.ledger().timestamp().saturating_add(delay);
+        let key = (STORAGE_PENDING_CONFIGS, config.owner.clone());
+        env.storage().persistent().set(
+            &key,
+            &PendingSplitConfig {
+                config: config.clone(),
+                effective_at,
+            },
+        );
+        Self::extend_persistent_ttl(env, &key);
+        Some(effective_at)
+    }
+
+    /// Publish `Updated`, or `ChangeQueued` with the time a queued change
+    /// takes effect.
+    fn publish_change(env: &Env, owner: Address, queued: Option<u64>) {
+        match queued {
+            None => env
+                .events()
+                .publish((symbol_short!("split"), SplitEvent::Updated), owner),
+            Some(effective_at) => env.events().publish(
+                (symbol_short!("split"), SplitEvent::ChangeQueued),
+                (owner, effective_at),
+            ),
+        }
+    }
+
+    /// Store `owner`'s queued change as the active configuration once its
+    /// delay has passed.
+    fn apply_due_config(env: &Env, owner: &Address) {
+        if let Some(pending) = Self::load_pending_config(env, owner) {
+            if pending.effective_at <= env.ledger().timestamp() {
+                Self::save_config(env, &pending.config);
+                env.events()
+                    .publish((symbol_short!("split"), SplitEvent::Updated), owner.clone());
+            }
+        }
+    }
+
+    fn load_pending_config(env: &Env, owner: &Address) -> Option<PendingSplitConfig> {
+        env.storage()
+            .persistent()
+            .get(&(STORAGE_PENDING_CONFIGS, owner.clone()))
+    }
+
+    fn remove_pending_config(env: &Env, owner: &Address) {
+        env.storage()
+            .persistent()
+            .remove(&(STORAGE_PENDING_CONFIGS, owner.clone()));
+    }
+
+    fn remove_legacy_config(env: &Env, owner: &Address) {
+        let single: Option<LegacySplitConfig> =
+            env.storage().instance().get(&STORAGE_LEGACY_CONFIG);
+        if single.is_some_and(|c| c.owner == *owner) {
+            env.storage().instance().remove(&STORAGE_LEGACY_CONFIG);
+        }
+    }
+
+    /// `owner`'s stored configuration, or the default split (with
+    /// `initialized == false`) when none has been stored.
+    fn resolve_config(env: &Env, owner: &Address) -> SplitConfig {
+        Self::load_config(env, owner).unwrap_or_else(|| SplitConfig {
+            owner: owner.clone(),
+            buckets: Self::legacy_buckets(
+                env,
+                DEFAULT_SPENDING_PERCENT,
+                DEFAULT_SAVINGS_PERCENT,
+                DEFAULT_BILLS_PERCENT,
+                DEFAULT_INSURANCE_PERCENT,
+            ),
+            remainder_policy: Self::legacy_remainder_policy(),
+            change_delay: 0,
+            timestamp: 0,
+            initialized: false,
+        })
     }
 
     /// Extend the TTL of instance storage
@@ -774,9 +2629,63 @@ impl RemittanceSplit {
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
     }
 
+    /// Extend the TTL of a per-owner or per-schedule persistent entry.
+    fn extend_persistent_ttl<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+        env.storage().persistent().extend_ttl(
+            key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn load_schedule(env: &Env, schedule_id: u32) -> Option<RemittanceSchedule> {
+        env.storage()
+            .persistent()
+            .get(&(STORAGE_SCHEDULES, schedule_id))
+    }
+
+    fn save_schedule(env: &Env, schedule: &RemittanceSchedule) {
+        let key = (STORAGE_SCHEDULES, schedule.id);
+        env.storage().persistent().set(&key, schedule);
+        Self::extend_persistent_ttl(env, &key);
+    }
+
+    /// IDs of `owner`'s schedules, ascending.
+    fn owner_schedule_ids(env: &Env, owner: &Address) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&(STORAGE_OWNER_SCHEDULE_IDS, owner.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// IDs of `owner`'s schedules parked by `migrate_storage`.
+    fn owner_legacy_schedule_ids(env: &Env, owner: &Address) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&(STORAGE_OWNER_LEGACY_IDS, owner.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn append_owner_schedule_id(env: &Env, owner: &Address, schedule_id: u32) {
+        let key = (STORAGE_OWNER_SCHEDULE_IDS, owner.clone());
+        let mut ids = Self::owner_schedule_ids(env, owner);
+        if let Err(position) = ids.binary_search(schedule_id) {
+            ids.insert(position, schedule_id);
+            env.storage().persistent().set(&key, &ids);
+        }
+        Self::extend_persistent_ttl(env, &key);
+    }
+
+    /// Schedule a distribution of `amount` of `token` from `owner`, first due at
+    /// `next_due` and repeating every `interval` seconds (0 = one-shot).
+    ///
+    /// Executions pull funds with `transfer_from`, so `owner` must approve this
+    /// contract as spender on `token`.
     pub fn create_remittance_schedule(
         env: Env,
         owner: Address,
+        token: Address,
+        accounts: AccountGroup,
         amount: i128,
         next_due: u64,
         interval: u64,
@@ -791,25 +2700,24 @@ impl RemittanceSplit {
         if next_due <= current_time {
             return Err(RemittanceSplitError::InvalidDueDate);
         }
+        if !Self::token_allowed(&env, &owner, &token) {
+            return Err(RemittanceSplitError::TokenNotAllowed);
+        }
 
         Self::extend_instance_ttl(&env);
 
-        let mut schedules: Map<u32, RemittanceSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("REM_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
-
         let next_schedule_id = env
             .storage()
             .instance()
-            .get(&symbol_short!("NEXT_RSCH"))
+            .get(&STORAGE_NEXT_SCHEDULE_ID)
             .unwrap_or(0u32)
             + 1;
 
         let schedule = RemittanceSchedule {
             id: next_schedule_id,
             owner: owner.clone(),
+            token,
+            accounts,
             amount,
             next_due,
             interval,
@@ -820,13 +2728,11 @@ impl RemittanceSplit {
             missed_count: 0,
         };
 
-        schedules.set(next_schedule_id, schedule);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("REM_SCH"), &schedules);
+        Self::save_schedule(&env, &schedule);
+        Self::append_owner_schedule_id(&env, &owner, next_schedule_id);
         env.storage()
             .instance()
-            .set(&symbol_short!("NEXT_RSCH"), &next_schedule_id);
+            .set(&STORAGE_NEXT_SCHEDULE_ID, &next_schedule_id);
 
         env.events().publish(
             (symbol_short!("schedule"), ScheduleEvent::Created),
@@ -857,15 +2763,8 @@ impl RemittanceSplit {
 
         Self::extend_instance_ttl(&env);
 
-        let mut schedules: Map<u32, RemittanceSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("REM_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut schedule = schedules
-            .get(schedule_id)
-            .ok_or(RemittanceSplitError::ScheduleNotFound)?;
+        let mut schedule =
+            Self::load_schedule(&env, schedule_id).ok_or(RemittanceSplitError::ScheduleNotFound)?;
 
         if schedule.owner != caller {
             return Err(RemittanceSplitError::Unauthorized);
@@ -876,10 +2775,7 @@ impl RemittanceSplit {
         schedule.interval = interval;
         schedule.recurring = interval > 0;
 
-        schedules.set(schedule_id, schedule);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("REM_SCH"), &schedules);
+        Self::save_schedule(&env, &schedule);
 
         env.events().publish(
             (symbol_short!("schedule"), ScheduleEvent::Modified),
@@ -898,15 +2794,8 @@ impl RemittanceSplit {
 
         Self::extend_instance_ttl(&env);
 
-        let mut schedules: Map<u32, RemittanceSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("REM_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut schedule = schedules
-            .get(schedule_id)
-            .ok_or(RemittanceSplitError::ScheduleNotFound)?;
+        let mut schedule =
+            Self::load_schedule(&env, schedule_id).ok_or(RemittanceSplitError::ScheduleNotFound)?;
 
         if schedule.owner != caller {
             return Err(RemittanceSplitError::Unauthorized);
@@ -914,10 +2803,7 @@ impl RemittanceSplit {
 
         schedule.active = false;
 
-        schedules.set(schedule_id, schedule);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("REM_SCH"), &schedules);
+        Self::save_schedule(&env, &schedule);
 
         env.events().publish(
             (symbol_short!("schedule"), ScheduleEvent::Cancelled),
@@ -927,16 +2813,147 @@ impl RemittanceSplit {
         Ok(true)
     }
 
-    pub fn get_remittance_schedules(env: Env, owner: Address) -> Vec<RemittanceSchedule> {
-        let schedules: Map<u32, RemittanceSchedule> = env
+    /// Execute due schedules with ids after `cursor`, looking at no more than
+    /// `limit` schedules (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT).
+    /// Pass `next_cursor` back to continue; 0 means the sweep reached the last
+    /// schedule. Anyone may call this, e.g. a keeper bot.
+    ///
+    /// Each due schedule pulls its amount from the owner with `transfer_from`
+    /// and splits it like `distribute`. A schedule that cannot be paid (its
+    /// token is off the owner's allow-list, the owner's balance or allowance
+    /// is short, or the split fails) moves no funds, is reported in `failed`
+    /// instead of reverting the batch and counts as missed. Recurring
+    /// schedules then advance `next_due` to the first future slot, counting
+    /// any slots skipped in between as missed too; one-shot schedules are
+    /// deactivated.
+    pub fn execute_due_remittance_schedules(
+        env: Env,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<ScheduleRun, RemittanceSplitError> {
+        Self::require_not_paused(&env)?;
+        Self::extend_instance_ttl(&env);
+
+        let current_time = env.ledger().timestamp();
+        let mut executed = Vec::new(&env);
+        let mut failed = Vec::new(&env);
+
+        let last_id: u32 = env
             .storage()
             .instance()
-            .get(&symbol_short!("REM_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+            .get(&STORAGE_NEXT_SCHEDULE_ID)
+            .unwrap_or(0);
+        let end = cursor.saturating_add(Self::clamp_limit(limit)).min(last_id);
+
+        for schedule_id in cursor.saturating_add(1)..=end {
+            let mut schedule = match Self::load_schedule(&env, schedule_id) {
+                Some(schedule) => schedule,
+                None => continue,
+            };
+            if !schedule.active || schedule.next_due > current_time {
+                continue;
+            }
+
+            let mut missed: u32 = 0;
+            match Self::pay_schedule(&env, &schedule) {
+                Ok(()) => {
+                    schedule.last_executed = Some(current_time);
+                    executed.push_back(schedule_id);
+                    env.events().publish(
+                        (symbol_short!("schedule"), ScheduleEvent::Executed),
+                        (schedule_id, schedule.owner.clone()),
+                    );
+                }
+                Err(reason) => {
+                    missed = 1;
+                    failed.push_back(Self::record_schedule_failure(&env, schedule_id, reason));
+                }
+            }
+
+            if schedule.recurring && schedule.interval > 0 {
+                let skipped = (current_time - schedule.next_due) / schedule.interval;
+                missed = missed.saturating_add(u32::try_from(skipped).unwrap_or(u32::MAX));
+                schedule.next_due = skipped
+                    .saturating_add(1)
+                    .saturating_mul(schedule.interval)
+                    .saturating_add(schedule.next_due);
+            } else {
+                schedule.active = false;
+            }
+
+            if missed > 0 {
+                schedule.missed_count = schedule.missed_count.saturating_add(missed);
+                env.events().publish(
+                    (symbol_short!("schedule"), ScheduleEvent::Missed),
+                    (schedule_id, missed),
+                );
+            }
+
+            Self::save_schedule(&env, &schedule);
+        }
+
+        Ok(ScheduleRun {
+            executed,
+            failed,
+            next_cursor: if end < last_id { end } else { 0 },
+        })
+    }
+
+    /// Pull and split one schedule's amount. Nothing moves when it fails.
+    fn pay_schedule(env: &Env, schedule: &RemittanceSchedule) -> Result<(), ScheduleFailureReason> {
+        if !Self::token_allowed(env, &schedule.owner, &schedule.token) {
+            return Err(ScheduleFailureReason::TokenNotAllowed);
+        }
+        let token = TokenClient::new(env, &schedule.token);
+        if token.balance(&schedule.owner) < schedule.amount
+            || token.allowance(&schedule.owner, &env.current_contract_address()) < schedule.amount
+        {
+            return Err(ScheduleFailureReason::InsufficientFunds);
+        }
+
+        Self::transfer_split(
+            env,
+            &schedule.token,
+            &schedule.owner,
+            &schedule.accounts,
+            schedule.amount,
+            true,
+        )
+        .map(|_| ())
+        .map_err(|_| ScheduleFailureReason::SplitFailed)
+    }
+
+    fn record_schedule_failure(
+        env: &Env,
+        schedule_id: u32,
+        reason: ScheduleFailureReason,
+    ) -> ScheduleFailure {
+        let entry = ScheduleFailure {
+            schedule_id,
+            reason,
+            timestamp: env.ledger().timestamp(),
+        };
+        let key = (STORAGE_SCHEDULE_FAILURES, schedule_id);
+        env.storage().persistent().set(&key, &entry);
+        Self::extend_persistent_ttl(env, &key);
+        env.events().publish(
+            (symbol_short!("schedule"), ScheduleEvent::Failed),
+            (schedule_id, reason),
+        );
+        entry
+    }
+
+    /// The most recent failure recorded for `schedule_id`, if any.
+    pub fn get_schedule_failure(env: Env, schedule_id: u32) -> Option<ScheduleFailure> {
+        env.storage()
+            .persistent()
+            .get(&(STORAGE_SCHEDULE_FAILURES, schedule_id))
+    }
 
+    pub fn get_remittance_schedules(env: Env, owner: Address) -> Vec<RemittanceSchedule> {
         let mut result = Vec::new(&env);
-        for (_, schedule) in schedules.iter() {
-            if schedule.owner == owner {
+        for schedule_id in Self::owner_schedule_ids(&env, &owner).iter() {
+            if let Some(schedule) = Self::load_schedule(&env, schedule_id) {
                 result.push_back(schedule);
             }
         }
@@ -944,19 +2961,15 @@ impl RemittanceSplit {
     }
 
     pub fn get_remittance_schedule(env: Env, schedule_id: u32) -> Option<RemittanceSchedule> {
-        let schedules: Map<u32, RemittanceSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("REM_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        schedules.get(schedule_id)
+        Self::load_schedule(&env, schedule_id)
     }
 }
 
 #[cfg(test)]
 mod test;
-mod test {
+
+#[cfg(test)]
+mod event_tests {
     use super::*;
     use soroban_sdk::testutils::storage::Instance as _;
     use soroban_sdk::testutils::{Address as _, Events, Ledger, LedgerInfo};
@@ -994,7 +3007,7 @@ mod test {
         let events_before = env.events().all().len();
 
         // Calculate split
-        let result = client.calculate_split(&1000);
+        let result = client.calculate_split(&owner, &1000);
         assert_eq!(result.len(), 4);
         assert_eq!(result.get(0).unwrap(), 400); // 40% of 1000
         assert_eq!(result.get(1).unwrap(), 300); // 30% of 1000
@@ -1018,8 +3031,8 @@ mod test {
         client.initialize_split(&owner, &0, &50, &25, &15, &10);
 
         // Calculate split twice
-        client.calculate_split(&2000);
-        client.calculate_split(&3000);
+        client.calculate_split(&owner, &2000);
+        client.calculate_split(&owner, &3000);
 
         // Should have 5 events total (1 init + 2*2 calc)
         let events = env.events().all();
@@ -1179,18 +3192,18 @@ mod test {
         });
 
         // Calculate split to exercise read path
-        let result = client.calculate_split(&1000);
+        let result = client.calculate_split(&owner, &1000);
         assert_eq!(result.len(), 4);
 
         // Config should be accessible with updated values
-        let config = client.get_config();
+        let config = client.get_config(&owner);
         assert!(
             config.is_some(),
             "Config must persist across ledger advancements"
         );
-        let config = config.unwrap();
-        assert_eq!(config.spending_percent, 40);
-        assert_eq!(config.savings_percent, 25);
+        let config = config.unwrap().active;
+        assert_eq!(config.buckets.get(0).unwrap().share_bps, 4000);
+        assert_eq!(config.buckets.get(1).unwrap().share_bps, 2500);
 
         // TTL is still valid (within the second extension window)
         let ttl = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
@@ -1206,6 +3219,7 @@ mod test {
     // ============================================================================
 
     /// 1. test_initialize_split_success
+    ///
     /// Owner authorizes the call, percentages sum to 100, config is stored correctly.
     #[test]
     fn test_initialize_split_success() {
@@ -1219,17 +3233,19 @@ mod test {
         assert!(result, "initialize_split should return true on success");
 
         let config = client
-            .get_config()
+            .get_config(&owner)
             .expect("config should be stored after init");
+        let config = config.active;
         assert_eq!(config.owner, owner);
-        assert_eq!(config.spending_percent, 50);
-        assert_eq!(config.savings_percent, 30);
-        assert_eq!(config.bills_percent, 15);
-        assert_eq!(config.insurance_percent, 5);
+        assert_eq!(config.buckets.get(0).unwrap().share_bps, 5000);
+        assert_eq!(config.buckets.get(1).unwrap().share_bps, 3000);
+        assert_eq!(config.buckets.get(2).unwrap().share_bps, 1500);
+        assert_eq!(config.buckets.get(3).unwrap().share_bps, 500);
         assert!(config.initialized);
     }
 
     /// 2. test_initialize_split_requires_auth
+    ///
     /// Calling initialize_split without the owner authorizing should panic.
     #[test]
     #[should_panic]
@@ -1245,6 +3261,7 @@ mod test {
     }
 
     /// 3. test_initialize_split_percentages_must_sum_to_100
+    ///
     /// Percentages that do not sum to 100 must return PercentagesDoNotSumTo100.
     #[test]
     fn test_initialize_split_percentages_must_sum_to_100() {
@@ -1270,6 +3287,7 @@ mod test {
     }
 
     /// 4. test_initialize_split_already_initialized_panics
+    ///
     /// Calling initialize_split a second time should return AlreadyInitialized.
     #[test]
     fn test_initialize_split_already_initialized_panics() {
@@ -1288,6 +3306,7 @@ mod test {
     }
 
     /// 5. test_update_split_owner_only
+    ///
     /// Only the owner can call update_split; any other address must get Unauthorized.
     #[test]
     fn test_update_split_owner_only() {
@@ -1300,9 +3319,10 @@ mod test {
 
         client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
-        // other address is not the owner — must fail
+        // other address has no split of its own and cannot touch owner's
         let result = client.try_update_split(&other, &0, &40, &40, &10, &10);
-        assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+        assert_eq!(result, Err(Ok(RemittanceSplitError::NotInitialized)));
+        assert_eq!(client.get_split(&owner).get(0).unwrap(), 5000);
 
         // owner can update just fine
         let ok = client.update_split(&owner, &1, &40, &40, &10, &10);
@@ -1310,6 +3330,7 @@ mod test {
     }
 
     /// 6. test_update_split_percentages_must_sum_to_100
+    ///
     /// update_split must reject percentages that do not sum to 100.
     #[test]
     fn test_update_split_percentages_must_sum_to_100() {
@@ -1337,35 +3358,40 @@ mod test {
     }
 
     /// 7. test_get_split_returns_default_before_init
+    ///
     /// Before initialize_split is called, get_split must return the hardcoded
-    /// default of [50, 30, 15, 5].
+    /// default of [5000, 3000, 1500, 500] bps.
     #[test]
     fn test_get_split_returns_default_before_init() {
         let env = Env::default();
         let contract_id = env.register_contract(None, RemittanceSplit);
         let client = RemittanceSplitClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
 
-        let split = client.get_split();
+        let split = client.get_split(&owner);
         assert_eq!(split.len(), 4);
-        assert_eq!(split.get(0).unwrap(), 50);
-        assert_eq!(split.get(1).unwrap(), 30);
-        assert_eq!(split.get(2).unwrap(), 15);
-        assert_eq!(split.get(3).unwrap(), 5);
+        assert_eq!(split.get(0).unwrap(), 5000);
+        assert_eq!(split.get(1).unwrap(), 3000);
+        assert_eq!(split.get(2).unwrap(), 1500);
+        assert_eq!(split.get(3).unwrap(), 500);
     }
 
     /// 8. test_get_config_returns_none_before_init
+    ///
     /// Before initialize_split is called, get_config must return None.
     #[test]
     fn test_get_config_returns_none_before_init() {
         let env = Env::default();
         let contract_id = env.register_contract(None, RemittanceSplit);
         let client = RemittanceSplitClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
 
-        let config = client.get_config();
+        let config = client.get_config(&owner);
         assert!(config.is_none(), "get_config should be None before init");
     }
 
     /// 9. test_get_config_returns_some_after_init
+    ///
     /// After initialize_split, get_config must return Some with correct owner.
     #[test]
     fn test_get_config_returns_some_after_init() {
@@ -1377,21 +3403,22 @@ mod test {
 
         client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
-        let config = client.get_config();
+        let config = client.get_config(&owner);
         assert!(config.is_some(), "get_config should be Some after init");
 
-        let config = config.unwrap();
+        let config = config.unwrap().active;
         assert_eq!(
             config.owner, owner,
             "config owner must match the initializer"
         );
-        assert_eq!(config.spending_percent, 50);
-        assert_eq!(config.savings_percent, 30);
-        assert_eq!(config.bills_percent, 15);
-        assert_eq!(config.insurance_percent, 5);
+        assert_eq!(config.buckets.get(0).unwrap().share_bps, 5000);
+        assert_eq!(config.buckets.get(1).unwrap().share_bps, 3000);
+        assert_eq!(config.buckets.get(2).unwrap().share_bps, 1500);
+        assert_eq!(config.buckets.get(3).unwrap().share_bps, 500);
     }
 
     /// 10. test_calculate_split_positive_amount
+    ///
     /// Correct amounts for a positive total; insurance receives the remainder.
     #[test]
     fn test_calculate_split_positive_amount() {
@@ -1404,7 +3431,7 @@ mod test {
         // 50 / 30 / 15 / 5
         client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
-        let amounts = client.calculate_split(&1000);
+        let amounts = client.calculate_split(&owner, &1000);
         assert_eq!(amounts.len(), 4);
         // spending: 50% of 1000 = 500
         assert_eq!(amounts.get(0).unwrap(), 500);
@@ -1417,6 +3444,7 @@ mod test {
     }
 
     /// 11. test_calculate_split_zero_or_negative_panics
+    ///
     /// total_amount of 0 or any negative value must return InvalidAmount.
     #[test]
     fn test_calculate_split_zero_or_negative_panics() {
@@ -1429,15 +3457,15 @@ mod test {
         client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
         // Zero
-        let result_zero = client.try_calculate_split(&0);
+        let result_zero = client.try_calculate_split(&owner, &0);
         assert_eq!(result_zero, Err(Ok(RemittanceSplitError::InvalidAmount)));
 
         // Negative
-        let result_neg = client.try_calculate_split(&-1);
+        let result_neg = client.try_calculate_split(&owner, &-1);
         assert_eq!(result_neg, Err(Ok(RemittanceSplitError::InvalidAmount)));
 
         // Large negative
-        let result_large_neg = client.try_calculate_split(&-9999);
+        let result_large_neg = client.try_calculate_split(&owner, &-9999);
         assert_eq!(
             result_large_neg,
             Err(Ok(RemittanceSplitError::InvalidAmount))
@@ -1445,6 +3473,7 @@ mod test {
     }
 
     /// 12. test_calculate_split_rounding
+    ///
     /// The sum of all split amounts must always equal total_amount exactly
     /// (insurance absorbs any integer division remainder).
     #[test]
@@ -1459,22 +3488,23 @@ mod test {
         client.initialize_split(&owner, &0, &33, &33, &33, &1);
 
         // total = 100: 33+33+33 = 99, insurance gets remainder = 1
-        let amounts = client.calculate_split(&100);
+        let amounts = client.calculate_split(&owner, &100);
         let sum: i128 = amounts.iter().sum();
         assert_eq!(sum, 100, "split amounts must sum to total_amount");
 
         // total = 7: each of 33% = 2 (floor), remainder = 7 - 2 - 2 - 2 = 1
-        let amounts2 = client.calculate_split(&7);
+        let amounts2 = client.calculate_split(&owner, &7);
         let sum2: i128 = amounts2.iter().sum();
         assert_eq!(sum2, 7, "split amounts must sum to total_amount");
 
         // total = 1000
-        let amounts3 = client.calculate_split(&1000);
+        let amounts3 = client.calculate_split(&owner, &1000);
         let sum3: i128 = amounts3.iter().sum();
         assert_eq!(sum3, 1000, "split amounts must sum to total_amount");
     }
 
     /// 13. test_event_emitted_on_initialize_and_update
+    ///
     /// Events must be published when initialize_split and update_split are called.
     #[test]
     fn test_event_emitted_on_initialize_and_update() {
@@ -3,7 +3,7 @@
 use super::*;
 use soroban_sdk::{
     testutils::{Address as AddressTrait, Events, Ledger, LedgerInfo},
-    Address, Env, IntoVal, Symbol, TryFromVal, Val, Vec,
+    Address, Env, IntoVal, Symbol, TryFromVal, Vec,
 };
 
 fn set_time(env: &Env, timestamp: u64) {
@@ -38,14 +38,14 @@ fn test_initialize_split() {
         &5,  // insurance
     );
 
-    assert_eq!(success, true);
+    assert!(success);
 
-    let config = client.get_config().unwrap();
+    let config = client.get_config(&owner).unwrap().active;
     assert_eq!(config.owner, owner);
-    assert_eq!(config.spending_percent, 50);
-    assert_eq!(config.savings_percent, 30);
-    assert_eq!(config.bills_percent, 15);
-    assert_eq!(config.insurance_percent, 5);
+    assert_eq!(config.buckets.get(0).unwrap().share_bps, 5000);
+    assert_eq!(config.buckets.get(1).unwrap().share_bps, 3000);
+    assert_eq!(config.buckets.get(2).unwrap().share_bps, 1500);
+    assert_eq!(config.buckets.get(3).unwrap().share_bps, 500);
 }
 
 #[test]
@@ -62,7 +62,10 @@ fn test_initialize_split_invalid_sum() {
         &50, &50, &10, // Sums to 110
         &0,
     );
-    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidPercentages)));
+    assert_eq!(
+        result,
+        Err(Ok(RemittanceSplitError::PercentagesDoNotSumTo100))
+    );
 }
 
 #[test]
@@ -92,13 +95,13 @@ fn test_update_split() {
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
     let success = client.update_split(&owner, &1, &40, &40, &10, &10);
-    assert_eq!(success, true);
+    assert!(success);
 
-    let config = client.get_config().unwrap();
-    assert_eq!(config.spending_percent, 40);
-    assert_eq!(config.savings_percent, 40);
-    assert_eq!(config.bills_percent, 10);
-    assert_eq!(config.insurance_percent, 10);
+    let config = client.get_config(&owner).unwrap().active;
+    assert_eq!(config.buckets.get(0).unwrap().share_bps, 4000);
+    assert_eq!(config.buckets.get(1).unwrap().share_bps, 4000);
+    assert_eq!(config.buckets.get(2).unwrap().share_bps, 1000);
+    assert_eq!(config.buckets.get(3).unwrap().share_bps, 1000);
 }
 
 #[test]
@@ -113,8 +116,91 @@ fn test_update_split_unauthorized() {
 
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
+    // Configs are per owner: `other` has none, and owner's split is untouched.
     let result = client.try_update_split(&other, &0, &40, &40, &10, &10);
-    assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotInitialized)));
+    assert_eq!(client.get_split(&owner).get(0).unwrap(), 5000);
+}
+
+#[test]
+fn test_configs_are_independent_per_owner() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize_split(&alice, &0, &50, &30, &15, &5);
+    client.initialize_split(&bob, &0, &10, &20, &30, &40);
+    client.update_split(&alice, &1, &25, &25, &25, &25);
+
+    let a = client.calculate_split(&alice, &1000);
+    assert_eq!(a.get(0).unwrap(), 250);
+    assert_eq!(a.get(3).unwrap(), 250);
+
+    let b = client.calculate_split(&bob, &1000);
+    assert_eq!(b.get(0).unwrap(), 100);
+    assert_eq!(b.get(1).unwrap(), 200);
+    assert_eq!(b.get(2).unwrap(), 300);
+    assert_eq!(b.get(3).unwrap(), 400);
+
+    // An owner without a config falls back to the default split.
+    assert!(client.get_config(&carol).is_none());
+    let c = client.calculate_split(&carol, &1000);
+    assert_eq!(c.get(0).unwrap(), 500);
+    assert_eq!(c.get(1).unwrap(), 300);
+    assert_eq!(c.get(2).unwrap(), 150);
+    assert_eq!(c.get(3).unwrap(), 50);
+}
+
+#[test]
+fn test_first_admin_assignments_require_contract_admin() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let pauser = Address::generate(&env);
+    let attacker = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    assert_eq!(
+        client.try_set_pause_admin(&attacker, &attacker),
+        Err(Ok(RemittanceSplitError::NotInitialized))
+    );
+
+    client.init(&admin);
+    assert_eq!(
+        client.try_init(&attacker),
+        Err(Ok(RemittanceSplitError::AlreadyInitialized))
+    );
+    assert_eq!(client.get_admin(), Some(admin.clone()));
+
+    for result in [
+        client.try_set_pause_admin(&attacker, &attacker),
+        client.try_set_upgrade_admin(&attacker, &attacker),
+        client.try_set_preset_admin(&attacker, &attacker),
+    ] {
+        assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+    }
+
+    client.set_pause_admin(&admin, &pauser);
+    client.set_upgrade_admin(&admin, &admin);
+    client.pause(&pauser);
+    assert!(client.is_paused());
+
+    // Once assigned, only the holder can hand the role on.
+    assert_eq!(
+        client.try_set_pause_admin(&admin, &admin),
+        Err(Ok(RemittanceSplitError::Unauthorized))
+    );
+    client.set_pause_admin(&pauser, &admin);
+    client.unpause(&admin);
+    client.set_version(&admin, &2);
+    assert_eq!(client.get_version(), 2);
 }
 
 #[test]
@@ -129,7 +215,7 @@ fn test_calculate_split() {
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
     // Test with 1000 units
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
 
     // spending: 50% of 1000 = 500
     // savings: 30% of 1000 = 300
@@ -160,7 +246,7 @@ fn test_calculate_split_rounding() {
     // insurance = total - spending - savings - bills
     // 100 - 33 - 33 - 33 = 1. Correct.
 
-    let amounts = client.calculate_split(&100);
+    let amounts = client.calculate_split(&owner, &100);
     assert_eq!(amounts.get(0).unwrap(), 33);
     assert_eq!(amounts.get(1).unwrap(), 33);
     assert_eq!(amounts.get(2).unwrap(), 33);
@@ -177,7 +263,7 @@ fn test_calculate_split_zero_amount() {
     env.mock_all_auths();
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
-    let result = client.try_calculate_split(&0);
+    let result = client.try_calculate_split(&owner, &0);
     assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidAmount)));
 }
 
@@ -198,7 +284,7 @@ fn test_calculate_complex_rounding() {
     // 23% = 230
     // 41% = 410
     // Sum = 1000. Perfect.
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 170);
     assert_eq!(amounts.get(1).unwrap(), 190);
     assert_eq!(amounts.get(2).unwrap(), 230);
@@ -209,7 +295,7 @@ fn test_calculate_complex_rounding() {
     // 19% of 3 = 0
     // 23% of 3 = 0
     // Remainder = 3 - 0 - 0 - 0 = 3. All goes to insurance.
-    let tiny_amounts = client.calculate_split(&3);
+    let tiny_amounts = client.calculate_split(&owner, &3);
     assert_eq!(tiny_amounts.get(0).unwrap(), 0);
     assert_eq!(tiny_amounts.get(3).unwrap(), 3);
 }
@@ -223,10 +309,13 @@ fn test_create_remittance_schedule() {
 
     env.mock_all_auths();
     set_time(&env, 1000);
+    let token = Address::generate(&env);
 
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.add_allowed_token(&owner, &1, &token);
 
-    let schedule_id = client.create_remittance_schedule(&owner, &10000, &3000, &86400);
+    let schedule_id =
+        client.create_remittance_schedule(&owner, &token, &accounts(&env), &10000, &3000, &86400);
     assert_eq!(schedule_id, 1);
 
     let schedule = client.get_remittance_schedule(&schedule_id);
@@ -247,10 +336,13 @@ fn test_modify_remittance_schedule() {
 
     env.mock_all_auths();
     set_time(&env, 1000);
+    let token = Address::generate(&env);
 
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.add_allowed_token(&owner, &1, &token);
 
-    let schedule_id = client.create_remittance_schedule(&owner, &10000, &3000, &86400);
+    let schedule_id =
+        client.create_remittance_schedule(&owner, &token, &accounts(&env), &10000, &3000, &86400);
     client.modify_remittance_schedule(&owner, &schedule_id, &15000, &4000, &172800);
 
     let schedule = client.get_remittance_schedule(&schedule_id).unwrap();
@@ -268,10 +360,13 @@ fn test_cancel_remittance_schedule() {
 
     env.mock_all_auths();
     set_time(&env, 1000);
+    let token = Address::generate(&env);
 
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.add_allowed_token(&owner, &1, &token);
 
-    let schedule_id = client.create_remittance_schedule(&owner, &10000, &3000, &86400);
+    let schedule_id =
+        client.create_remittance_schedule(&owner, &token, &accounts(&env), &10000, &3000, &86400);
     client.cancel_remittance_schedule(&owner, &schedule_id);
 
     let schedule = client.get_remittance_schedule(&schedule_id).unwrap();
@@ -287,11 +382,13 @@ fn test_get_remittance_schedules() {
 
     env.mock_all_auths();
     set_time(&env, 1000);
+    let token = Address::generate(&env);
 
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.add_allowed_token(&owner, &1, &token);
 
-    client.create_remittance_schedule(&owner, &10000, &3000, &86400);
-    client.create_remittance_schedule(&owner, &5000, &4000, &172800);
+    client.create_remittance_schedule(&owner, &token, &accounts(&env), &10000, &3000, &86400);
+    client.create_remittance_schedule(&owner, &token, &accounts(&env), &5000, &4000, &172800);
 
     let schedules = client.get_remittance_schedules(&owner);
     assert_eq!(schedules.len(), 2);
@@ -306,10 +403,19 @@ fn test_remittance_schedule_validation() {
 
     env.mock_all_auths();
     set_time(&env, 5000);
+    let token = Address::generate(&env);
 
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
-
-    let result = client.try_create_remittance_schedule(&owner, &10000, &3000, &86400);
+    client.add_allowed_token(&owner, &1, &token);
+
+    let result = client.try_create_remittance_schedule(
+        &owner,
+        &token,
+        &accounts(&env),
+        &10000,
+        &3000,
+        &86400,
+    );
     assert!(result.is_err());
 }
 
@@ -322,12 +428,282 @@ fn test_remittance_schedule_zero_amount() {
 
     env.mock_all_auths();
     set_time(&env, 1000);
+    let token = Address::generate(&env);
 
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.add_allowed_token(&owner, &1, &token);
 
-    let result = client.try_create_remittance_schedule(&owner, &0, &3000, &86400);
+    let result =
+        client.try_create_remittance_schedule(&owner, &token, &accounts(&env), &0, &3000, &86400);
     assert!(result.is_err());
 }
+
+/// Registers a stellar asset, mints `balance` to `owner` and approves the split
+/// contract to pull `allowance` of it.
+fn funded_token(
+    env: &Env,
+    contract_id: &Address,
+    owner: &Address,
+    balance: i128,
+    allowance: i128,
+) -> Address {
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    soroban_sdk::token::StellarAssetClient::new(env, &token).mint(owner, &balance);
+    TokenClient::new(env, &token).approve(owner, contract_id, &allowance, &10_000);
+    token
+}
+
+#[test]
+fn test_execute_due_remittance_schedules() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    set_time(&env, 1000);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token = funded_token(&env, &contract_id, &owner, 10_000, 10_000);
+    client.add_allowed_token(&owner, &1, &token);
+    let group = accounts(&env);
+    let recurring = client.create_remittance_schedule(&owner, &token, &group, &1000, &2000, &1000);
+    let one_shot = client.create_remittance_schedule(&owner, &token, &group, &1000, &5000, &0);
+
+    // Nothing is due yet.
+    assert_eq!(
+        client
+            .execute_due_remittance_schedules(&0, &0)
+            .executed
+            .len(),
+        0
+    );
+
+    set_time(&env, 2000);
+    let executed = client.execute_due_remittance_schedules(&0, &0).executed;
+    assert_eq!(executed, Vec::from_array(&env, [recurring]));
+
+    let balance = |addr: &Address| client.get_usdc_balance(&token, addr);
+    assert_eq!(balance(&group.spending), 500);
+    assert_eq!(balance(&group.savings), 300);
+    assert_eq!(balance(&group.bills), 150);
+    assert_eq!(balance(&group.insurance), 50);
+    assert_eq!(balance(&owner), 9000);
+
+    let schedule = client.get_remittance_schedule(&recurring).unwrap();
+    assert_eq!(schedule.next_due, 3000);
+    assert_eq!(schedule.last_executed, Some(2000));
+    assert_eq!(schedule.missed_count, 0);
+
+    // Running again in the same slot is a no-op.
+    assert_eq!(
+        client
+            .execute_due_remittance_schedules(&0, &0)
+            .executed
+            .len(),
+        0
+    );
+
+    set_time(&env, 5000);
+    let executed = client.execute_due_remittance_schedules(&0, &0).executed;
+    assert_eq!(executed, Vec::from_array(&env, [recurring, one_shot]));
+    assert_eq!(balance(&owner), 7000);
+
+    // One payment covered the 3000, 4000 and 5000 slots; two count as missed.
+    let schedule = client.get_remittance_schedule(&recurring).unwrap();
+    assert_eq!(schedule.next_due, 6000);
+    assert_eq!(schedule.missed_count, 2);
+    assert!(!client.get_remittance_schedule(&one_shot).unwrap().active);
+    assert_eq!(client.get_distributed_total(&owner, &token), 3000);
+}
+
+#[test]
+fn test_execute_due_remittance_schedules_without_allowance_is_missed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    set_time(&env, 1000);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token = funded_token(&env, &contract_id, &owner, 10_000, 500);
+    client.add_allowed_token(&owner, &1, &token);
+    let schedule_id =
+        client.create_remittance_schedule(&owner, &token, &accounts(&env), &1000, &2000, &1000);
+
+    set_time(&env, 2000);
+    let run = client.execute_due_remittance_schedules(&0, &0);
+    assert_eq!(run.executed.len(), 0);
+    let failure = ScheduleFailure {
+        schedule_id,
+        reason: ScheduleFailureReason::InsufficientFunds,
+        timestamp: 2000,
+    };
+    assert_eq!(run.failed, Vec::from_array(&env, [failure.clone()]));
+    assert_eq!(client.get_schedule_failure(&schedule_id), Some(failure));
+    assert_eq!(client.get_usdc_balance(&token, &owner), 10_000);
+
+    let schedule = client.get_remittance_schedule(&schedule_id).unwrap();
+    assert_eq!(schedule.missed_count, 1);
+    assert_eq!(schedule.next_due, 3000);
+    assert_eq!(schedule.last_executed, None);
+    assert!(schedule.active);
+}
+
+#[test]
+fn test_execute_due_remittance_schedules_checks_token_allow_list() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    set_time(&env, 1000);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token = funded_token(&env, &contract_id, &owner, 10_000, 10_000);
+    assert_eq!(
+        client.try_create_remittance_schedule(&owner, &token, &accounts(&env), &1000, &2000, &0),
+        Err(Ok(RemittanceSplitError::TokenNotAllowed))
+    );
+
+    client.add_allowed_token(&owner, &1, &token);
+    let schedule_id =
+        client.create_remittance_schedule(&owner, &token, &accounts(&env), &1000, &2000, &0);
+    client.remove_allowed_token(&owner, &2, &token);
+
+    set_time(&env, 2000);
+    let run = client.execute_due_remittance_schedules(&0, &0);
+    assert_eq!(run.executed.len(), 0);
+    assert_eq!(run.failed.len(), 1);
+    assert_eq!(
+        run.failed.get(0).unwrap().reason,
+        ScheduleFailureReason::TokenNotAllowed
+    );
+    assert_eq!(client.get_usdc_balance(&token, &owner), 10_000);
+
+    let schedule = client.get_remittance_schedule(&schedule_id).unwrap();
+    assert_eq!(schedule.missed_count, 1);
+    assert!(!schedule.active);
+}
+
+#[test]
+fn test_execute_due_remittance_schedules_is_bounded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    set_time(&env, 1000);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token = funded_token(&env, &contract_id, &owner, 10_000, 10_000);
+    client.add_allowed_token(&owner, &1, &token);
+    let group = accounts(&env);
+    for _ in 0..5 {
+        client.create_remittance_schedule(&owner, &token, &group, &100, &2000, &0);
+    }
+
+    set_time(&env, 2000);
+    let run = client.execute_due_remittance_schedules(&0, &2);
+    assert_eq!(run.executed, Vec::from_array(&env, [1, 2]));
+    assert_eq!(run.next_cursor, 2);
+
+    let run = client.execute_due_remittance_schedules(&run.next_cursor, &2);
+    assert_eq!(run.executed, Vec::from_array(&env, [3, 4]));
+    assert_eq!(run.next_cursor, 4);
+
+    let run = client.execute_due_remittance_schedules(&run.next_cursor, &2);
+    assert_eq!(run.executed, Vec::from_array(&env, [5]));
+    assert_eq!(run.next_cursor, 0);
+    assert_eq!(client.get_distributed_total(&owner, &token), 500);
+}
+
+#[test]
+fn test_migrate_storage_moves_legacy_schedules() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+    set_time(&env, 1000);
+
+    let legacy_schedule = |id: u32, owner: &Address| LegacyRemittanceSchedule {
+        id,
+        owner: owner.clone(),
+        amount: 100 * id as i128,
+        next_due: 2000,
+        interval: 0,
+        recurring: false,
+        active: true,
+        created_at: 1000,
+        last_executed: None,
+        missed_count: 0,
+    };
+    let mut schedules = Map::new(&env);
+    schedules.set(1u32, legacy_schedule(1, &owner));
+    schedules.set(2u32, legacy_schedule(2, &other));
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        storage.set(&symbol_short!("REM_SCH"), &schedules);
+        storage.set(&symbol_short!("NEXT_RSCH"), &2u32);
+    });
+
+    client.init(&admin);
+    client.set_upgrade_admin(&admin, &admin);
+    assert_eq!(
+        client.try_migrate_storage(&owner),
+        Err(Ok(RemittanceSplitError::Unauthorized))
+    );
+    assert_eq!(client.migrate_storage(&admin), 2);
+    assert_eq!(client.migrate_storage(&admin), 0);
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&symbol_short!("REM_SCH")));
+    });
+
+    // Parked until the owner supplies a token and destinations.
+    assert_eq!(client.get_remittance_schedules(&owner).len(), 0);
+    assert!(client.get_remittance_schedule(&1).is_none());
+    let parked = client.get_legacy_schedules(&owner);
+    assert_eq!(parked.len(), 1);
+    assert_eq!(parked.get(0).unwrap().amount, 100);
+    assert_eq!(client.get_legacy_schedules(&other).len(), 1);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token = funded_token(&env, &contract_id, &owner, 10_000, 10_000);
+    assert_eq!(
+        client.try_restore_legacy_schedule(&owner, &1, &token, &accounts(&env)),
+        Err(Ok(RemittanceSplitError::TokenNotAllowed))
+    );
+    client.add_allowed_token(&owner, &1, &token);
+    assert_eq!(
+        client.try_restore_legacy_schedule(&owner, &2, &token, &accounts(&env)),
+        Err(Ok(RemittanceSplitError::Unauthorized))
+    );
+    assert!(client.restore_legacy_schedule(&owner, &1, &token, &accounts(&env)));
+    assert_eq!(client.get_legacy_schedules(&owner).len(), 0);
+    let restored = client.get_remittance_schedule(&1).unwrap();
+    assert_eq!(restored.amount, 100);
+    assert_eq!(restored.next_due, 2000);
+    assert_eq!(restored.token, token);
+    assert_eq!(
+        client.try_restore_legacy_schedule(&owner, &1, &token, &accounts(&env)),
+        Err(Ok(RemittanceSplitError::ScheduleNotFound))
+    );
+
+    // New schedules continue after the migrated ids.
+    let id = client.create_remittance_schedule(&owner, &token, &accounts(&env), &50, &3000, &0);
+    assert_eq!(id, 3);
+    assert_eq!(client.get_remittance_schedules(&owner).len(), 2);
+
+    set_time(&env, 2000);
+    let run = client.execute_due_remittance_schedules(&0, &10);
+    assert_eq!(run.executed, Vec::from_array(&env, [1]));
+    assert_eq!(client.get_distributed_total(&owner, &token), 100);
+}
 #[test]
 fn test_initialize_split_events() {
     let env = Env::default();
@@ -397,7 +773,7 @@ fn test_calculate_split_events() {
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
     let total_amount = 1000i128;
-    client.calculate_split(&total_amount);
+    client.calculate_split(&owner, &total_amount);
 
     let events = env.events().all();
     // calculate_split publishes two events:
@@ -463,14 +839,14 @@ fn test_split_boundary_100_0_0_0() {
     assert!(ok);
 
     // get_split must return the exact percentages
-    let split = client.get_split();
-    assert_eq!(split.get(0).unwrap(), 100);
+    let split = client.get_split(&owner);
+    assert_eq!(split.get(0).unwrap(), 10000);
     assert_eq!(split.get(1).unwrap(), 0);
     assert_eq!(split.get(2).unwrap(), 0);
     assert_eq!(split.get(3).unwrap(), 0);
 
     // calculate_split must allocate the entire amount to spending
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 1000);
     assert_eq!(amounts.get(1).unwrap(), 0);
     assert_eq!(amounts.get(2).unwrap(), 0);
@@ -490,13 +866,13 @@ fn test_split_boundary_0_100_0_0() {
     let ok = client.initialize_split(&owner, &0, &0, &100, &0, &0);
     assert!(ok);
 
-    let split = client.get_split();
+    let split = client.get_split(&owner);
     assert_eq!(split.get(0).unwrap(), 0);
-    assert_eq!(split.get(1).unwrap(), 100);
+    assert_eq!(split.get(1).unwrap(), 10000);
     assert_eq!(split.get(2).unwrap(), 0);
     assert_eq!(split.get(3).unwrap(), 0);
 
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 0);
     assert_eq!(amounts.get(1).unwrap(), 1000);
     assert_eq!(amounts.get(2).unwrap(), 0);
@@ -516,13 +892,13 @@ fn test_split_boundary_0_0_100_0() {
     let ok = client.initialize_split(&owner, &0, &0, &0, &100, &0);
     assert!(ok);
 
-    let split = client.get_split();
+    let split = client.get_split(&owner);
     assert_eq!(split.get(0).unwrap(), 0);
     assert_eq!(split.get(1).unwrap(), 0);
-    assert_eq!(split.get(2).unwrap(), 100);
+    assert_eq!(split.get(2).unwrap(), 10000);
     assert_eq!(split.get(3).unwrap(), 0);
 
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 0);
     assert_eq!(amounts.get(1).unwrap(), 0);
     assert_eq!(amounts.get(2).unwrap(), 1000);
@@ -542,14 +918,14 @@ fn test_split_boundary_0_0_0_100() {
     let ok = client.initialize_split(&owner, &0, &0, &0, &0, &100);
     assert!(ok);
 
-    let split = client.get_split();
+    let split = client.get_split(&owner);
     assert_eq!(split.get(0).unwrap(), 0);
     assert_eq!(split.get(1).unwrap(), 0);
     assert_eq!(split.get(2).unwrap(), 0);
-    assert_eq!(split.get(3).unwrap(), 100);
+    assert_eq!(split.get(3).unwrap(), 10000);
 
     // Insurance gets the remainder: 1000 - 0 - 0 - 0 = 1000
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 0);
     assert_eq!(amounts.get(1).unwrap(), 0);
     assert_eq!(amounts.get(2).unwrap(), 0);
@@ -569,14 +945,14 @@ fn test_split_boundary_25_25_25_25() {
     let ok = client.initialize_split(&owner, &0, &25, &25, &25, &25);
     assert!(ok);
 
-    let split = client.get_split();
-    assert_eq!(split.get(0).unwrap(), 25);
-    assert_eq!(split.get(1).unwrap(), 25);
-    assert_eq!(split.get(2).unwrap(), 25);
-    assert_eq!(split.get(3).unwrap(), 25);
+    let split = client.get_split(&owner);
+    assert_eq!(split.get(0).unwrap(), 2500);
+    assert_eq!(split.get(1).unwrap(), 2500);
+    assert_eq!(split.get(2).unwrap(), 2500);
+    assert_eq!(split.get(3).unwrap(), 2500);
 
     // 25 % of 1000 = 250 for each category
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 250);
     assert_eq!(amounts.get(1).unwrap(), 250);
     assert_eq!(amounts.get(2).unwrap(), 250);
@@ -601,13 +977,13 @@ fn test_update_split_boundary_percentages() {
     let ok = client.update_split(&owner, &1, &100, &0, &0, &0);
     assert!(ok);
 
-    let split = client.get_split();
-    assert_eq!(split.get(0).unwrap(), 100);
+    let split = client.get_split(&owner);
+    assert_eq!(split.get(0).unwrap(), 10000);
     assert_eq!(split.get(1).unwrap(), 0);
     assert_eq!(split.get(2).unwrap(), 0);
     assert_eq!(split.get(3).unwrap(), 0);
 
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 1000);
     assert_eq!(amounts.get(1).unwrap(), 0);
     assert_eq!(amounts.get(2).unwrap(), 0);
@@ -617,13 +993,13 @@ fn test_update_split_boundary_percentages() {
     let ok = client.update_split(&owner, &1, &25, &25, &25, &25);
     assert!(ok);
 
-    let split = client.get_split();
-    assert_eq!(split.get(0).unwrap(), 25);
-    assert_eq!(split.get(1).unwrap(), 25);
-    assert_eq!(split.get(2).unwrap(), 25);
-    assert_eq!(split.get(3).unwrap(), 25);
+    let split = client.get_split(&owner);
+    assert_eq!(split.get(0).unwrap(), 2500);
+    assert_eq!(split.get(1).unwrap(), 2500);
+    assert_eq!(split.get(2).unwrap(), 2500);
+    assert_eq!(split.get(3).unwrap(), 2500);
 
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 250);
     assert_eq!(amounts.get(1).unwrap(), 250);
     assert_eq!(amounts.get(2).unwrap(), 250);
@@ -641,12 +1017,1284 @@ fn test_update_split_not_initialized() {
     let result = client.try_update_split(&caller, &0, &25, &25, &25, &25);
     assert_eq!(result, Err(Ok(RemittanceSplitError::NotInitialized)));
 
-    let config = client.get_config();
+    let config = client.get_config(&caller);
     assert!(config.is_none());
 
-    let split = client.get_split();
-    assert_eq!(split.get(0).unwrap(), 50);
-    assert_eq!(split.get(1).unwrap(), 30);
-    assert_eq!(split.get(2).unwrap(), 15);
-    assert_eq!(split.get(3).unwrap(), 5);
+    let split = client.get_split(&caller);
+    assert_eq!(split.get(0).unwrap(), 5000);
+    assert_eq!(split.get(1).unwrap(), 3000);
+    assert_eq!(split.get(2).unwrap(), 1500);
+    assert_eq!(split.get(3).unwrap(), 500);
+}
+
+fn bucket(env: &Env, name: &str, share_bps: u32, destination: Option<Address>) -> SplitBucket {
+    SplitBucket {
+        name: Symbol::new(env, name),
+        share_bps,
+        destination,
+        fixed_amount: None,
+        min_amount: None,
+        max_amount: None,
+    }
+}
+
+fn accounts(env: &Env) -> AccountGroup {
+    AccountGroup {
+        spending: Address::generate(env),
+        savings: Address::generate(env),
+        bills: Address::generate(env),
+        insurance: Address::generate(env),
+    }
+}
+
+#[test]
+fn test_initialize_split_buckets_n_way() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let grandma = Address::generate(&env);
+
+    let buckets = Vec::from_array(
+        &env,
+        [
+            bucket(&env, "SPENDING", 4000, None),
+            bucket(&env, "SAVINGS", 2000, None),
+            bucket(&env, "school", 2000, Some(Address::generate(&env))),
+            bucket(&env, "tithe", 1000, Some(Address::generate(&env))),
+            bucket(&env, "grandma", 1000, Some(grandma.clone())),
+        ],
+    );
+    let to_grandma = RemainderPolicy::NamedBucket(Symbol::new(&env, "grandma"));
+    assert!(client.initialize_split_buckets(&owner, &0, &buckets, &to_grandma));
+    assert_eq!(client.get_nonce(&owner), 1);
+
+    let config = client.get_config(&owner).unwrap().active;
+    assert_eq!(config.buckets, buckets);
+    assert_eq!(config.remainder_policy, to_grandma);
+    assert_eq!(
+        client.get_split(&owner),
+        Vec::from_array(&env, [4000u32, 2000, 2000, 1000, 1000])
+    );
+
+    let amounts = client.calculate_split(&owner, &1001);
+    assert_eq!(
+        amounts,
+        Vec::from_array(&env, [400i128, 200, 200, 100, 101])
+    );
+
+    let allocations = client.get_split_allocations(&owner, &1000);
+    assert_eq!(allocations.len(), 5);
+    let last = allocations.get(4).unwrap();
+    assert_eq!(last.category, Symbol::new(&env, "grandma"));
+    assert_eq!(last.amount, 100);
+}
+
+#[test]
+fn test_split_buckets_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    let empty: Vec<SplitBucket> = Vec::new(&env);
+    assert_eq!(
+        client.try_initialize_split_buckets(&owner, &0, &empty, &RemainderPolicy::LargestBucket),
+        Err(Ok(RemittanceSplitError::InvalidBuckets))
+    );
+
+    let duplicate = Vec::from_array(
+        &env,
+        [
+            bucket(&env, "school", 5000, None),
+            bucket(&env, "school", 5000, None),
+        ],
+    );
+    assert_eq!(
+        client.try_initialize_split_buckets(
+            &owner,
+            &0,
+            &duplicate,
+            &RemainderPolicy::LargestBucket
+        ),
+        Err(Ok(RemittanceSplitError::InvalidBuckets))
+    );
+
+    let short = Vec::from_array(
+        &env,
+        [
+            bucket(&env, "school", 5000, None),
+            bucket(&env, "tithe", 4999, None),
+        ],
+    );
+    assert_eq!(
+        client.try_initialize_split_buckets(&owner, &0, &short, &RemainderPolicy::LargestBucket),
+        Err(Ok(RemittanceSplitError::PercentagesDoNotSumTo100))
+    );
+
+    let mut too_many = Vec::new(&env);
+    for _ in 0..11 {
+        too_many.push_back(bucket(&env, "x", 0, None));
+    }
+    assert_eq!(
+        client.try_initialize_split_buckets(&owner, &0, &too_many, &RemainderPolicy::LargestBucket),
+        Err(Ok(RemittanceSplitError::InvalidBuckets))
+    );
+}
+
+#[test]
+fn test_update_split_buckets_replaces_legacy_buckets() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let buckets = Vec::from_array(
+        &env,
+        [
+            bucket(&env, "SAVINGS", 7000, None),
+            bucket(&env, "grandma", 3000, Some(Address::generate(&env))),
+        ],
+    );
+    assert!(client.update_split_buckets(&owner, &1, &buckets, &RemainderPolicy::LargestBucket));
+    assert_eq!(client.get_config(&owner).unwrap().active.buckets, buckets);
+    assert_eq!(
+        client.calculate_split(&owner, &1000),
+        Vec::from_array(&env, [700i128, 300])
+    );
+
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_update_split_buckets(&other, &0, &buckets, &RemainderPolicy::LargestBucket),
+        Err(Ok(RemittanceSplitError::NotInitialized))
+    );
+}
+
+#[test]
+fn test_distribute_usdc_pays_custom_and_legacy_buckets() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let grandma = Address::generate(&env);
+
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    soroban_sdk::token::StellarAssetClient::new(&env, &token.address()).mint(&owner, &1_000);
+
+    let buckets = Vec::from_array(
+        &env,
+        [
+            bucket(&env, "SPENDING", 6000, None),
+            bucket(&env, "grandma", 4000, Some(grandma.clone())),
+        ],
+    );
+    client.initialize_split_buckets(&owner, &0, &buckets, &RemainderPolicy::LargestBucket);
+
+    let group = accounts(&env);
+    assert!(client.distribute_usdc(&token.address(), &owner, &1, &group, &1_000));
+
+    let balance = |addr: &Address| client.get_usdc_balance(&token.address(), addr);
+    assert_eq!(balance(&group.spending), 600);
+    assert_eq!(balance(&grandma), 400);
+    assert_eq!(balance(&group.savings), 0);
+    assert_eq!(balance(&owner), 0);
+}
+
+#[test]
+fn test_allowed_tokens_are_managed_by_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let usdc = Address::generate(&env);
+    let eurc = Address::generate(&env);
+
+    // Only owners with a configuration can keep a list.
+    assert_eq!(
+        client.try_add_allowed_token(&owner, &0, &usdc),
+        Err(Ok(RemittanceSplitError::NotInitialized))
+    );
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.add_allowed_token(&owner, &1, &usdc);
+    client.add_allowed_token(&owner, &2, &eurc);
+    client.add_allowed_token(&owner, &3, &usdc);
+    assert_eq!(
+        client.get_allowed_tokens(&owner),
+        Vec::from_array(&env, [usdc.clone(), eurc.clone()])
+    );
+
+    client.remove_allowed_token(&owner, &4, &usdc);
+    assert_eq!(
+        client.get_allowed_tokens(&owner),
+        Vec::from_array(&env, [eurc])
+    );
+    assert_eq!(
+        client.try_remove_allowed_token(&owner, &5, &usdc),
+        Err(Ok(RemittanceSplitError::TokenNotAllowed))
+    );
+    assert_eq!(client.get_allowed_tokens(&Address::generate(&env)).len(), 0);
+}
+
+#[test]
+fn test_distribute_only_allowed_tokens_and_tracks_totals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let usdc = funded_token(&env, &contract_id, &owner, 10_000, 0);
+    let eurc = funded_token(&env, &contract_id, &owner, 10_000, 0);
+    let group = accounts(&env);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.add_allowed_token(&owner, &1, &usdc);
+
+    assert_eq!(
+        client.try_distribute(&eurc, &owner, &2, &group, &1_000),
+        Err(Ok(RemittanceSplitError::TokenNotAllowed))
+    );
+    assert_eq!(client.get_usdc_balance(&eurc, &owner), 10_000);
+
+    assert!(client.distribute(&usdc, &owner, &2, &group, &1_000));
+    assert_eq!(client.get_usdc_balance(&usdc, &group.spending), 500);
+
+    let event = env.events().all().last().unwrap();
+    let topic: SplitEvent = SplitEvent::try_from_val(&env, &event.1.get(1).unwrap()).unwrap();
+    assert_eq!(topic, SplitEvent::Distributed);
+    let data = DistributedEvent::try_from_val(&env, &event.2).unwrap();
+    assert_eq!(data.token, usdc);
+    assert_eq!(data.total_amount, 1_000);
+    assert_eq!(data.allocations.get(3).unwrap().amount, 50);
+
+    // distribute_usdc stays unrestricted but still counts towards the totals.
+    client.distribute_usdc(&eurc, &owner, &3, &group, &400);
+    client.distribute(&usdc, &owner, &4, &group, &200);
+    assert_eq!(client.get_distributed_total(&owner, &usdc), 1_200);
+    assert_eq!(client.get_distributed_total(&owner, &eurc), 400);
+    assert_eq!(
+        client.get_distributed_total(&Address::generate(&env), &usdc),
+        0
+    );
+}
+
+#[test]
+fn test_preview_distribution() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let token = funded_token(&env, &contract_id, &owner, 1_000, 2_000);
+    let group = accounts(&env);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.add_allowed_token(&owner, &1, &token);
+
+    let preview = client.preview_distribution(&owner, &token, &group, &1_000);
+    assert_eq!(preview.total_amount, 1_000);
+    assert_eq!(preview.buckets.len(), 4);
+    let savings = preview.buckets.get(1).unwrap();
+    assert_eq!(savings.name, symbol_short!("SAVINGS"));
+    assert_eq!(savings.amount, 300);
+    assert_eq!(savings.destination, Some(group.savings.clone()));
+    assert_eq!(preview.balance, 1_000);
+    assert_eq!(preview.allowance, 2_000);
+    assert!(preview.balance_sufficient);
+    assert!(preview.allowance_sufficient);
+    assert_eq!(preview.issues.len(), 0);
+
+    // Previewing is read-only.
+    assert_eq!(client.get_nonce(&owner), 2);
+    assert_eq!(client.get_usdc_balance(&token, &owner), 1_000);
+
+    let preview = client.preview_distribution(&owner, &token, &group, &1_500);
+    assert!(!preview.balance_sufficient);
+    assert!(preview.allowance_sufficient);
+}
+
+#[test]
+fn test_preview_distribution_flags_issues() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let token = funded_token(&env, &contract_id, &owner, 1_000, 0);
+
+    let buckets = Vec::from_array(
+        &env,
+        [
+            bucket(&env, "SPENDING", 10000, Some(owner.clone())),
+            bucket(&env, "SAVINGS", 0, None),
+            bucket(&env, "school", 0, None),
+        ],
+    );
+    client.initialize_split_buckets(&owner, &0, &buckets, &RemainderPolicy::LargestBucket);
+
+    let preview = client.preview_distribution(&owner, &token, &accounts(&env), &1_000);
+    assert!(!preview.allowance_sufficient);
+    assert_eq!(preview.buckets.get(2).unwrap().destination, None);
+    assert_eq!(
+        preview.issues,
+        Vec::from_array(
+            &env,
+            [
+                PreviewIssue::TokenNotAllowed,
+                PreviewIssue::DestinationIsSource(symbol_short!("SPENDING")),
+                PreviewIssue::ZeroAmountBucket(symbol_short!("SAVINGS")),
+                PreviewIssue::MissingDestination(symbol_short!("school")),
+                PreviewIssue::ZeroAmountBucket(symbol_short!("school")),
+            ]
+        )
+    );
+
+    assert_eq!(
+        client.try_preview_distribution(&owner, &token, &accounts(&env), &0),
+        Err(Ok(RemittanceSplitError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_distribution_history_is_recorded_and_paginated() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let token = funded_token(&env, &contract_id, &owner, 10_000, 0);
+    let group = accounts(&env);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    for (nonce, time) in [(1u64, 100u64), (2, 200), (3, 300)] {
+        set_time(&env, time);
+        client.distribute_usdc(&token, &owner, &nonce, &group, &(time as i128 * 10));
+    }
+
+    let page = client.get_distributions(&owner, &0, &2);
+    assert_eq!(page.count, 2);
+    assert_eq!(page.next_cursor, 2);
+    let first = page.items.get(0).unwrap();
+    assert_eq!(first.id, 1);
+    assert_eq!(first.timestamp, 100);
+    assert_eq!(first.token, token);
+    assert_eq!(first.total_amount, 1_000);
+    assert_eq!(
+        first.allocations.get(0).unwrap(),
+        Allocation {
+            category: symbol_short!("SPENDING"),
+            amount: 500,
+        }
+    );
+
+    let page = client.get_distributions(&owner, &page.next_cursor, &2);
+    assert_eq!(page.count, 1);
+    assert_eq!(page.next_cursor, 0);
+    assert_eq!(page.items.get(0).unwrap().total_amount, 3_000);
+
+    // Failed distributions leave no record.
+    let result = client.try_distribute_usdc(&token, &owner, &4, &group, &0);
+    assert!(result.is_err());
+    assert_eq!(client.get_distributions(&owner, &0, &0).count, 3);
+    assert_eq!(
+        client
+            .get_distributions(&Address::generate(&env), &0, &0)
+            .count,
+        0
+    );
+}
+
+#[test]
+fn test_distribution_totals_by_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let usdc = funded_token(&env, &contract_id, &owner, 20_000, 0);
+    let eurc = funded_token(&env, &contract_id, &owner, 10_000, 0);
+    let group = accounts(&env);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    set_time(&env, 100);
+    client.distribute_usdc(&usdc, &owner, &1, &group, &1_000);
+    set_time(&env, 200);
+    client.distribute_usdc(&eurc, &owner, &2, &group, &2_000);
+    set_time(&env, 300);
+    client.distribute_usdc(&usdc, &owner, &3, &group, &4_000);
+    set_time(&env, 400);
+    client.distribute_usdc(&usdc, &owner, &4, &group, &8_000);
+
+    let page = client.get_distribution_totals(&owner, &150, &300, &0, &0);
+    assert_eq!(page.next_cursor, 0);
+    let totals = page.totals;
+    assert_eq!(totals.len(), 2);
+    let eurc_totals = totals.get(0).unwrap();
+    assert_eq!(eurc_totals.token, eurc);
+    assert_eq!(eurc_totals.count, 1);
+    assert_eq!(eurc_totals.total_amount, 2_000);
+    let usdc_totals = totals.get(1).unwrap();
+    assert_eq!(usdc_totals.token, usdc);
+    assert_eq!(usdc_totals.count, 1);
+    assert_eq!(usdc_totals.total_amount, 4_000);
+
+    let totals = client
+        .get_distribution_totals(&owner, &0, &u64::MAX, &0, &0)
+        .totals;
+    let usdc_totals = totals.get(0).unwrap();
+    assert_eq!(usdc_totals.count, 3);
+    assert_eq!(usdc_totals.total_amount, 13_000);
+    assert_eq!(usdc_totals.allocations.len(), 4);
+    assert_eq!(usdc_totals.allocations.get(1).unwrap().amount, 3_900);
+
+    assert_eq!(
+        client
+            .get_distribution_totals(&owner, &500, &600, &0, &0)
+            .totals
+            .len(),
+        0
+    );
+
+    // Paging looks at `limit` distributions at a time.
+    let page = client.get_distribution_totals(&owner, &0, &u64::MAX, &0, &3);
+    assert_eq!(page.next_cursor, 3);
+    assert_eq!(page.totals.get(0).unwrap().total_amount, 5_000);
+    let page = client.get_distribution_totals(&owner, &0, &u64::MAX, &3, &3);
+    assert_eq!(page.next_cursor, 0);
+    assert_eq!(page.totals.len(), 1);
+    assert_eq!(page.totals.get(0).unwrap().total_amount, 8_000);
+}
+
+#[test]
+fn test_set_and_revoke_delegates() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    let hot_wallet = Address::generate(&env);
+    let token = Address::generate(&env);
+    let group = accounts(&env);
+    set_time(&env, 1000);
+
+    assert_eq!(
+        client.try_set_delegate(&owner, &0, &keeper, &token, &group, &0, &86400),
+        Err(Ok(RemittanceSplitError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_set_delegate(&owner, &0, &keeper, &token, &group, &500, &0),
+        Err(Ok(RemittanceSplitError::InvalidDelegate))
+    );
+    assert_eq!(
+        client.try_set_delegate(&owner, &0, &owner, &token, &group, &500, &86400),
+        Err(Ok(RemittanceSplitError::InvalidDelegate))
+    );
+
+    client.set_delegate(&owner, &0, &keeper, &token, &group, &500, &86400);
+    client.set_delegate(&owner, &1, &hot_wallet, &token, &group, &1_000, &3600);
+    client.set_delegate(&owner, &2, &keeper, &token, &group, &700, &86400);
+
+    let delegates = client.get_delegates(&owner);
+    assert_eq!(delegates.len(), 2);
+    assert_eq!(
+        delegates.get(0).unwrap(),
+        Delegate {
+            delegate: keeper.clone(),
+            token: token.clone(),
+            accounts: group.clone(),
+            cap: 700,
+            period: 86400,
+            period_start: 1000,
+            spent: 0,
+        }
+    );
+
+    client.revoke_delegate(&owner, &3, &keeper);
+    let delegates = client.get_delegates(&owner);
+    assert_eq!(delegates.len(), 1);
+    assert_eq!(delegates.get(0).unwrap().delegate, hot_wallet);
+    assert_eq!(
+        client.try_revoke_delegate(&owner, &4, &keeper),
+        Err(Ok(RemittanceSplitError::DelegateNotFound))
+    );
+}
+
+#[test]
+fn test_distribute_as_delegate_respects_period_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let token = funded_token(&env, &contract_id, &owner, 10_000, 10_000);
+    let group = accounts(&env);
+    set_time(&env, 1000);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.add_allowed_token(&owner, &1, &token);
+    client.set_delegate(&owner, &2, &keeper, &token, &group, &1_000, &3600);
+
+    assert_eq!(
+        client.try_distribute_as_delegate(&stranger, &0, &owner, &token, &100),
+        Err(Ok(RemittanceSplitError::Unauthorized))
+    );
+
+    assert!(client.distribute_as_delegate(&keeper, &0, &owner, &token, &600));
+    assert_eq!(client.get_usdc_balance(&token, &group.spending), 300);
+    assert_eq!(client.get_usdc_balance(&token, &owner), 9_400);
+    assert_eq!(client.get_nonce(&keeper), 1);
+    assert_eq!(client.get_nonce(&owner), 3);
+
+    let event = env.events().all().last().unwrap();
+    let data = DistributedEvent::try_from_val(&env, &event.2).unwrap();
+    assert_eq!(data.delegate, Some(keeper.clone()));
+    assert_eq!(data.from, owner);
+
+    assert_eq!(
+        client.try_distribute_as_delegate(&keeper, &1, &owner, &token, &500),
+        Err(Ok(RemittanceSplitError::DelegateCapExceeded))
+    );
+    client.distribute_as_delegate(&keeper, &1, &owner, &token, &400);
+
+    // The cap is available again in the next period.
+    set_time(&env, 1000 + 3600 * 2 + 10);
+    client.distribute_as_delegate(&keeper, &2, &owner, &token, &1_000);
+    let delegate = client.get_delegates(&owner).get(0).unwrap();
+    assert_eq!(delegate.period_start, 1000 + 3600 * 2);
+    assert_eq!(delegate.spent, 1_000);
+    assert_eq!(client.get_distributed_total(&owner, &token), 2_000);
+
+    // The cap is counted in one token; others cannot draw on it.
+    let other = funded_token(&env, &contract_id, &owner, 10_000, 10_000);
+    client.add_allowed_token(&owner, &3, &other);
+    assert_eq!(
+        client.try_distribute_as_delegate(&keeper, &3, &owner, &other, &100),
+        Err(Ok(RemittanceSplitError::TokenNotAllowed))
+    );
+    client.set_delegate(&owner, &4, &keeper, &other, &group, &1_000, &3600);
+    assert_eq!(client.get_delegates(&owner).get(0).unwrap().spent, 0);
+    assert_eq!(
+        client.try_distribute_as_delegate(&keeper, &3, &owner, &token, &100),
+        Err(Ok(RemittanceSplitError::TokenNotAllowed))
+    );
+    client.distribute_as_delegate(&keeper, &3, &owner, &other, &1_000);
+
+    client.revoke_delegate(&owner, &5, &keeper);
+    set_time(&env, 1000 + 3600 * 3);
+    assert_eq!(
+        client.try_distribute_as_delegate(&keeper, &4, &owner, &token, &100),
+        Err(Ok(RemittanceSplitError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_change_delay_queues_config_updates() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let token = funded_token(&env, &contract_id, &owner, 10_000, 0);
+    let group = accounts(&env);
+    set_time(&env, 1000);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    assert_eq!(
+        client.try_set_change_delay(&owner, &1, &(30 * 86400 + 1)),
+        Err(Ok(RemittanceSplitError::InvalidChangeDelay))
+    );
+    // Without a delay in force, setting one applies at once.
+    client.set_change_delay(&owner, &1, &3600);
+    assert_eq!(client.get_config(&owner).unwrap().active.change_delay, 3600);
+
+    client.update_split(&owner, &2, &25, &25, &25, &25);
+    let view = client.get_config(&owner).unwrap();
+    assert_eq!(view.active.buckets.get(0).unwrap().share_bps, 5000);
+    let pending = match view.pending {
+        PendingChange::Queued(pending) => pending,
+        PendingChange::None => panic!("update should be queued"),
+    };
+    assert_eq!(pending.effective_at, 4600);
+    assert_eq!(pending.config.buckets.get(0).unwrap().share_bps, 2500);
+    assert_eq!(client.calculate_split(&owner, &1000).get(0).unwrap(), 500);
+
+    // update_split does not consume a nonce.
+    client.distribute_usdc(&token, &owner, &2, &group, &1000);
+    assert_eq!(client.get_usdc_balance(&token, &group.spending), 500);
+
+    // Once the delay passes the new split is used without further calls.
+    set_time(&env, 4600);
+    let view = client.get_config(&owner).unwrap();
+    assert_eq!(view.pending, PendingChange::None);
+    assert_eq!(view.active.buckets.get(0).unwrap().share_bps, 2500);
+    assert_eq!(client.calculate_split(&owner, &1000).get(0).unwrap(), 250);
+
+    client.distribute_usdc(&token, &owner, &3, &group, &1000);
+    assert_eq!(client.get_usdc_balance(&token, &group.spending), 750);
+    assert_eq!(
+        client.get_config(&owner).unwrap().pending,
+        PendingChange::None
+    );
+}
+
+#[test]
+fn test_update_after_due_change_keeps_it_in_force() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    set_time(&env, 1000);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.set_change_delay(&owner, &1, &3600);
+    client.update_split(&owner, &2, &25, &25, &25, &25);
+
+    // The first change is due, and a second one is queued behind it.
+    set_time(&env, 1000 + 3600);
+    client.update_split(&owner, &2, &10, &20, &30, &40);
+    let view = client.get_config(&owner).unwrap();
+    assert_eq!(view.active.buckets.get(0).unwrap().share_bps, 2500);
+    let pending = match view.pending {
+        PendingChange::Queued(pending) => pending,
+        PendingChange::None => panic!("second update should be queued"),
+    };
+    assert_eq!(pending.effective_at, 1000 + 2 * 3600);
+    assert_eq!(pending.config.buckets.get(0).unwrap().share_bps, 1000);
+
+    // Cancelling the second change leaves the first one in force.
+    client.cancel_pending_config(&owner, &2);
+    set_time(&env, 1000 + 3 * 3600);
+    let view = client.get_config(&owner).unwrap();
+    assert_eq!(view.pending, PendingChange::None);
+    assert_eq!(view.active.buckets.get(0).unwrap().share_bps, 2500);
+    assert_eq!(client.calculate_split(&owner, &1000).get(0).unwrap(), 250);
+}
+
+#[test]
+fn test_cancel_pending_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    set_time(&env, 1000);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.set_change_delay(&owner, &1, &3600);
+    assert_eq!(
+        client.try_cancel_pending_config(&owner, &2),
+        Err(Ok(RemittanceSplitError::NoPendingChange))
+    );
+
+    // Removing the delay is itself delayed, so it can be cancelled.
+    client.set_change_delay(&owner, &2, &0);
+    assert_eq!(client.get_config(&owner).unwrap().active.change_delay, 3600);
+    client.cancel_pending_config(&owner, &3);
+    assert_eq!(
+        client.get_config(&owner).unwrap().pending,
+        PendingChange::None
+    );
+
+    set_time(&env, 10_000);
+    assert_eq!(client.get_config(&owner).unwrap().active.change_delay, 3600);
+
+    // A change whose delay has passed is already in force.
+    client.set_remainder_policy(&owner, &4, &RemainderPolicy::LargestRemainder);
+    set_time(&env, 10_000 + 3600);
+    assert_eq!(
+        client.try_cancel_pending_config(&owner, &5),
+        Err(Ok(RemittanceSplitError::NoPendingChange))
+    );
+    assert_eq!(
+        client.get_config(&owner).unwrap().active.remainder_policy,
+        RemainderPolicy::LargestRemainder
+    );
+}
+
+#[test]
+fn test_distribute_usdc_missing_destination() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    soroban_sdk::token::StellarAssetClient::new(&env, &token.address()).mint(&owner, &1_000);
+
+    let buckets = Vec::from_array(
+        &env,
+        [
+            bucket(&env, "SPENDING", 5000, None),
+            bucket(&env, "school", 5000, None),
+        ],
+    );
+    client.initialize_split_buckets(&owner, &0, &buckets, &RemainderPolicy::LargestBucket);
+
+    let result = client.try_distribute_usdc(&token.address(), &owner, &1, &accounts(&env), &1_000);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::MissingDestination)));
+    assert_eq!(client.get_usdc_balance(&token.address(), &owner), 1_000);
+}
+
+#[test]
+fn test_legacy_config_is_migrated_to_buckets() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        let legacy = LegacySplitConfig {
+            owner: owner.clone(),
+            spending_percent: 40,
+            savings_percent: 30,
+            bills_percent: 10,
+            insurance_percent: 20,
+            timestamp: 7,
+            initialized: true,
+        };
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CONFIG"), &legacy);
+    });
+
+    let config = client.get_config(&owner).unwrap().active;
+    assert_eq!(config.timestamp, 7);
+    assert_eq!(config.buckets.len(), 4);
+    assert_eq!(
+        config.buckets.get(0).unwrap().name,
+        symbol_short!("SPENDING")
+    );
+    assert_eq!(
+        client.get_split(&owner),
+        Vec::from_array(&env, [4000u32, 3000, 1000, 2000])
+    );
+    assert!(client.get_config(&other).is_none());
+
+    // Migrated owners are treated as initialized and can be updated.
+    assert_eq!(
+        client.try_initialize_split(&owner, &0, &25, &25, &25, &25),
+        Err(Ok(RemittanceSplitError::AlreadyInitialized))
+    );
+    client.update_split(&owner, &0, &25, &25, &25, &25);
+    assert_eq!(
+        client.get_split(&owner),
+        Vec::from_array(&env, [2500u32, 2500, 2500, 2500])
+    );
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&symbol_short!("CONFIG")));
+    });
+}
+
+fn thirds(env: &Env) -> Vec<SplitBucket> {
+    Vec::from_array(
+        env,
+        [
+            bucket(env, "SPENDING", 3333, None),
+            bucket(env, "SAVINGS", 3333, None),
+            bucket(env, "BILLS", 3334, None),
+        ],
+    )
+}
+
+#[test]
+fn test_remainder_policy_largest_remainder() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.initialize_split_buckets(
+        &owner,
+        &0,
+        &thirds(&env),
+        &RemainderPolicy::LargestRemainder,
+    );
+
+    // Exact shares 33.33 / 33.33 / 33.34: no dust at all.
+    assert_eq!(
+        client.calculate_split(&owner, &100),
+        Vec::from_array(&env, [33i128, 33, 34])
+    );
+    // Exact shares 3.3330 / 3.3330 / 3.3340: floors 3/3/3, one unit of dust
+    // goes to BILLS, whose exact share lost the most (0.334).
+    assert_eq!(
+        client.calculate_split(&owner, &10),
+        Vec::from_array(&env, [3i128, 3, 4])
+    );
+    // Exact shares 0.6666 / 0.6666 / 0.6668: two units of dust, first to BILLS,
+    // then the tie between SPENDING and SAVINGS goes to the earlier bucket.
+    assert_eq!(
+        client.calculate_split(&owner, &2),
+        Vec::from_array(&env, [1i128, 0, 1])
+    );
+}
+
+#[test]
+fn test_remainder_policy_largest_and_named_bucket() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    let buckets = Vec::from_array(
+        &env,
+        [
+            bucket(&env, "SPENDING", 2500, None),
+            bucket(&env, "SAVINGS", 5000, None),
+            bucket(&env, "BILLS", 2500, None),
+        ],
+    );
+    client.initialize_split_buckets(&owner, &0, &buckets, &RemainderPolicy::LargestBucket);
+    assert_eq!(
+        client.calculate_split(&owner, &7),
+        Vec::from_array(&env, [1i128, 5, 1])
+    );
+
+    client.set_remainder_policy(
+        &owner,
+        &1,
+        &RemainderPolicy::NamedBucket(symbol_short!("SPENDING")),
+    );
+    assert_eq!(client.get_config(&owner).unwrap().active.buckets, buckets);
+    assert_eq!(
+        client.calculate_split(&owner, &7),
+        Vec::from_array(&env, [3i128, 3, 1])
+    );
+}
+
+#[test]
+fn test_remainder_policy_validation_and_legacy_default() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    let unknown = RemainderPolicy::NamedBucket(symbol_short!("tithe"));
+    assert_eq!(
+        client.try_initialize_split_buckets(&owner, &0, &thirds(&env), &unknown),
+        Err(Ok(RemittanceSplitError::InvalidRemainderPolicy))
+    );
+
+    // Percentage configs keep sending the dust to INSURANCE.
+    client.initialize_split(&owner, &0, &33, &33, &33, &1);
+    assert_eq!(
+        client.get_config(&owner).unwrap().active.remainder_policy,
+        RemainderPolicy::NamedBucket(symbol_short!("INSURANCE"))
+    );
+    assert_eq!(
+        client.calculate_split(&owner, &10),
+        Vec::from_array(&env, [3i128, 3, 3, 1])
+    );
+    assert_eq!(
+        client.try_set_remainder_policy(&owner, &1, &unknown),
+        Err(Ok(RemittanceSplitError::InvalidRemainderPolicy))
+    );
+}
+
+fn fixed(env: &Env, name: &str, amount: i128) -> SplitBucket {
+    SplitBucket {
+        fixed_amount: Some(amount),
+        ..bucket(env, name, 0, None)
+    }
+}
+
+fn bounded(
+    env: &Env,
+    name: &str,
+    share_bps: u32,
+    min: Option<i128>,
+    max: Option<i128>,
+) -> SplitBucket {
+    SplitBucket {
+        min_amount: min,
+        max_amount: max,
+        ..bucket(env, name, share_bps, None)
+    }
+}
+
+#[test]
+fn test_fixed_amount_then_percentages() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    // Exactly 50 to bills, the rest 60/40.
+    let buckets = Vec::from_array(
+        &env,
+        [
+            fixed(&env, "BILLS", 50),
+            bucket(&env, "SPENDING", 6000, None),
+            bucket(&env, "SAVINGS", 4000, None),
+        ],
+    );
+    client.initialize_split_buckets(&owner, &0, &buckets, &RemainderPolicy::LargestBucket);
+
+    assert_eq!(
+        client.calculate_split(&owner, &550),
+        Vec::from_array(&env, [50i128, 300, 200])
+    );
+    assert_eq!(
+        client.calculate_split(&owner, &50),
+        Vec::from_array(&env, [50i128, 0, 0])
+    );
+    assert_eq!(
+        client.try_calculate_split(&owner, &49),
+        Err(Ok(RemittanceSplitError::InsufficientAmount))
+    );
+}
+
+#[test]
+fn test_cap_redistributes_to_other_buckets() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    // Savings gets 20% but never more than 200; the excess follows the
+    // 60/20 ratio of the other buckets.
+    let buckets = Vec::from_array(
+        &env,
+        [
+            bucket(&env, "SPENDING", 6000, None),
+            bounded(&env, "SAVINGS", 2000, None, Some(200)),
+            bucket(&env, "BILLS", 2000, None),
+        ],
+    );
+    client.initialize_split_buckets(&owner, &0, &buckets, &RemainderPolicy::LargestBucket);
+
+    assert_eq!(
+        client.calculate_split(&owner, &500),
+        Vec::from_array(&env, [300i128, 100, 100])
+    );
+    assert_eq!(
+        client.calculate_split(&owner, &2_000),
+        Vec::from_array(&env, [1_350i128, 200, 450])
+    );
+}
+
+#[test]
+fn test_floor_takes_from_other_buckets() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    let buckets = Vec::from_array(
+        &env,
+        [
+            fixed(&env, "rent", 100),
+            bucket(&env, "SPENDING", 9000, None),
+            bounded(&env, "INSURANCE", 1000, Some(30), None),
+        ],
+    );
+    client.initialize_split_buckets(&owner, &0, &buckets, &RemainderPolicy::LargestBucket);
+
+    // 10% of 200 is 20, below the floor of 30.
+    assert_eq!(
+        client.calculate_split(&owner, &300),
+        Vec::from_array(&env, [100i128, 170, 30])
+    );
+    // Above the floor the plain percentage applies.
+    assert_eq!(
+        client.calculate_split(&owner, &1_100),
+        Vec::from_array(&env, [100i128, 900, 100])
+    );
+    // Fixed amount plus floor need 130.
+    assert_eq!(
+        client.try_calculate_split(&owner, &129),
+        Err(Ok(RemittanceSplitError::InsufficientAmount))
+    );
+}
+
+#[test]
+fn test_floor_releases_capped_bucket() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    // 99 of 100 would exceed SPENDING's cap of 90, but paying SAVINGS its
+    // floor of 20 leaves SPENDING only 80, under the cap.
+    let buckets = Vec::from_array(
+        &env,
+        [
+            bounded(&env, "SPENDING", 9900, None, Some(90)),
+            bounded(&env, "SAVINGS", 100, Some(20), None),
+        ],
+    );
+    client.initialize_split_buckets(&owner, &0, &buckets, &RemainderPolicy::LargestBucket);
+    assert_eq!(
+        client.calculate_split(&owner, &100),
+        Vec::from_array(&env, [80i128, 20])
+    );
+}
+
+#[test]
+fn test_remainder_skips_capped_bucket() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    let buckets = Vec::from_array(
+        &env,
+        [
+            bounded(&env, "SPENDING", 5000, None, Some(3)),
+            bucket(&env, "SAVINGS", 5000, None),
+        ],
+    );
+    client.initialize_split_buckets(
+        &owner,
+        &0,
+        &buckets,
+        &RemainderPolicy::NamedBucket(symbol_short!("SPENDING")),
+    );
+    // 7 splits 3.5 / 3.5: SPENDING is at its cap, so the dust goes to SAVINGS.
+    assert_eq!(
+        client.calculate_split(&owner, &7),
+        Vec::from_array(&env, [3i128, 4])
+    );
+}
+
+#[test]
+fn test_bucket_rules_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let policy = RemainderPolicy::LargestBucket;
+
+    let invalid = [
+        // Fixed bucket with a share.
+        Vec::from_array(
+            &env,
+            [
+                SplitBucket {
+                    fixed_amount: Some(10),
+                    ..bucket(&env, "rent", 1000, None)
+                },
+                bucket(&env, "SPENDING", 9000, None),
+            ],
+        ),
+        // Non-positive fixed amount.
+        Vec::from_array(
+            &env,
+            [
+                fixed(&env, "rent", 0),
+                bucket(&env, "SPENDING", 10000, None),
+            ],
+        ),
+        // Minimum above maximum.
+        Vec::from_array(
+            &env,
+            [
+                bounded(&env, "SAVINGS", 5000, Some(10), Some(5)),
+                bucket(&env, "SPENDING", 5000, None),
+            ],
+        ),
+        // Every share capped: nowhere for the excess to go.
+        Vec::from_array(
+            &env,
+            [
+                bounded(&env, "SAVINGS", 5000, None, Some(5)),
+                bounded(&env, "SPENDING", 5000, None, Some(5)),
+            ],
+        ),
+    ];
+    for buckets in invalid {
+        assert_eq!(
+            client.try_initialize_split_buckets(&owner, &0, &buckets, &policy),
+            Err(Ok(RemittanceSplitError::InvalidBuckets))
+        );
+    }
+
+    // Dust cannot be routed to a fixed bucket.
+    let buckets = Vec::from_array(
+        &env,
+        [
+            fixed(&env, "rent", 10),
+            bucket(&env, "SPENDING", 10000, None),
+        ],
+    );
+    assert_eq!(
+        client.try_initialize_split_buckets(
+            &owner,
+            &0,
+            &buckets,
+            &RemainderPolicy::NamedBucket(symbol_short!("rent"))
+        ),
+        Err(Ok(RemittanceSplitError::InvalidRemainderPolicy))
+    );
+}
+
+#[test]
+fn test_snapshot_checksum_detects_tampering() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let snapshot = client.export_snapshot(&owner).unwrap();
+    assert_eq!(snapshot.version, 4);
+    assert_eq!(
+        snapshot.checksum,
+        env.crypto()
+            .sha256(&snapshot.config.clone().to_xdr(&env))
+            .to_bytes()
+    );
+
+    // Moving shares between buckets keeps every sum the same but changes
+    // the hash.
+    let mut tampered = snapshot.clone();
+    let mut buckets = tampered.config.buckets.clone();
+    let mut first = buckets.get(0).unwrap();
+    let mut second = buckets.get(1).unwrap();
+    first.share_bps -= 100;
+    second.share_bps += 100;
+    buckets.set(0, first);
+    buckets.set(1, second);
+    tampered.config.buckets = buckets;
+    assert_eq!(
+        client.try_import_snapshot(&owner, &1, &tampered),
+        Err(Ok(RemittanceSplitError::ChecksumMismatch))
+    );
+
+    assert!(client.import_snapshot(&owner, &1, &snapshot));
+}
+
+#[test]
+fn test_presets_are_versioned_copies() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let early = Address::generate(&env);
+    let late = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.init(&admin);
+    client.set_preset_admin(&admin, &admin);
+
+    let student = Symbol::new(&env, "student");
+    let v1 = Vec::from_array(
+        &env,
+        [
+            bucket(&env, "SPENDING", 6000, None),
+            bucket(&env, "SAVINGS", 4000, None),
+        ],
+    );
+    let v2 = Vec::from_array(
+        &env,
+        [
+            bucket(&env, "SPENDING", 5000, None),
+            bucket(&env, "SAVINGS", 5000, None),
+        ],
+    );
+    let policy = RemainderPolicy::LargestBucket;
+
+    assert_eq!(client.set_preset(&admin, &student, &v1, &policy), 1);
+    assert_eq!(client.initialize_split_from_preset(&early, &0, &student), 1);
+
+    assert_eq!(client.set_preset(&admin, &student, &v2, &policy), 2);
+    assert_eq!(client.initialize_split_from_preset(&late, &0, &student), 2);
+
+    // The earlier owner keeps the version they were given.
+    assert_eq!(client.get_config(&early).unwrap().active.buckets, v1);
+    assert_eq!(client.get_config(&late).unwrap().active.buckets, v2);
+    assert_eq!(client.get_preset(&student, &Some(1)).unwrap().buckets, v1);
+    assert_eq!(client.get_preset(&student, &None).unwrap().version, 2);
+
+    let presets = client.list_presets();
+    assert_eq!(presets.len(), 1);
+    assert_eq!(presets.get(0).unwrap().version, 2);
+
+    client.retire_preset(&admin, &student);
+    assert_eq!(client.list_presets().len(), 0);
+    assert_eq!(
+        client.try_initialize_split_from_preset(&Address::generate(&env), &0, &student),
+        Err(Ok(RemittanceSplitError::PresetNotFound))
+    );
+    assert_eq!(client.get_config(&early).unwrap().active.buckets, v1);
+
+    // Publishing again brings it back as the next version.
+    assert_eq!(client.set_preset(&admin, &student, &v1, &policy), 3);
+    assert_eq!(client.list_presets().len(), 1);
+}
+
+#[test]
+fn test_set_preset_validation() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let id = Symbol::new(&env, "debt_payoff");
+    let buckets = Vec::from_array(&env, [bucket(&env, "BILLS", 10000, None)]);
+    let policy = RemainderPolicy::LargestBucket;
+
+    // No preset admin yet.
+    assert_eq!(
+        client.try_set_preset(&admin, &id, &buckets, &policy),
+        Err(Ok(RemittanceSplitError::Unauthorized))
+    );
+    client.init(&admin);
+    client.set_preset_admin(&admin, &admin);
+    assert_eq!(
+        client.try_set_preset(&other, &id, &buckets, &policy),
+        Err(Ok(RemittanceSplitError::Unauthorized))
+    );
+
+    let with_destination =
+        Vec::from_array(&env, [bucket(&env, "BILLS", 10000, Some(other.clone()))]);
+    assert_eq!(
+        client.try_set_preset(&admin, &id, &with_destination, &policy),
+        Err(Ok(RemittanceSplitError::InvalidPreset))
+    );
+    let short = Vec::from_array(&env, [bucket(&env, "BILLS", 9000, None)]);
+    assert_eq!(
+        client.try_set_preset(&admin, &id, &short, &policy),
+        Err(Ok(RemittanceSplitError::PercentagesDoNotSumTo100))
+    );
+    assert_eq!(
+        client.try_retire_preset(&admin, &id),
+        Err(Ok(RemittanceSplitError::PresetNotFound))
+    );
+
+    client.set_preset(&admin, &id, &buckets, &policy);
+    client.initialize_split(&other, &0, &50, &30, &15, &5);
+    assert_eq!(
+        client.try_initialize_split_from_preset(&other, &1, &id),
+        Err(Ok(RemittanceSplitError::AlreadyInitialized))
+    );
 }
@@ -8,10 +8,9 @@
 //! - No unexpected panics or wrap-around behavior
 //!
 //! ## Documented Limitations
-//! - calculate_split uses checked_mul and checked_div to prevent overflow
-//! - Maximum safe amount depends on split percentages (multiplication can overflow)
-//! - Overflow returns RemittanceSplitError::Overflow rather than panicking
-//! - For 100% total split, max safe value is approximately i128::MAX / 100
+//! - calculate_split splits `amount / 10_000` and `amount % 10_000` separately,
+//!   so any positive i128 amount can be split without intermediate overflow
+//! - Any remaining overflow returns RemittanceSplitError::Overflow rather than panicking
 
 use remittance_split::{RemittanceSplit, RemittanceSplitClient};
 use soroban_sdk::testutils::Address as AddressTrait;
@@ -32,7 +31,7 @@ fn test_calculate_split_with_large_amount() {
     // Test with i128::MAX / 200 to ensure multiplication by percentages doesn't overflow
     let large_amount = i128::MAX / 200;
     // client.calculate_split returns Vec<i128> directly
-    let amounts = client.calculate_split(&large_amount);
+    let amounts = client.calculate_split(&owner, &large_amount);
 
     assert_eq!(amounts.len(), 4);
     let total: i128 = amounts.iter().sum();
@@ -52,7 +51,7 @@ fn test_calculate_split_near_max_safe_value() {
 
     // Maximum safe value for multiplication by 100 (largest percentage)
     let max_safe = i128::MAX / 100 - 1;
-    let amounts = client.calculate_split(&max_safe);
+    let amounts = client.calculate_split(&owner, &max_safe);
 
     let total: i128 = amounts.iter().sum();
     assert!((total - max_safe).abs() < 4); // Allow small rounding difference
@@ -72,7 +71,7 @@ fn test_calculate_split_near_max_safe_value() {
 //     // Value that will overflow when multiplied by percentage
 //     let overflow_amount = i128::MAX / 50; // Will overflow when multiplied by 50
 
-//     let result = client.try_calculate_split(&overflow_amount);
+//     let result = client.try_calculate_split(&owner, &overflow_amount);
 
 //     // Should return Overflow error, not panic
 //     assert_eq!(result, Err(Ok(RemittanceSplitError::Overflow)));
@@ -92,7 +91,7 @@ fn test_calculate_split_with_minimal_percentages() {
     let large_amount = i128::MAX / 150;
 
     // FIX: Remove .is_ok() and .unwrap()
-    let amounts = client.calculate_split(&large_amount);
+    let amounts = client.calculate_split(&owner, &large_amount);
 
     let total: i128 = amounts.iter().sum();
     assert_eq!(total, large_amount);
@@ -111,7 +110,7 @@ fn test_get_split_allocations_with_large_amount() {
 
     let large_amount = i128::MAX / 200;
 
-    let allocations = client.get_split_allocations(&large_amount);
+    let allocations = client.get_split_allocations(&owner, &large_amount);
 
     assert_eq!(allocations.len(), 4);
     let total: i128 = allocations.iter().map(|a| a.amount).sum();
@@ -133,7 +132,7 @@ fn test_multiple_splits_with_large_amounts() {
 
     for _ in 0..5 {
         // FIX: result is now directly the amounts Vec
-        let amounts = client.calculate_split(&large_amount);
+        let amounts = client.calculate_split(&owner, &large_amount);
 
         let total: i128 = amounts.iter().sum();
         assert_eq!(total, large_amount);
@@ -154,7 +153,7 @@ fn test_edge_case_i128_max_divided_by_100() {
     let edge_amount = i128::MAX / 100;
 
     // FIX: Remove .is_ok() and .unwrap()
-    let amounts = client.calculate_split(&edge_amount);
+    let amounts = client.calculate_split(&owner, &edge_amount);
 
     assert_eq!(amounts.len(), 4);
 }
@@ -174,7 +173,7 @@ fn test_split_with_100_percent_to_one_category() {
     let large_amount = i128::MAX / 150;
 
     // FIX: result is now the amounts Vec directly
-    let amounts = client.calculate_split(&large_amount);
+    let amounts = client.calculate_split(&owner, &large_amount);
 
     // First amount should be the full amount
     // .get(i) returns Option, so .unwrap() here is correct and necessary
@@ -199,7 +198,7 @@ fn test_rounding_behavior_with_large_amounts() {
 
     let large_amount = i128::MAX / 200;
 
-    let amounts = client.calculate_split(&large_amount);
+    let amounts = client.calculate_split(&owner, &large_amount);
 
     let total: i128 = amounts.iter().sum();
 
@@ -229,7 +228,7 @@ fn test_sequential_large_calculations() {
 
     for amount in amounts_to_test {
         // FIX: result is directly the soroban_sdk::Vec<i128>
-        let splits = client.calculate_split(&amount);
+        let splits = client.calculate_split(&owner, &amount);
 
         let total: i128 = splits.iter().sum();
         assert_eq!(total, amount, "Failed for amount: {}", amount);
@@ -247,21 +246,21 @@ fn test_checked_arithmetic_prevents_silent_overflow() {
 
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
-    // Test values that would overflow with unchecked arithmetic
-    let dangerous_amounts = vec![
-        i128::MAX / 40, // Will overflow when multiplied by 50
-        i128::MAX / 30, // Will overflow when multiplied by 50
-        i128::MAX,      // Will definitely overflow
-    ];
+    // Values that would overflow a naive `amount * share` computation. The
+    // split is computed as quotient and remainder, so they must succeed
+    // without wrapping around.
+    let dangerous_amounts = vec![i128::MAX / 40, i128::MAX / 30, i128::MAX];
 
     for amount in dangerous_amounts {
-        let result = client.try_calculate_split(&amount);
-        // Should return error, not panic or wrap around
-        assert!(
-            result.is_err(),
-            "Should have detected overflow for amount: {}",
-            amount
-        );
+        let amounts = client.calculate_split(&owner, &amount);
+        let mut total: i128 = 0;
+        for part in amounts.iter() {
+            assert!(part >= 0, "Negative allocation for amount: {}", amount);
+            total = total
+                .checked_add(part)
+                .expect("allocations must not exceed the input");
+        }
+        assert_eq!(total, amount, "Allocations must sum to amount: {}", amount);
     }
 }
 
@@ -281,7 +280,7 @@ fn test_insurance_remainder_calculation_with_large_values() {
 
     // FIX: Remove .is_ok() and .unwrap()
     // result is already soroban_sdk::Vec<i128>
-    let amounts = client.calculate_split(&large_amount);
+    let amounts = client.calculate_split(&owner, &large_amount);
 
     // Verify insurance (last element) is calculated correctly as remainder
     // Note: Soroban Vec::get returns Option, so these unwrap()s are correct for the elements
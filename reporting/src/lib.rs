@@ -1,6 +1,7 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contractclient, contractimpl, contracttype, symbol_short, Address, Env, Map, Vec,
+    contract, contractclient, contractimpl, contracttype, symbol_short, Address, Env, Map, Symbol,
+    Vec,
 };
 
 use remitwise_common::Category;
@@ -29,6 +30,7 @@ pub struct HealthScore {
 pub struct CategoryBreakdown {
     pub category: Category,
     pub amount: i128,
+    /// Bucket share in whole percent, rounded to the nearest percent
     pub percentage: u32,
 }
 
@@ -77,6 +79,8 @@ pub struct BillComplianceReport {
     pub total_amount: i128,
     pub paid_amount: i128,
     pub unpaid_amount: i128,
+    /// Late fees charged on top of the bill amounts above
+    pub late_fees: i128,
     pub compliance_percentage: u32,
     pub period_start: u64,
     pub period_end: u64,
@@ -208,8 +212,8 @@ pub struct StorageStats {
 
 #[contractclient(name = "RemittanceSplitClient")]
 pub trait RemittanceSplitTrait {
-    fn get_split(env: &Env) -> Vec<u32>;
-    fn calculate_split(env: Env, total_amount: i128) -> Vec<i128>;
+    fn get_split(env: &Env, owner: Address) -> Vec<u32>;
+    fn get_split_allocations(env: Env, owner: Address, total_amount: i128) -> Vec<Allocation>;
 }
 
 #[contractclient(name = "SavingsGoalsClient")]
@@ -233,6 +237,13 @@ pub trait InsuranceTrait {
 
 // Data structures from other contracts (needed for client traits)
 
+#[contracttype]
+#[derive(Clone)]
+pub struct Allocation {
+    pub category: Symbol,
+    pub amount: i128,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct SavingsGoal {
@@ -246,12 +257,37 @@ pub struct SavingsGoal {
     pub unlock_date: Option<u64>,
 }
 
+#[contracttype]
+#[derive(Clone)]
+pub enum LateFee {
+    None,
+    Flat(i128),
+    Percent(u32),
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct LateFeeRule {
+    pub fee: LateFee,
+    pub grace_days: u32,
+    pub period_days: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct BillPayment {
+    pub amount: i128,
+    pub timestamp: u64,
+    pub payer: Address,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct Bill {
     pub id: u32,
     pub owner: Address,
     pub name: soroban_sdk::String,
+    pub external_ref: Option<soroban_sdk::String>,
     pub amount: i128,
     pub due_date: u64,
     pub recurring: bool,
@@ -261,6 +297,11 @@ pub struct Bill {
     pub paid_at: Option<u64>,
     pub schedule_id: Option<u32>,
     pub currency: soroban_sdk::String,
+    pub payee: Option<Address>,
+    pub token: Option<Address>,
+    pub payments: Vec<BillPayment>,
+    pub late_fee_rule: LateFeeRule,
+    pub late_fees: i128,
 }
 
 #[contracttype]
@@ -384,7 +425,7 @@ impl ReportingContract {
     /// Generate remittance summary report
     pub fn get_remittance_summary(
         env: Env,
-        _user: Address,
+        user: Address,
         total_amount: i128,
         period_start: u64,
         period_end: u64,
@@ -396,22 +437,31 @@ impl ReportingContract {
             .expect("Contract addresses not configured");
 
         let split_client = RemittanceSplitClient::new(&env, &addresses.remittance_split);
-        let split_percentages = split_client.get_split();
-        let split_amounts = split_client.calculate_split(&total_amount);
+        // Shares come back in basis points, in the same bucket order as the
+        // allocations; buckets themselves are matched by name.
+        let split_shares_bps = split_client.get_split(&user);
+        let allocations = split_client.get_split_allocations(&user, &total_amount);
 
         let mut breakdown = Vec::new(&env);
         let categories = [
-            Category::Spending,
-            Category::Savings,
-            Category::Bills,
-            Category::Insurance,
+            (Category::Spending, symbol_short!("SPENDING")),
+            (Category::Savings, symbol_short!("SAVINGS")),
+            (Category::Bills, symbol_short!("BILLS")),
+            (Category::Insurance, symbol_short!("INSURANCE")),
         ];
 
-        for (i, &category) in categories.iter().enumerate() {
+        for (category, bucket) in categories {
+            let index = allocations
+                .iter()
+                .position(|allocation| allocation.category == bucket)
+                .expect(
+                    "Remittance split is missing a SPENDING, SAVINGS, BILLS or INSURANCE bucket",
+                );
+            let share_bps = split_shares_bps.get(index as u32).unwrap_or(0);
             breakdown.push_back(CategoryBreakdown {
                 category,
-                amount: split_amounts.get(i as u32).unwrap_or(0),
-                percentage: split_percentages.get(i as u32).unwrap_or(0),
+                amount: allocations.get(index as u32).unwrap().amount,
+                percentage: (share_bps + 50) / 100,
             });
         }
 
@@ -493,6 +543,7 @@ impl ReportingContract {
         let mut total_amount = 0i128;
         let mut paid_amount = 0i128;
         let mut unpaid_amount = 0i128;
+        let mut late_fees = 0i128;
 
         let current_time = env.ledger().timestamp();
 
@@ -508,6 +559,7 @@ impl ReportingContract {
 
             total_bills += 1;
             total_amount += bill.amount;
+            late_fees += bill.late_fees;
 
             if bill.paid {
                 paid_bills += 1;
@@ -521,11 +573,7 @@ impl ReportingContract {
             }
         }
 
-        let compliance_percentage = if total_bills > 0 {
-            (paid_bills * 100) / total_bills
-        } else {
-            100
-        };
+        let compliance_percentage = (paid_bills * 100).checked_div(total_bills).unwrap_or(100);
 
         BillComplianceReport {
             total_bills,
@@ -535,6 +583,7 @@ impl ReportingContract {
             total_amount,
             paid_amount,
             unpaid_amount,
+            late_fees,
             compliance_percentage,
             period_start,
             period_end,
@@ -1,7 +1,7 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Map, String,
-    Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, token::TokenClient,
+    xdr::ToXdr, Address, BytesN, Env, IntoVal, Map, String, Symbol, Val, Vec,
 };
 
 // Event topics
@@ -39,6 +39,8 @@ pub struct GoalCompletedEvent {
 
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280;
 const INSTANCE_BUMP_AMOUNT: u32 = 518400;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = 17280;
+const PERSISTENT_BUMP_AMOUNT: u32 = 518400;
 
 /// Pagination constants
 pub const DEFAULT_PAGE_LIMIT: u32 = 20;
@@ -56,6 +58,149 @@ pub struct SavingsGoal {
     pub locked: bool,
     pub unlock_date: Option<u64>,
     pub tags: Vec<String>,
+    /// Token held in custody for this goal. `None` for bookkeeping-only goals
+    /// created with `create_goal`, whose `current_amount` moves no tokens.
+    pub token: Option<Address>,
+    pub lock_policy: LockPolicy,
+}
+
+/// Goal layout kept in the instance `GOALS` map before goals moved to
+/// per-entry storage. Only read by `migrate_storage`.
+#[contracttype]
+#[derive(Clone)]
+pub struct LegacySavingsGoal {
+    pub id: u32,
+    pub owner: Address,
+    pub name: String,
+    pub target_amount: i128,
+    pub current_amount: i128,
+    pub target_date: u64,
+    pub locked: bool,
+    pub unlock_date: Option<u64>,
+    pub tags: Vec<String>,
+}
+
+/// A depositor to a goal. `total` is everything they have deposited, and
+/// `approved` is whether they may still deposit. The owner is always approved.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GoalContribution {
+    pub contributor: Address,
+    pub approved: bool,
+    pub total: i128,
+}
+
+/// A progress point on a goal: a percentage of `target_amount` or an
+/// absolute amount.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MilestoneTarget {
+    Percent(u32),
+    Amount(i128),
+}
+
+/// A milestone and when the goal first reached it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Milestone {
+    pub target: MilestoneTarget,
+    pub reached_at: Option<u64>,
+}
+
+/// Progress towards a goal. The required rates are what must be deposited
+/// per day or week, rounded up, to reach the target by `target_date`; once the
+/// date has passed they equal the whole remaining amount.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GoalProgress {
+    pub goal_id: u32,
+    pub current_amount: i128,
+    pub target_amount: i128,
+    pub percent: u32,
+    pub seconds_remaining: u64,
+    pub required_daily: i128,
+    pub required_weekly: i128,
+    pub milestones: Vec<Milestone>,
+}
+
+/// Interest rate for one token. `index` is the interest earned per unit of
+/// balance since the rate was first set, scaled by `INTEREST_INDEX_SCALE`,
+/// as of `updated_at`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InterestRate {
+    pub apr_bps: u32,
+    pub index: i128,
+    pub updated_at: u64,
+}
+
+/// Interest earned by one goal: `pending` has accrued but not yet been
+/// added to `current_amount`, `claimed` has.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GoalInterest {
+    pub goal_id: u32,
+    pub apr_bps: u32,
+    pub pending: i128,
+    pub claimed: i128,
+}
+
+/// Stored per-goal accrual state; `index` is the token index at the last sync.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InterestAccount {
+    pub index: i128,
+    pub pending: i128,
+    pub claimed: i128,
+}
+
+/// What happens when deposits take a goal to or past its `target_amount`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CompletionAction {
+    /// Deposits keep accumulating on the goal.
+    None,
+    /// Anything above the target moves to the given goal.
+    SweepTo(u32),
+    /// Schedules funding the goal are deactivated.
+    PauseSchedules,
+    /// Anything above the target is paid back to the owner.
+    ReturnExcess,
+}
+
+/// How `withdraw_from_goal` treats a withdrawal before the goal matures.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LockPolicy {
+    /// Withdrawals fail while the goal is locked or before its `unlock_date`.
+    Hard,
+    /// Withdrawals are allowed at any time, ignoring `locked`, but one made
+    /// before the goal's `unlock_date` or `target_date` forfeits the given
+    /// basis points of the amount to the destination.
+    Soft(u32, PenaltyDestination),
+    /// Withdrawals are never restricted.
+    None,
+}
+
+/// Where an early-withdrawal penalty goes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PenaltyDestination {
+    /// Paid to an address, e.g. one the family designated.
+    Recipient(Address),
+    /// Credited to another goal holding the same token.
+    Goal(u32),
+}
+
+/// Custodial balance check for one token: the contract's `balance` should
+/// equal `liabilities`, the sum of `current_amount` over the token's goals.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SolvencyReport {
+    pub token: Address,
+    pub balance: i128,
+    pub liabilities: i128,
+    pub solvent: bool,
 }
 
 /// Paginated result for savings goal queries
@@ -95,6 +240,7 @@ pub enum SavingsGoalsError {
     GoalLocked = 4,
     InsufficientBalance = 5,
     Overflow = 6,
+    TokenMismatch = 7,
 }
 
 impl From<SavingsGoalsError> for soroban_sdk::Error {
@@ -124,6 +270,10 @@ impl From<SavingsGoalsError> for soroban_sdk::Error {
                 soroban_sdk::xdr::ScErrorType::Contract,
                 soroban_sdk::xdr::ScErrorCode::InvalidInput,
             )),
+            SavingsGoalsError::TokenMismatch => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidInput,
+            )),
         }
     }
 }
@@ -154,15 +304,72 @@ pub enum SavingsEvent {
     ScheduleMissed,
     ScheduleModified,
     ScheduleCancelled,
+    ContributorAdded,
+    ContributorRemoved,
+    MilestoneReached,
+    GoalTransfer,
+    GoalsRebalanced,
+    InterestAccrued,
+    OverflowSwept,
+    ExcessReturned,
+    SchedulesPaused,
+    ScheduleFailed,
+    DepositAllocated,
+    InterestFunded,
+}
+
+/// Why `execute_due_savings_schedules` could not run a due schedule.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ScheduleFailureReason {
+    GoalNotFound,
+    Overflow,
+    Paused,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduleFailure {
+    pub schedule_id: u32,
+    pub reason: ScheduleFailureReason,
+    pub timestamp: u64,
+}
+
+/// Outcome of one bounded `execute_due_savings_schedules` call.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduleRun {
+    pub executed: Vec<u32>,
+    pub failed: Vec<ScheduleFailure>,
+    /// Pass as `cursor` to continue the sweep. 0 = no more schedules.
+    pub next_cursor: u32,
 }
 
+/// State a goal keeps outside `SavingsGoal`. Snapshots carry one per goal,
+/// in the same order as `goals`, so an import restores it. Entries that were
+/// never written read as their defaults.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GoalState {
+    pub goal_id: u32,
+    pub funding: GoalFunding,
+    pub contributions: Vec<GoalContribution>,
+    pub milestones: Vec<Milestone>,
+    pub interest: InterestAccount,
+    pub completion: CompletionAction,
+}
+
+/// Snapshot for data export/import. The checksum is the SHA-256 of the XDR
+/// encoding of `(next_id, goals, states)`, so `data_migration` can verify it
+/// off-chain.
 #[contracttype]
 #[derive(Clone)]
 pub struct GoalsExportSnapshot {
     pub version: u32,
-    pub checksum: u64,
+    pub checksum: BytesN<32>,
     pub next_id: u32,
     pub goals: Vec<SavingsGoal>,
+    pub states: Vec<GoalState>,
 }
 
 #[contracttype]
@@ -174,10 +381,18 @@ pub struct AuditEntry {
     pub success: bool,
 }
 
-const SNAPSHOT_VERSION: u32 = 1;
+const SNAPSHOT_VERSION: u32 = 4;
 const MAX_AUDIT_ENTRIES: u32 = 100;
 const CONTRACT_VERSION: u32 = 1;
 const MAX_BATCH_SIZE: u32 = 50;
+const MAX_PENALTY_BPS: u32 = 10_000;
+const MAX_CONTRIBUTORS: u32 = 20;
+const MAX_MILESTONES: u32 = 10;
+const SECONDS_PER_DAY: u64 = 86_400;
+const SECONDS_PER_WEEK: u64 = 7 * SECONDS_PER_DAY;
+const SECONDS_PER_YEAR: u64 = 365 * SECONDS_PER_DAY;
+const MAX_APR_BPS: u32 = 10_000;
+pub const INTEREST_INDEX_SCALE: i128 = 1_000_000_000_000;
 
 pub mod pause_functions {
     use soroban_sdk::{symbol_short, Symbol};
@@ -195,6 +410,42 @@ pub struct ContributionItem {
     pub amount: i128,
 }
 
+/// A goal's share in `rebalance_goals`.
+#[contracttype]
+#[derive(Clone)]
+pub struct RebalanceWeight {
+    pub goal_id: u32,
+    pub weight: u32,
+}
+
+/// How `deposit_to_owner_goals` divides a deposit across goals.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FundingStrategy {
+    /// Fill goals one at a time, highest `priority` first.
+    Priority,
+    /// Split in proportion to each goal's `weight`.
+    Proportional,
+    /// Fill goals one at a time, earliest `target_date` first.
+    ClosestDeadline,
+}
+
+/// A goal's place in `deposit_to_owner_goals`. Goals that never had one set
+/// have priority 0 and weight 1.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GoalFunding {
+    pub priority: u32,
+    pub weight: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GoalAllocation {
+    pub goal_id: u32,
+    pub amount: i128,
+}
+
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -213,6 +464,32 @@ impl SavingsGoalContract {
     const STORAGE_NEXT_ID: Symbol = symbol_short!("NEXT_ID");
     const STORAGE_GOALS: Symbol = symbol_short!("GOALS");
     const STORAGE_OWNER_GOAL_IDS: Symbol = symbol_short!("OWN_GOAL");
+    const STORAGE_SCHEDULES: Symbol = symbol_short!("SAV_SCH");
+    const STORAGE_OWNER_SCHEDULE_IDS: Symbol = symbol_short!("OWN_SCH");
+    /// Sum of `current_amount` over custodial goals, per token.
+    const STORAGE_CUSTODY: Symbol = symbol_short!("CUSTODY");
+    const STORAGE_CONTRIBUTIONS: Symbol = symbol_short!("CONTRIBS");
+    const STORAGE_MILESTONES: Symbol = symbol_short!("MILESTONE");
+    const STORAGE_RATE_ADMIN: Symbol = symbol_short!("RATE_ADM");
+    const STORAGE_RATES: Symbol = symbol_short!("RATES");
+    const STORAGE_INTEREST: Symbol = symbol_short!("INTEREST");
+    /// Tokens set aside for interest, per token.
+    const STORAGE_RESERVE: Symbol = symbol_short!("RESERVE");
+    const STORAGE_COMPLETION: Symbol = symbol_short!("ON_DONE");
+    const STORAGE_SCHEDULE_FAILURES: Symbol = symbol_short!("SCH_FAIL");
+    const STORAGE_FUNDING: Symbol = symbol_short!("FUNDING");
+
+    /// Funding of a goal that never had `set_goal_funding` called.
+    const DEFAULT_FUNDING: GoalFunding = GoalFunding {
+        priority: 0,
+        weight: 1,
+    };
+    /// Interest account of a goal that has not accrued yet.
+    const EMPTY_INTEREST: InterestAccount = InterestAccount {
+        index: 0,
+        pending: 0,
+        claimed: 0,
+    };
 
     // -----------------------------------------------------------------------
     // Internal helpers
@@ -258,22 +535,19 @@ impl SavingsGoalContract {
     // Pause / upgrade
     // -----------------------------------------------------------------------
 
-    /// Bootstrap storage: set NEXT_ID to 1 and GOALS to an empty map only when
-    /// those keys are missing. Intended to be idempotent: calling init() more
-    /// than once (e.g. from different entrypoints or upgrade paths) must not
-    /// overwrite existing goals or reset NEXT_ID, to avoid ID collisions and
-    /// data loss.
+    /// Bootstrap storage: set NEXT_ID to 1 only when the key is missing.
+    /// Goals live in their own entries, so there is no map to create.
+    /// Intended to be idempotent: calling init() more than once (e.g. from
+    /// different entrypoints or upgrade paths) must not overwrite existing
+    /// goals or reset NEXT_ID, to avoid ID collisions and data loss.
+    ///
+    /// `rate_admin` becomes the interest rate admin on the first call; later
+    /// calls leave the current admin in place.
     pub fn init(env: Env) {
         let storage = env.storage().persistent();
         if storage.get::<_, u32>(&Self::STORAGE_NEXT_ID).is_none() {
             storage.set(&Self::STORAGE_NEXT_ID, &1u32);
         }
-        if storage
-            .get::<_, Map<u32, SavingsGoal>>(&Self::STORAGE_GOALS)
-            .is_none()
-        {
-            storage.set(&Self::STORAGE_GOALS, &Map::<u32, SavingsGoal>::new(&env));
-        }
     }
 
     pub fn set_pause_admin(env: Env, caller: Address, new_admin: Address) {
@@ -392,6 +666,63 @@ impl SavingsGoalContract {
             .set(&symbol_short!("UPG_ADM"), &new_admin);
     }
 
+    /// Move goals and schedules that earlier versions kept in single
+    /// instance-storage maps into per-entry persistent storage, rebuilding
+    /// the owner indexes. Upgrade admin only.
+    ///
+    /// Legacy goals hold no tokens and had no lock policy, so they become
+    /// bookkeeping-only goals with `LockPolicy::Hard`.
+    ///
+    /// Run once right after upgrading. Returns the number of entries moved;
+    /// calling it again finds nothing and returns 0.
+    pub fn migrate_storage(env: Env, caller: Address) -> u32 {
+        caller.require_auth();
+        let admin = Self::get_upgrade_admin(&env).expect("No upgrade admin set");
+        if admin != caller {
+            Self::append_audit(&env, symbol_short!("migrate"), &caller, false);
+            panic!("Unauthorized");
+        }
+        Self::extend_instance_ttl(&env);
+
+        let instance = env.storage().instance();
+        let mut moved = 0u32;
+        let goals: Option<Map<u32, LegacySavingsGoal>> = instance.get(&Self::STORAGE_GOALS);
+        for (_, legacy) in goals.unwrap_or_else(|| Map::new(&env)).iter() {
+            let goal = SavingsGoal {
+                id: legacy.id,
+                owner: legacy.owner,
+                name: legacy.name,
+                target_amount: legacy.target_amount,
+                current_amount: legacy.current_amount,
+                target_date: legacy.target_date,
+                locked: legacy.locked,
+                unlock_date: legacy.unlock_date,
+                tags: legacy.tags,
+                token: None,
+                lock_policy: LockPolicy::Hard,
+            };
+            Self::save_goal(&env, &goal);
+            Self::append_owner_goal_id(&env, &goal.owner, goal.id);
+            moved += 1;
+        }
+        let schedules: Option<Map<u32, SavingsSchedule>> = instance.get(&Self::STORAGE_SCHEDULES);
+        for (_, schedule) in schedules.unwrap_or_else(|| Map::new(&env)).iter() {
+            Self::save_schedule(&env, &schedule);
+            Self::append_owner_schedule_id(&env, &schedule.owner, schedule.id);
+            moved += 1;
+        }
+        instance.remove(&Self::STORAGE_GOALS);
+        instance.remove(&Self::STORAGE_OWNER_GOAL_IDS);
+        instance.remove(&Self::STORAGE_SCHEDULES);
+        // Left behind by init() in earlier versions.
+        env.storage().persistent().remove(&Self::STORAGE_GOALS);
+
+        Self::append_audit(&env, symbol_short!("migrate"), &caller, true);
+        env.events()
+            .publish((symbol_short!("savings"), symbol_short!("migrated")), moved);
+        moved
+    }
+
     pub fn set_version(env: Env, caller: Address, new_version: u32) {
         caller.require_auth();
         let admin = Self::get_upgrade_admin(&env).expect("No upgrade admin set");
@@ -417,29 +748,18 @@ impl SavingsGoalContract {
             panic!("Tags cannot be empty");
         }
         for tag in tags.iter() {
-            if tag.len() == 0 || tag.len() > 32 {
+            if tag.is_empty() || tag.len() > 32 {
                 panic!("Tag must be between 1 and 32 characters");
             }
         }
     }
 
-    pub fn add_tags_to_goal(
-        env: Env,
-        caller: Address,
-        goal_id: u32,
-        tags: Vec<String>,
-    ) {
+    pub fn add_tags_to_goal(env: Env, caller: Address, goal_id: u32, tags: Vec<String>) {
         caller.require_auth();
         Self::validate_tags(&tags);
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut goal = goals.get(goal_id).expect("Goal not found");
+        let mut goal = Self::load_goal(&env, goal_id).expect("Goal not found");
 
         if goal.owner != caller {
             Self::append_audit(&env, symbol_short!("add_tags"), &caller, false);
@@ -450,10 +770,7 @@ impl SavingsGoalContract {
             goal.tags.push_back(tag);
         }
 
-        goals.set(goal_id, goal);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        Self::save_goal(&env, &goal);
 
         env.events().publish(
             (symbol_short!("savings"), symbol_short!("tags_add")),
@@ -463,23 +780,12 @@ impl SavingsGoalContract {
         Self::append_audit(&env, symbol_short!("add_tags"), &caller, true);
     }
 
-    pub fn remove_tags_from_goal(
-        env: Env,
-        caller: Address,
-        goal_id: u32,
-        tags: Vec<String>,
-    ) {
+    pub fn remove_tags_from_goal(env: Env, caller: Address, goal_id: u32, tags: Vec<String>) {
         caller.require_auth();
         Self::validate_tags(&tags);
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut goal = goals.get(goal_id).expect("Goal not found");
+        let mut goal = Self::load_goal(&env, goal_id).expect("Goal not found");
 
         if goal.owner != caller {
             Self::append_audit(&env, symbol_short!("rem_tags"), &caller, false);
@@ -501,10 +807,7 @@ impl SavingsGoalContract {
         }
 
         goal.tags = new_tags;
-        goals.set(goal_id, goal);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        Self::save_goal(&env, &goal);
 
         env.events().publish(
             (symbol_short!("savings"), symbol_short!("tags_rem")),
@@ -524,6 +827,34 @@ impl SavingsGoalContract {
         name: String,
         target_amount: i128,
         target_date: u64,
+    ) -> Result<u32, SavingsGoalsError> {
+        Self::new_goal(env, owner, name, target_amount, target_date, None)
+    }
+
+    /// Creates a goal whose balance is held in `token`.
+    ///
+    /// Deposits transfer `token` from the depositor into the contract and
+    /// withdrawals transfer it back to the owner, so locks protect real funds.
+    /// Scheduled deposits pull from the owner through the allowance they
+    /// granted this contract.
+    pub fn create_token_goal(
+        env: Env,
+        owner: Address,
+        name: String,
+        target_amount: i128,
+        target_date: u64,
+        token: Address,
+    ) -> Result<u32, SavingsGoalsError> {
+        Self::new_goal(env, owner, name, target_amount, target_date, Some(token))
+    }
+
+    fn new_goal(
+        env: Env,
+        owner: Address,
+        name: String,
+        target_amount: i128,
+        target_date: u64,
+        token: Option<Address>,
     ) -> Result<u32, SavingsGoalsError> {
         owner.require_auth();
         Self::require_not_paused(&env, pause_functions::CREATE_GOAL);
@@ -535,12 +866,6 @@ impl SavingsGoalContract {
 
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
         let next_id = env
             .storage()
             .instance()
@@ -558,12 +883,11 @@ impl SavingsGoalContract {
             locked: true,
             unlock_date: None,
             tags: Vec::new(&env),
+            token,
+            lock_policy: LockPolicy::Hard,
         };
 
-        goals.set(next_id, goal.clone());
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        Self::save_goal(&env, &goal);
         env.storage()
             .instance()
             .set(&symbol_short!("NEXT_ID"), &next_id);
@@ -588,7 +912,7 @@ impl SavingsGoalContract {
     /// Adds funds to an existing savings goal.
     ///
     /// # Arguments
-    /// * `caller` - Address of the goal owner (must authorize)
+    /// * `caller` - Goal owner or approved contributor (must authorize)
     /// * `goal_id` - ID of the goal to add funds to
     /// * `amount` - Amount to add in stroops (must be > 0)
     ///
@@ -598,7 +922,6 @@ impl SavingsGoalContract {
     /// # Errors
     /// * `InvalidAmount` - If amount ≤ 0
     /// * `GoalNotFound` - If goal_id does not exist
-    /// * `Unauthorized` - If caller is not the goal owner
     /// * `Overflow` - If adding amount would overflow i128
     ///
     /// # Panics
@@ -619,13 +942,7 @@ impl SavingsGoalContract {
 
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut goal = match goals.get(goal_id) {
+        let mut goal = match Self::load_goal(&env, goal_id) {
             Some(g) => g,
             None => {
                 Self::append_audit(&env, symbol_short!("add"), &caller, false);
@@ -633,24 +950,25 @@ impl SavingsGoalContract {
             }
         };
 
-        // Access control: verify caller is the owner
-        if goal.owner != caller {
+        // Access control: verify caller is the owner or an approved contributor
+        if !Self::can_contribute(&env, &goal, &caller) {
             Self::append_audit(&env, symbol_short!("add"), &caller, false);
-            panic!("Only the goal owner can add funds");
+            panic!("Only the goal owner or an approved contributor can add funds");
         }
 
+        Self::sync_interest(&env, &goal);
         goal.current_amount = goal
             .current_amount
             .checked_add(amount)
             .ok_or(SavingsGoalsError::Overflow)?;
+        Self::take_custody(&env, &goal, &caller, amount);
+        Self::record_contribution(&env, goal_id, &caller, amount);
         let new_total = goal.current_amount;
         let was_completed = new_total >= goal.target_amount;
         let previously_completed = (new_total - amount) >= goal.target_amount;
 
-        goals.set(goal_id, goal.clone());
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        Self::save_goal(&env, &goal);
+        Self::check_milestones(&env, &goal);
 
         let funds_event = FundsAddedEvent {
             goal_id,
@@ -683,6 +1001,11 @@ impl SavingsGoalContract {
             );
         }
 
+        if was_completed {
+            Self::apply_completion_action(&env, goal_id);
+            return Ok(Self::load_goal(&env, goal_id).unwrap().current_amount);
+        }
+
         Ok(new_total)
     }
 
@@ -696,40 +1019,34 @@ impl SavingsGoalContract {
         if contributions.len() > MAX_BATCH_SIZE {
             panic!("Batch too large");
         }
-        let goals_map: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
         for item in contributions.iter() {
             if item.amount <= 0 {
                 panic!("Amount must be positive");
             }
-            let goal = goals_map.get(item.goal_id).expect("Goal not found");
-            if goal.owner != caller {
-                panic!("Not owner of all goals");
+            let goal = Self::load_goal(&env, item.goal_id).expect("Goal not found");
+            if !Self::can_contribute(&env, &goal, &caller) {
+                panic!("Not a contributor to all goals");
             }
         }
         Self::extend_instance_ttl(&env);
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
         let mut count = 0u32;
         for item in contributions.iter() {
-            let mut goal = goals.get(item.goal_id).expect("Goal not found");
-            if goal.owner != caller {
+            let mut goal = Self::load_goal(&env, item.goal_id).expect("Goal not found");
+            if !Self::can_contribute(&env, &goal, &caller) {
                 panic!("Batch validation failed");
             }
+            Self::sync_interest(&env, &goal);
             goal.current_amount = goal
                 .current_amount
                 .checked_add(item.amount)
                 .expect("overflow");
+            Self::take_custody(&env, &goal, &caller, item.amount);
+            Self::record_contribution(&env, item.goal_id, &caller, item.amount);
             let new_total = goal.current_amount;
             let was_completed = new_total >= goal.target_amount;
             let previously_completed = (new_total - item.amount) >= goal.target_amount;
-            goals.set(item.goal_id, goal.clone());
+            Self::save_goal(&env, &goal);
+            Self::check_milestones(&env, &goal);
             let funds_event = FundsAddedEvent {
                 goal_id: item.goal_id,
                 amount: item.amount,
@@ -756,11 +1073,11 @@ impl SavingsGoalContract {
                     (item.goal_id, caller.clone()),
                 );
             }
+            if was_completed {
+                Self::apply_completion_action(&env, item.goal_id);
+            }
             count += 1;
         }
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
         env.events().publish(
             (symbol_short!("savings"), symbol_short!("batch_add")),
             (count, caller),
@@ -782,10 +1099,14 @@ impl SavingsGoalContract {
     /// * `InvalidAmount` - If amount ≤ 0
     /// * `GoalNotFound` - If goal_id does not exist
     /// * `Unauthorized` - If caller is not the goal owner
-    /// * `GoalLocked` - If goal is locked or time-locked
+    /// * `GoalLocked` - If a hard-locked goal is locked or time-locked
     /// * `InsufficientBalance` - If amount > current_amount
     /// * `Overflow` - If subtraction would underflow i128
     ///
+    /// Under a soft lock policy an early withdrawal succeeds, but the penalty
+    /// is taken out of `amount` and sent to the policy's destination. The
+    /// `FundsWithdrawn` event reports `(goal_id, caller, amount, penalty)`.
+    ///
     /// # Panics
     /// * If `caller` does not authorize the transaction
     pub fn withdraw_from_goal(
@@ -804,13 +1125,7 @@ impl SavingsGoalContract {
 
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut goal = match goals.get(goal_id) {
+        let mut goal = match Self::load_goal(&env, goal_id) {
             Some(g) => g,
             None => {
                 Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
@@ -823,112 +1138,451 @@ impl SavingsGoalContract {
             return Err(SavingsGoalsError::Unauthorized);
         }
 
-        if goal.locked {
-            Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
-            return Err(SavingsGoalsError::GoalLocked);
-        }
-
-        if let Some(unlock_date) = goal.unlock_date {
-            let current_time = env.ledger().timestamp();
-            if current_time < unlock_date {
+        let penalty = match Self::withdrawal_penalty(&env, &goal, amount) {
+            Ok(penalty) => penalty,
+            Err(e) => {
                 Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
-                return Err(SavingsGoalsError::GoalLocked);
+                return Err(e);
             }
-        }
+        };
 
         if amount > goal.current_amount {
             Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
             return Err(SavingsGoalsError::InsufficientBalance);
         }
 
+        Self::sync_interest(&env, &goal);
         goal.current_amount = goal
             .current_amount
             .checked_sub(amount)
             .ok_or(SavingsGoalsError::Overflow)?;
         let new_amount = goal.current_amount;
+        Self::release_custody(&env, &goal, &goal.owner, amount - penalty);
+        if let Err(e) = Self::route_penalty(&env, &goal, penalty) {
+            Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
+            return Err(e);
+        }
 
-        goals.set(goal_id, goal);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        Self::save_goal(&env, &goal);
 
         Self::append_audit(&env, symbol_short!("withdraw"), &caller, true);
         env.events().publish(
             (symbol_short!("savings"), SavingsEvent::FundsWithdrawn),
-            (goal_id, caller, amount),
+            (goal_id, caller, amount, penalty),
         );
 
         Ok(new_amount)
     }
 
-    pub fn lock_goal(env: Env, caller: Address, goal_id: u32) -> bool {
+    /// Moves `amount` from one of the caller's goals to another goal in one
+    /// step, without the withdraw and deposit events.
+    ///
+    /// The source goal's lock policy applies as for `withdraw_from_goal`: a
+    /// hard lock blocks the transfer and a soft lock's penalty is taken out of
+    /// `amount`. The caller must own `from_goal` and may contribute to
+    /// `to_goal`; both goals must hold the same token. Emits `GoalTransfer`
+    /// with `(from_goal, to_goal, amount, penalty)`.
+    ///
+    /// The credit to `to_goal` counts as a deposit by `caller`, as in
+    /// `add_to_goal`: it is recorded as a contribution and can trigger the
+    /// goal's completion action.
+    ///
+    /// # Returns
+    /// `Ok(credited)` - The amount added to `to_goal`
+    pub fn transfer_between_goals(
+        env: Env,
+        caller: Address,
+        from_goal: u32,
+        to_goal: u32,
+        amount: i128,
+    ) -> Result<i128, SavingsGoalsError> {
         caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::LOCK);
+        Self::require_not_paused(&env, pause_functions::WITHDRAW);
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL);
+
+        if amount <= 0 || from_goal == to_goal {
+            Self::append_audit(&env, symbol_short!("transfer"), &caller, false);
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
+        let (mut source, target) = match (
+            Self::load_goal(&env, from_goal),
+            Self::load_goal(&env, to_goal),
+        ) {
+            (Some(source), Some(target)) => (source, target),
+            _ => {
+                Self::append_audit(&env, symbol_short!("transfer"), &caller, false);
+                return Err(SavingsGoalsError::GoalNotFound);
+            }
+        };
+        if source.owner != caller || !Self::can_contribute(&env, &target, &caller) {
+            Self::append_audit(&env, symbol_short!("transfer"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        if source.token != target.token {
+            Self::append_audit(&env, symbol_short!("transfer"), &caller, false);
+            return Err(SavingsGoalsError::TokenMismatch);
+        }
 
-        let mut goal = match goals.get(goal_id) {
-            Some(g) => g,
-            None => {
-                Self::append_audit(&env, symbol_short!("lock"), &caller, false);
-                panic!("Goal not found");
+        let penalty = match Self::withdrawal_penalty(&env, &source, amount) {
+            Ok(penalty) => penalty,
+            Err(e) => {
+                Self::append_audit(&env, symbol_short!("transfer"), &caller, false);
+                return Err(e);
             }
         };
+        if amount > source.current_amount {
+            Self::append_audit(&env, symbol_short!("transfer"), &caller, false);
+            return Err(SavingsGoalsError::InsufficientBalance);
+        }
 
-        if goal.owner != caller {
-            Self::append_audit(&env, symbol_short!("lock"), &caller, false);
-            panic!("Only the goal owner can lock this goal");
+        Self::sync_interest(&env, &source);
+        source.current_amount -= amount;
+        Self::save_goal(&env, &source);
+        if let Err(e) = Self::route_penalty(&env, &source, penalty) {
+            Self::append_audit(&env, symbol_short!("transfer"), &caller, false);
+            return Err(e);
         }
 
-        goal.locked = true;
-        goals.set(goal_id, goal);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        // Re-read: the penalty may have been credited to the target.
+        let credited = amount - penalty;
+        let mut target = Self::load_goal(&env, to_goal).unwrap();
+        Self::sync_interest(&env, &target);
+        let previously_completed = target.current_amount >= target.target_amount;
+        target.current_amount = target
+            .current_amount
+            .checked_add(credited)
+            .ok_or(SavingsGoalsError::Overflow)?;
+        Self::record_contribution(&env, to_goal, &caller, credited);
+        let was_completed = target.current_amount >= target.target_amount;
+        Self::save_goal(&env, &target);
+        Self::check_milestones(&env, &target);
 
-        Self::append_audit(&env, symbol_short!("lock"), &caller, true);
+        Self::append_audit(&env, symbol_short!("transfer"), &caller, true);
         env.events().publish(
-            (symbol_short!("savings"), SavingsEvent::GoalLocked),
-            (goal_id, caller),
+            (symbol_short!("savings"), SavingsEvent::GoalTransfer),
+            (from_goal, to_goal, amount, penalty),
         );
 
-        true
+        if was_completed && !previously_completed {
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::GoalCompleted),
+                (to_goal, caller),
+            );
+        }
+        if was_completed {
+            Self::apply_completion_action(&env, to_goal);
+        }
+
+        Ok(credited)
     }
 
-    pub fn unlock_goal(env: Env, caller: Address, goal_id: u32) -> bool {
+    /// Redistributes the combined balance of the caller's goals in
+    /// proportion to `weights`, returning the new balances in input order.
+    ///
+    /// Rounding dust goes to the goal with the largest weight. All goals must
+    /// be owned by the caller and hold the same token. Rebalancing never
+    /// charges penalties, so a goal whose lock policy would block or penalize
+    /// a withdrawal cannot be drawn down. Emits one `GoalsRebalanced` event
+    /// with `(caller, goal_ids, new_amounts)`.
+    ///
+    /// Goals brought up to their target emit `GoalCompleted`, but completion
+    /// actions are not applied: the requested balances are kept as they are.
+    pub fn rebalance_goals(
+        env: Env,
+        caller: Address,
+        weights: Vec<RebalanceWeight>,
+    ) -> Result<Vec<i128>, SavingsGoalsError> {
         caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::UNLOCK);
-        Self::extend_instance_ttl(&env);
-
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut goal = match goals.get(goal_id) {
-            Some(g) => g,
-            None => {
-                Self::append_audit(&env, symbol_short!("unlock"), &caller, false);
-                panic!("Goal not found");
-            }
-        };
+        Self::require_not_paused(&env, pause_functions::WITHDRAW);
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL);
 
-        if goal.owner != caller {
-            Self::append_audit(&env, symbol_short!("unlock"), &caller, false);
-            panic!("Only the goal owner can unlock this goal");
+        if weights.is_empty() || weights.len() > MAX_BATCH_SIZE {
+            Self::append_audit(&env, symbol_short!("rebalance"), &caller, false);
+            return Err(SavingsGoalsError::InvalidAmount);
         }
 
-        goal.locked = false;
-        goals.set(goal_id, goal);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        Self::extend_instance_ttl(&env);
+
+        let mut selected: Vec<SavingsGoal> = Vec::new(&env);
+        let mut goal_ids: Vec<u32> = Vec::new(&env);
+        let mut total: i128 = 0;
+        let mut weight_sum: i128 = 0;
+        let mut largest = 0u32;
+        for (i, item) in weights.iter().enumerate() {
+            let goal = match Self::load_goal(&env, item.goal_id) {
+                Some(g) => g,
+                None => {
+                    Self::append_audit(&env, symbol_short!("rebalance"), &caller, false);
+                    return Err(SavingsGoalsError::GoalNotFound);
+                }
+            };
+            if goal.owner != caller {
+                Self::append_audit(&env, symbol_short!("rebalance"), &caller, false);
+                return Err(SavingsGoalsError::Unauthorized);
+            }
+            if selected
+                .get(0)
+                .is_some_and(|first| first.token != goal.token)
+            {
+                Self::append_audit(&env, symbol_short!("rebalance"), &caller, false);
+                return Err(SavingsGoalsError::TokenMismatch);
+            }
+            if goal_ids.contains(item.goal_id) {
+                Self::append_audit(&env, symbol_short!("rebalance"), &caller, false);
+                return Err(SavingsGoalsError::InvalidAmount);
+            }
+            if item.weight > weights.get(largest).unwrap().weight {
+                largest = i as u32;
+            }
+            total = total
+                .checked_add(goal.current_amount)
+                .ok_or(SavingsGoalsError::Overflow)?;
+            weight_sum += item.weight as i128;
+            goal_ids.push_back(item.goal_id);
+            selected.push_back(goal);
+        }
+        if weight_sum == 0 {
+            Self::append_audit(&env, symbol_short!("rebalance"), &caller, false);
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+
+        let mut new_amounts: Vec<i128> = Vec::new(&env);
+        let mut assigned: i128 = 0;
+        for item in weights.iter() {
+            let share = total
+                .checked_mul(item.weight as i128)
+                .ok_or(SavingsGoalsError::Overflow)?
+                / weight_sum;
+            assigned += share;
+            new_amounts.push_back(share);
+        }
+        let dust = total - assigned;
+        new_amounts.set(largest, new_amounts.get(largest).unwrap() + dust);
+
+        for (i, mut goal) in selected.iter().enumerate() {
+            let new_amount = new_amounts.get(i as u32).unwrap();
+            if new_amount < goal.current_amount {
+                let decrease = goal.current_amount - new_amount;
+                match Self::withdrawal_penalty(&env, &goal, decrease) {
+                    Ok(0) => {}
+                    Ok(_) | Err(_) => {
+                        Self::append_audit(&env, symbol_short!("rebalance"), &caller, false);
+                        return Err(SavingsGoalsError::GoalLocked);
+                    }
+                }
+            }
+            Self::sync_interest(&env, &goal);
+            let previously_completed = goal.current_amount >= goal.target_amount;
+            goal.current_amount = new_amount;
+            Self::check_milestones(&env, &goal);
+            Self::save_goal(&env, &goal);
+            if goal.current_amount >= goal.target_amount && !previously_completed {
+                Self::publish_goal_completed(&env, &goal, &caller);
+            }
+        }
+
+        Self::append_audit(&env, symbol_short!("rebalance"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalsRebalanced),
+            (caller, goal_ids, new_amounts.clone()),
+        );
+
+        Ok(new_amounts)
+    }
+
+    /// Set how `deposit_to_owner_goals` treats `goal_id`. Higher priorities
+    /// are filled first; the weight only matters to the proportional strategy,
+    /// and a weight of 0 leaves the goal out of it.
+    pub fn set_goal_funding(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        priority: u32,
+        weight: u32,
+    ) -> bool {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let goal = match Self::load_goal(&env, goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(&env, symbol_short!("funding"), &caller, false);
+                panic!("Goal not found");
+            }
+        };
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("funding"), &caller, false);
+            panic!("Only the goal owner can set funding");
+        }
+
+        let key = (Self::STORAGE_FUNDING, goal_id);
+        env.storage()
+            .persistent()
+            .set(&key, &GoalFunding { priority, weight });
+        Self::extend_persistent_ttl(&env, &key);
+
+        Self::append_audit(&env, symbol_short!("funding"), &caller, true);
+        true
+    }
+
+    pub fn get_goal_funding(env: Env, goal_id: u32) -> GoalFunding {
+        env.storage()
+            .persistent()
+            .get(&(Self::STORAGE_FUNDING, goal_id))
+            .unwrap_or(Self::DEFAULT_FUNDING)
+    }
+
+    /// Divide `amount` across `owner`'s unfinished goals in `token` using
+    /// `strategy` and deposit each share as `add_to_goal` would. Returns what
+    /// each goal received, in the order the strategy filled them.
+    ///
+    /// Only goals holding `token` take part; `None` selects goals without a
+    /// token. No goal is taken past its target: whatever the goals cannot
+    /// absorb is not deposited and stays with the owner. Ties go to the goal
+    /// created first, as does proportional rounding dust among equal weights.
+    /// Emits `DepositAllocated` with `(owner, amount, allocated)`.
+    pub fn deposit_to_owner_goals(
+        env: Env,
+        owner: Address,
+        token: Option<Address>,
+        amount: i128,
+        strategy: FundingStrategy,
+    ) -> Result<Vec<GoalAllocation>, SavingsGoalsError> {
+        owner.require_auth();
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL);
+
+        if amount <= 0 {
+            Self::append_audit(&env, symbol_short!("deposit"), &owner, false);
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let mut open: Vec<SavingsGoal> = Vec::new(&env);
+        for goal_id in Self::owner_goal_ids(&env, &owner).iter() {
+            let goal = match Self::load_goal(&env, goal_id) {
+                Some(goal) => goal,
+                None => {
+                    Self::append_audit(&env, symbol_short!("deposit"), &owner, false);
+                    return Err(SavingsGoalsError::GoalNotFound);
+                }
+            };
+            if goal.token != token || goal.current_amount >= goal.target_amount {
+                continue;
+            }
+            open.push_back(goal);
+        }
+
+        let allocations = match Self::allocate(&env, &open, amount, strategy) {
+            Ok(allocations) => allocations,
+            Err(e) => {
+                Self::append_audit(&env, symbol_short!("deposit"), &owner, false);
+                return Err(e);
+            }
+        };
+
+        let mut allocated: i128 = 0;
+        for allocation in allocations.iter() {
+            let mut goal =
+                Self::load_goal(&env, allocation.goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+            Self::sync_interest(&env, &goal);
+            goal.current_amount += allocation.amount;
+            Self::take_custody(&env, &goal, &owner, allocation.amount);
+            Self::record_contribution(&env, goal.id, &owner, allocation.amount);
+            Self::save_goal(&env, &goal);
+            Self::check_milestones(&env, &goal);
+            allocated += allocation.amount;
+
+            let funds_event = FundsAddedEvent {
+                goal_id: goal.id,
+                amount: allocation.amount,
+                new_total: goal.current_amount,
+                timestamp: env.ledger().timestamp(),
+            };
+            env.events().publish((FUNDS_ADDED,), funds_event);
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::FundsAdded),
+                (goal.id, owner.clone(), allocation.amount),
+            );
+
+            if goal.current_amount >= goal.target_amount {
+                let completed_event = GoalCompletedEvent {
+                    goal_id: goal.id,
+                    name: goal.name.clone(),
+                    final_amount: goal.current_amount,
+                    timestamp: env.ledger().timestamp(),
+                };
+                env.events().publish((GOAL_COMPLETED,), completed_event);
+                env.events().publish(
+                    (symbol_short!("savings"), SavingsEvent::GoalCompleted),
+                    (goal.id, owner.clone()),
+                );
+                Self::apply_completion_action(&env, goal.id);
+            }
+        }
+
+        Self::append_audit(&env, symbol_short!("deposit"), &owner, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::DepositAllocated),
+            (owner, amount, allocated),
+        );
+
+        Ok(allocations)
+    }
+
+    pub fn lock_goal(env: Env, caller: Address, goal_id: u32) -> bool {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::LOCK);
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = match Self::load_goal(&env, goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(&env, symbol_short!("lock"), &caller, false);
+                panic!("Goal not found");
+            }
+        };
+
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("lock"), &caller, false);
+            panic!("Only the goal owner can lock this goal");
+        }
+
+        goal.locked = true;
+        Self::save_goal(&env, &goal);
+
+        Self::append_audit(&env, symbol_short!("lock"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalLocked),
+            (goal_id, caller),
+        );
+
+        true
+    }
+
+    pub fn unlock_goal(env: Env, caller: Address, goal_id: u32) -> bool {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::UNLOCK);
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = match Self::load_goal(&env, goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(&env, symbol_short!("unlock"), &caller, false);
+                panic!("Goal not found");
+            }
+        };
+
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("unlock"), &caller, false);
+            panic!("Only the goal owner can unlock this goal");
+        }
+
+        goal.locked = false;
+        Self::save_goal(&env, &goal);
 
         Self::append_audit(&env, symbol_short!("unlock"), &caller, true);
         env.events().publish(
@@ -940,12 +1594,7 @@ impl SavingsGoalContract {
     }
 
     pub fn get_goal(env: Env, goal_id: u32) -> Option<SavingsGoal> {
-        let goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-        goals.get(goal_id)
+        Self::load_goal(&env, goal_id)
     }
 
     // -----------------------------------------------------------------------
@@ -964,25 +1613,17 @@ impl SavingsGoalContract {
     /// `next_cursor == 0` means no more pages.
     pub fn get_goals(env: Env, owner: Address, cursor: u32, limit: u32) -> GoalPage {
         let limit = Self::clamp_limit(limit);
-        let goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
 
         let mut result = Vec::new(&env);
         let mut next_cursor: u32 = 0;
         let mut collected: u32 = 0;
 
-        for (id, goal) in goals.iter() {
+        for id in Self::owner_goal_ids(&env, &owner).iter() {
             if id <= cursor {
                 continue;
             }
-            if goal.owner != owner {
-                continue;
-            }
             if collected < limit {
-                result.push_back(goal);
+                result.push_back(Self::load_goal(&env, id).unwrap());
                 collected += 1;
                 next_cursor = id; // track last returned ID
             } else {
@@ -1005,32 +1646,278 @@ impl SavingsGoalContract {
     /// Backward-compatible: returns ALL goals for owner in one Vec.
     /// Prefer the paginated `get_goals` for production use.
     pub fn get_all_goals(env: Env, owner: Address) -> Vec<SavingsGoal> {
-        let goals: Map<u32, SavingsGoal> = env
+        let mut result = Vec::new(&env);
+        for id in Self::owner_goal_ids(&env, &owner).iter() {
+            result.push_back(Self::load_goal(&env, id).unwrap());
+        }
+        result
+    }
+
+    /// Replace the milestones on `goal_id`. Percentages must be 1–100 and
+    /// amounts positive; at most 10 milestones.
+    ///
+    /// A milestone that was already reached keeps its timestamp. Any newly set
+    /// milestone the goal has already passed is reached immediately, so each
+    /// one emits `MilestoneReached` exactly once.
+    pub fn set_goal_milestones(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        targets: Vec<MilestoneTarget>,
+    ) -> bool {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let goal = match Self::load_goal(&env, goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(&env, symbol_short!("milestone"), &caller, false);
+                panic!("Goal not found");
+            }
+        };
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("milestone"), &caller, false);
+            panic!("Only the goal owner can set milestones");
+        }
+        if targets.len() > MAX_MILESTONES {
+            Self::append_audit(&env, symbol_short!("milestone"), &caller, false);
+            panic!("Too many milestones");
+        }
+
+        let previous = Self::milestones(&env, goal_id);
+        let mut milestones = Vec::new(&env);
+        for target in targets.iter() {
+            let valid = match target {
+                MilestoneTarget::Percent(percent) => (1..=100).contains(&percent),
+                MilestoneTarget::Amount(amount) => amount > 0,
+            };
+            if !valid {
+                Self::append_audit(&env, symbol_short!("milestone"), &caller, false);
+                panic!("Invalid milestone");
+            }
+            let reached_at = previous
+                .iter()
+                .find(|m| m.target == target)
+                .and_then(|m| m.reached_at);
+            milestones.push_back(Milestone { target, reached_at });
+        }
+        Self::set_milestones(&env, goal_id, &milestones);
+        Self::check_milestones(&env, &goal);
+
+        Self::append_audit(&env, symbol_short!("milestone"), &caller, true);
+        true
+    }
+
+    /// Progress of `goal_id` towards its target, or `None` if it does not exist.
+    pub fn get_goal_progress(env: Env, goal_id: u32) -> Option<GoalProgress> {
+        let goal = Self::load_goal(&env, goal_id)?;
+
+        let percent = goal
+            .current_amount
+            .saturating_mul(100)
+            .checked_div(goal.target_amount)
+            .unwrap_or(0)
+            .clamp(0, u32::MAX as i128) as u32;
+        let remaining = (goal.target_amount - goal.current_amount).max(0);
+        let seconds_remaining = goal.target_date.saturating_sub(env.ledger().timestamp());
+        let rate = |period: u64| -> i128 {
+            let periods = seconds_remaining.div_ceil(period).max(1) as i128;
+            (remaining + periods - 1) / periods
+        };
+
+        Some(GoalProgress {
+            goal_id,
+            current_amount: goal.current_amount,
+            target_amount: goal.target_amount,
+            percent,
+            seconds_remaining,
+            required_daily: rate(SECONDS_PER_DAY),
+            required_weekly: rate(SECONDS_PER_WEEK),
+            milestones: Self::milestones(&env, goal_id),
+        })
+    }
+
+    // -----------------------------------------------------------------------
+    // Interest
+    // -----------------------------------------------------------------------
+
+    /// Hand the interest rate admin role, set by `init`, to `new_admin`.
+    /// Only the current admin can call this.
+    /// Hand the interest rate admin role to `new_admin`. The first admin is
+    /// appointed by the upgrade admin; after that only the current rate
+    /// admin can hand it on.
+    pub fn set_rate_admin(env: Env, caller: Address, new_admin: Address) {
+        caller.require_auth();
+        let current: Option<Address> = env.storage().instance().get(&Self::STORAGE_RATE_ADMIN);
+        let authority = current.or_else(|| Self::get_upgrade_admin(&env));
+        if authority != Some(caller) {
+            panic!("Unauthorized");
+        }
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_RATE_ADMIN, &new_admin);
+    }
+
+    /// Set the APR, in basis points, paid on goals holding `token`.
+    ///
+    /// Interest accrues lazily from the ledger timestamp: goals earn at the
+    /// old rate up to this call and at the new one afterwards. It is only
+    /// credited out of the reserve topped up through `fund_interest`.
+    pub fn set_interest_rate(env: Env, caller: Address, token: Address, apr_bps: u32) {
+        caller.require_auth();
+        let admin: Option<Address> = env.storage().instance().get(&Self::STORAGE_RATE_ADMIN);
+        if admin != Some(caller.clone()) {
+            Self::append_audit(&env, symbol_short!("set_rate"), &caller, false);
+            panic!("Unauthorized");
+        }
+        if apr_bps > MAX_APR_BPS {
+            Self::append_audit(&env, symbol_short!("set_rate"), &caller, false);
+            panic!("APR cannot exceed 10000 bps");
+        }
+        Self::extend_instance_ttl(&env);
+
+        let mut rates: Map<Address, InterestRate> = env
             .storage()
             .instance()
-            .get(&symbol_short!("GOALS"))
+            .get(&Self::STORAGE_RATES)
             .unwrap_or_else(|| Map::new(&env));
-        let mut result = Vec::new(&env);
-        for (_, goal) in goals.iter() {
-            if goal.owner == owner {
-                result.push_back(goal);
+        rates.set(
+            token.clone(),
+            InterestRate {
+                apr_bps,
+                index: Self::interest_index(&env, &token),
+                updated_at: env.ledger().timestamp(),
+            },
+        );
+        env.storage().instance().set(&Self::STORAGE_RATES, &rates);
+        Self::append_audit(&env, symbol_short!("set_rate"), &caller, true);
+    }
+
+    pub fn get_interest_rate(env: Env, token: Address) -> Option<InterestRate> {
+        env.storage()
+            .instance()
+            .get(&Self::STORAGE_RATES)
+            .and_then(|rates: Map<Address, InterestRate>| rates.get(token))
+    }
+
+    /// Move `amount` of `token` from `caller` into the contract's interest
+    /// reserve for `token`. Anyone may fund it. Returns the new reserve.
+    pub fn fund_interest(env: Env, caller: Address, token: Address, amount: i128) -> i128 {
+        caller.require_auth();
+        if amount <= 0 {
+            Self::append_audit(&env, symbol_short!("fund_int"), &caller, false);
+            panic!("Amount must be positive");
+        }
+        Self::extend_instance_ttl(&env);
+
+        TokenClient::new(&env, &token).transfer(&caller, &env.current_contract_address(), &amount);
+        let reserve = Self::get_interest_reserve(env.clone(), token.clone())
+            .checked_add(amount)
+            .expect("overflow");
+        Self::set_interest_reserve(&env, &token, reserve);
+
+        Self::append_audit(&env, symbol_short!("fund_int"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::InterestFunded),
+            (token, caller, amount),
+        );
+        reserve
+    }
+
+    /// Tokens held for interest on `token` goals and not yet credited.
+    pub fn get_interest_reserve(env: Env, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&(Self::STORAGE_RESERVE, token))
+            .unwrap_or(0)
+    }
+
+    /// Add the interest `goal_id` has accrued to its `current_amount`.
+    ///
+    /// Anyone may call this; the interest always goes to the goal. Interest
+    /// is drawn from the token's reserve, so at most the reserve is credited
+    /// and the rest stays pending. Returns the amount added. Bookkeeping-only
+    /// goals never earn interest.
+    pub fn accrue_interest(env: Env, goal_id: u32) -> i128 {
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = Self::load_goal(&env, goal_id).expect("Goal not found");
+        let token = match &goal.token {
+            Some(token) => token.clone(),
+            None => return 0,
+        };
+
+        Self::sync_interest(&env, &goal);
+        let mut account = match Self::stored_interest_account(&env, goal_id) {
+            Some(account) if account.pending > 0 => account,
+            _ => return 0,
+        };
+        let reserve = Self::get_interest_reserve(env.clone(), token.clone());
+        let interest = account.pending.min(reserve);
+        if interest == 0 {
+            return 0;
+        }
+        let previously_completed = goal.current_amount >= goal.target_amount;
+        goal.current_amount = goal.current_amount.checked_add(interest).expect("overflow");
+        Self::set_interest_reserve(&env, &token, reserve - interest);
+        Self::adjust_custody(&env, &token, interest);
+        account.claimed += interest;
+        account.pending -= interest;
+        Self::set_interest_account(&env, goal_id, &account);
+
+        Self::check_milestones(&env, &goal);
+        Self::save_goal(&env, &goal);
+
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::InterestAccrued),
+            (goal_id, interest),
+        );
+
+        if goal.current_amount >= goal.target_amount {
+            if !previously_completed {
+                Self::publish_goal_completed(&env, &goal, &goal.owner);
             }
+            Self::apply_completion_action(&env, goal_id);
         }
-        result
+        interest
+    }
+
+    /// Interest earned by `goal_id` as of now, or `None` if it does not exist.
+    pub fn get_goal_interest(env: Env, goal_id: u32) -> Option<GoalInterest> {
+        let goal = Self::load_goal(&env, goal_id)?;
+        let account = Self::interest_account(&env, &goal);
+        let apr_bps = goal
+            .token
+            .and_then(|token| Self::get_interest_rate(env.clone(), token))
+            .map_or(0, |rate| rate.apr_bps);
+        Some(GoalInterest {
+            goal_id,
+            apr_bps,
+            pending: account.pending,
+            claimed: account.claimed,
+        })
     }
 
     pub fn is_goal_completed(env: Env, goal_id: u32) -> bool {
-        let storage = env.storage().instance();
-        let goals: Map<u32, SavingsGoal> = storage
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or(Map::new(&env));
-        if let Some(goal) = goals.get(goal_id) {
+        if let Some(goal) = Self::load_goal(&env, goal_id) {
             goal.current_amount >= goal.target_amount
         } else {
             false
         }
     }
 
+    /// Compare the contract's `token` balance with what its goals hold.
+    pub fn get_solvency(env: Env, token: Address) -> SolvencyReport {
+        let liabilities = Self::custody_total(&env, &token);
+        let balance = TokenClient::new(&env, &token).balance(&env.current_contract_address());
+        SolvencyReport {
+            token,
+            balance,
+            liabilities,
+            solvent: balance >= liabilities,
+        }
+    }
+
     // -----------------------------------------------------------------------
     // Snapshot, audit, schedule
     // -----------------------------------------------------------------------
@@ -1046,28 +1933,26 @@ impl SavingsGoalContract {
 
     pub fn export_snapshot(env: Env, caller: Address) -> GoalsExportSnapshot {
         caller.require_auth();
-        let goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
         let next_id = env
             .storage()
             .instance()
             .get(&symbol_short!("NEXT_ID"))
             .unwrap_or(0u32);
         let mut list = Vec::new(&env);
+        let mut states = Vec::new(&env);
         for i in 1..=next_id {
-            if let Some(g) = goals.get(i) {
+            if let Some(g) = Self::load_goal(&env, i) {
                 list.push_back(g);
+                states.push_back(Self::goal_state(&env, i));
             }
         }
-        let checksum = Self::compute_goals_checksum(SNAPSHOT_VERSION, next_id, &list);
+        let checksum = Self::compute_goals_checksum(&env, next_id, &list, &states);
         GoalsExportSnapshot {
             version: SNAPSHOT_VERSION,
             checksum,
             next_id,
             goals: list,
+            states,
         }
     }
 
@@ -1085,32 +1970,66 @@ impl SavingsGoalContract {
             panic!("Unsupported snapshot version");
         }
         let expected =
-            Self::compute_goals_checksum(snapshot.version, snapshot.next_id, &snapshot.goals);
+            Self::compute_goals_checksum(&env, snapshot.next_id, &snapshot.goals, &snapshot.states);
         if snapshot.checksum != expected {
             Self::append_audit(&env, symbol_short!("import"), &caller, false);
             panic!("Snapshot checksum mismatch");
         }
+        let aligned = snapshot.states.len() == snapshot.goals.len()
+            && snapshot
+                .goals
+                .iter()
+                .zip(snapshot.states.iter())
+                .all(|(goal, state)| goal.id == state.goal_id);
+        if !aligned {
+            Self::append_audit(&env, symbol_short!("import"), &caller, false);
+            panic!("Snapshot goal states do not match its goals");
+        }
+        // Importing replaces every goal, so it must neither orphan tokens the
+        // contract holds nor create balances no tokens back.
+        let custody: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&Self::STORAGE_CUSTODY)
+            .unwrap_or_else(|| Map::new(&env));
+        if custody.values().iter().any(|total| total != 0)
+            || snapshot
+                .goals
+                .iter()
+                .any(|g| g.token.is_some() && g.current_amount != 0)
+        {
+            Self::append_audit(&env, symbol_short!("import"), &caller, false);
+            panic!("Cannot import over custodial balances");
+        }
 
         Self::extend_instance_ttl(&env);
-        let mut goals: Map<u32, SavingsGoal> = Map::new(&env);
-        let mut owner_goal_ids: Map<Address, Vec<u32>> = Map::new(&env);
+        let previous_next_id: u32 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_ID"))
+            .unwrap_or(0);
+        let storage = env.storage().persistent();
+        for id in 1..=previous_next_id {
+            if let Some(g) = Self::load_goal(&env, id) {
+                storage.remove(&(Self::STORAGE_GOALS, id));
+                storage.remove(&(Self::STORAGE_OWNER_GOAL_IDS, g.owner));
+                storage.remove(&(Self::STORAGE_FUNDING, id));
+                storage.remove(&(Self::STORAGE_CONTRIBUTIONS, id));
+                storage.remove(&(Self::STORAGE_MILESTONES, id));
+                storage.remove(&(Self::STORAGE_INTEREST, id));
+                storage.remove(&(Self::STORAGE_COMPLETION, id));
+            }
+        }
         for g in snapshot.goals.iter() {
-            goals.set(g.id, g.clone());
-            let mut ids = owner_goal_ids
-                .get(g.owner.clone())
-                .unwrap_or_else(|| Vec::new(&env));
-            ids.push_back(g.id);
-            owner_goal_ids.set(g.owner.clone(), ids);
+            Self::save_goal(&env, &g);
+            Self::append_owner_goal_id(&env, &g.owner, g.id);
+        }
+        for state in snapshot.states.iter() {
+            Self::restore_goal_state(&env, &state);
         }
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
         env.storage()
             .instance()
             .set(&symbol_short!("NEXT_ID"), &snapshot.next_id);
-        env.storage()
-            .instance()
-            .set(&Self::STORAGE_OWNER_GOAL_IDS, &owner_goal_ids);
 
         Self::increment_nonce(&env, &caller);
         Self::append_audit(&env, symbol_short!("import"), &caller, true);
@@ -1135,91 +2054,677 @@ impl SavingsGoalContract {
         out
     }
 
-    fn require_nonce(env: &Env, address: &Address, expected: u64) {
-        let current = Self::get_nonce(env.clone(), address.clone());
-        if expected != current {
-            panic!("Invalid nonce: expected {}, got {}", current, expected);
-        }
+    fn require_nonce(env: &Env, address: &Address, expected: u64) {
+        let current = Self::get_nonce(env.clone(), address.clone());
+        if expected != current {
+            panic!("Invalid nonce: expected {}, got {}", current, expected);
+        }
+    }
+
+    fn increment_nonce(env: &Env, address: &Address) {
+        let current = Self::get_nonce(env.clone(), address.clone());
+        let next = current.checked_add(1).expect("nonce overflow");
+        let mut nonces: Map<Address, u64> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NONCES"))
+            .unwrap_or_else(|| Map::new(env));
+        nonces.set(address.clone(), next);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NONCES"), &nonces);
+    }
+
+    /// SHA-256 over the XDR encoding of `(next_id, goals, states)`; matches
+    /// `data_migration::ExportSnapshot::compute_checksum`.
+    fn compute_goals_checksum(
+        env: &Env,
+        next_id: u32,
+        goals: &Vec<SavingsGoal>,
+        states: &Vec<GoalState>,
+    ) -> BytesN<32> {
+        let payload = (next_id, goals.clone(), states.clone()).to_xdr(env);
+        env.crypto().sha256(&payload).to_bytes()
+    }
+
+    fn goal_state(env: &Env, goal_id: u32) -> GoalState {
+        GoalState {
+            goal_id,
+            funding: Self::get_goal_funding(env.clone(), goal_id),
+            contributions: Self::contributions(env, goal_id),
+            milestones: Self::milestones(env, goal_id),
+            interest: Self::stored_interest_account(env, goal_id).unwrap_or(Self::EMPTY_INTEREST),
+            completion: Self::get_completion_action(env.clone(), goal_id),
+        }
+    }
+
+    /// Write back what `goal_state` read. Empty lists and defaults are not
+    /// stored, like on a goal that never had them set.
+    fn restore_goal_state(env: &Env, state: &GoalState) {
+        let goal_id = state.goal_id;
+        if state.funding != Self::DEFAULT_FUNDING {
+            let key = (Self::STORAGE_FUNDING, goal_id);
+            env.storage().persistent().set(&key, &state.funding);
+            Self::extend_persistent_ttl(env, &key);
+        }
+        if !state.contributions.is_empty() {
+            Self::set_contributions(env, goal_id, &state.contributions);
+        }
+        if !state.milestones.is_empty() {
+            Self::set_milestones(env, goal_id, &state.milestones);
+        }
+        if state.interest != Self::EMPTY_INTEREST {
+            Self::set_interest_account(env, goal_id, &state.interest);
+        }
+        if state.completion != CompletionAction::None {
+            let key = (Self::STORAGE_COMPLETION, goal_id);
+            env.storage().persistent().set(&key, &state.completion);
+            Self::extend_persistent_ttl(env, &key);
+        }
+    }
+
+    fn append_audit(env: &Env, operation: Symbol, caller: &Address, success: bool) {
+        let timestamp = env.ledger().timestamp();
+        let mut log: Vec<AuditEntry> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("AUDIT"))
+            .unwrap_or_else(|| Vec::new(env));
+        if log.len() >= MAX_AUDIT_ENTRIES {
+            let mut new_log = Vec::new(env);
+            for i in 1..log.len() {
+                if let Some(entry) = log.get(i) {
+                    new_log.push_back(entry);
+                }
+            }
+            log = new_log;
+        }
+        log.push_back(AuditEntry {
+            operation,
+            caller: caller.clone(),
+            timestamp,
+            success,
+        });
+        env.storage().instance().set(&symbol_short!("AUDIT"), &log);
+    }
+
+    fn load_goal(env: &Env, goal_id: u32) -> Option<SavingsGoal> {
+        env.storage()
+            .persistent()
+            .get(&(Self::STORAGE_GOALS, goal_id))
+    }
+
+    fn save_goal(env: &Env, goal: &SavingsGoal) {
+        let key = (Self::STORAGE_GOALS, goal.id);
+        env.storage().persistent().set(&key, goal);
+        Self::extend_persistent_ttl(env, &key);
+    }
+
+    fn load_schedule(env: &Env, schedule_id: u32) -> Option<SavingsSchedule> {
+        env.storage()
+            .persistent()
+            .get(&(Self::STORAGE_SCHEDULES, schedule_id))
+    }
+
+    fn save_schedule(env: &Env, schedule: &SavingsSchedule) {
+        let key = (Self::STORAGE_SCHEDULES, schedule.id);
+        env.storage().persistent().set(&key, schedule);
+        Self::extend_persistent_ttl(env, &key);
+    }
+
+    /// IDs of `owner`'s goals, ascending.
+    fn owner_goal_ids(env: &Env, owner: &Address) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&(Self::STORAGE_OWNER_GOAL_IDS, owner.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn append_owner_goal_id(env: &Env, owner: &Address, goal_id: u32) {
+        Self::insert_index_id(env, &(Self::STORAGE_OWNER_GOAL_IDS, owner.clone()), goal_id);
+    }
+
+    /// IDs of `owner`'s schedules, ascending.
+    fn owner_schedule_ids(env: &Env, owner: &Address) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&(Self::STORAGE_OWNER_SCHEDULE_IDS, owner.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn append_owner_schedule_id(env: &Env, owner: &Address, schedule_id: u32) {
+        Self::insert_index_id(
+            env,
+            &(Self::STORAGE_OWNER_SCHEDULE_IDS, owner.clone()),
+            schedule_id,
+        );
+    }
+
+    /// Insert `id` into the ascending ID list under `key`, keeping it sorted
+    /// so `migrate_storage` can merge older IDs into a live index.
+    fn insert_index_id(env: &Env, key: &(Symbol, Address), id: u32) {
+        let storage = env.storage().persistent();
+        let mut ids: Vec<u32> = storage.get(key).unwrap_or_else(|| Vec::new(env));
+        if let Err(position) = ids.binary_search(id) {
+            ids.insert(position, id);
+            storage.set(key, &ids);
+        }
+        Self::extend_persistent_ttl(env, key);
+    }
+
+    /// Extend the TTL of a goal, schedule or owner index entry.
+    fn extend_persistent_ttl<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+        env.storage().persistent().extend_ttl(
+            key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    /// Extend the TTL of instance storage
+    fn extend_instance_ttl(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Move `amount` of a custodial goal's token from `from` into the
+    /// contract. No-op for bookkeeping-only goals.
+    fn take_custody(env: &Env, goal: &SavingsGoal, from: &Address, amount: i128) {
+        if let Some(token) = &goal.token {
+            TokenClient::new(env, token).transfer(from, &env.current_contract_address(), &amount);
+            Self::adjust_custody(env, token, amount);
+        }
+    }
+
+    /// Pay `amount` of a custodial goal's token out to `to`. No-op for
+    /// bookkeeping-only goals.
+    fn release_custody(env: &Env, goal: &SavingsGoal, to: &Address, amount: i128) {
+        if amount == 0 {
+            return;
+        }
+        if let Some(token) = &goal.token {
+            TokenClient::new(env, token).transfer(&env.current_contract_address(), to, &amount);
+            Self::adjust_custody(env, token, -amount);
+        }
+    }
+
+    /// Penalty owed on withdrawing `amount` from `goal` now, or `GoalLocked`
+    /// if its policy forbids the withdrawal.
+    fn withdrawal_penalty(
+        env: &Env,
+        goal: &SavingsGoal,
+        amount: i128,
+    ) -> Result<i128, SavingsGoalsError> {
+        let now = env.ledger().timestamp();
+        let before_unlock = goal.unlock_date.is_some_and(|date| now < date);
+        match &goal.lock_policy {
+            LockPolicy::Hard => {
+                if goal.locked || before_unlock {
+                    return Err(SavingsGoalsError::GoalLocked);
+                }
+                Ok(0)
+            }
+            LockPolicy::Soft(penalty_bps, _) => {
+                if before_unlock || now < goal.target_date {
+                    let penalty = amount
+                        .checked_mul(*penalty_bps as i128)
+                        .ok_or(SavingsGoalsError::Overflow)?;
+                    return Ok(penalty / MAX_PENALTY_BPS as i128);
+                }
+                Ok(0)
+            }
+            LockPolicy::None => Ok(0),
+        }
+    }
+
+    /// Send `penalty` taken from `goal` to its soft lock's destination.
+    fn route_penalty(
+        env: &Env,
+        goal: &SavingsGoal,
+        penalty: i128,
+    ) -> Result<(), SavingsGoalsError> {
+        if penalty == 0 {
+            return Ok(());
+        }
+        if let LockPolicy::Soft(_, destination) = &goal.lock_policy {
+            match destination {
+                PenaltyDestination::Recipient(recipient) => {
+                    Self::release_custody(env, goal, recipient, penalty);
+                }
+                PenaltyDestination::Goal(target_id) => {
+                    let mut target =
+                        Self::load_goal(env, *target_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+                    Self::sync_interest(env, &target);
+                    let previously_completed = target.current_amount >= target.target_amount;
+                    target.current_amount = target
+                        .current_amount
+                        .checked_add(penalty)
+                        .ok_or(SavingsGoalsError::Overflow)?;
+                    Self::check_milestones(env, &target);
+                    Self::save_goal(env, &target);
+
+                    if target.current_amount >= target.target_amount {
+                        if !previously_completed {
+                            Self::publish_goal_completed(env, &target, &goal.owner);
+                        }
+                        Self::apply_completion_action(env, *target_id);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Pull a scheduled deposit from the owner through their allowance.
+    /// Returns false, without transferring, when the owner's balance or
+    /// allowance does not cover it.
+    fn collect_scheduled(env: &Env, goal: &SavingsGoal, amount: i128) -> bool {
+        let token = match &goal.token {
+            Some(token) => token,
+            None => return true,
+        };
+        let client = TokenClient::new(env, token);
+        let this = env.current_contract_address();
+        if client.balance(&goal.owner) < amount || client.allowance(&goal.owner, &this) < amount {
+            return false;
+        }
+        client.transfer_from(&this, &goal.owner, &this, &amount);
+        Self::adjust_custody(env, token, amount);
+        true
+    }
+
+    fn adjust_custody(env: &Env, token: &Address, delta: i128) {
+        let mut custody: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&Self::STORAGE_CUSTODY)
+            .unwrap_or_else(|| Map::new(env));
+        let total = custody
+            .get(token.clone())
+            .unwrap_or(0)
+            .checked_add(delta)
+            .expect("overflow");
+        custody.set(token.clone(), total);
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_CUSTODY, &custody);
+    }
+
+    fn require_goal_owner(env: &Env, caller: &Address, goal_id: u32, operation: Symbol) {
+        let goal = match Self::load_goal(env, goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(env, operation, caller, false);
+                panic!("Goal not found");
+            }
+        };
+        if goal.owner != *caller {
+            Self::append_audit(env, operation, caller, false);
+            panic!("Only the goal owner can manage contributors");
+        }
+    }
+
+    fn can_contribute(env: &Env, goal: &SavingsGoal, caller: &Address) -> bool {
+        goal.owner == *caller
+            || Self::contributions(env, goal.id)
+                .iter()
+                .any(|entry| entry.contributor == *caller && entry.approved)
+    }
+
+    fn contributions(env: &Env, goal_id: u32) -> Vec<GoalContribution> {
+        env.storage()
+            .persistent()
+            .get(&(Self::STORAGE_CONTRIBUTIONS, goal_id))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn set_contributions(env: &Env, goal_id: u32, entries: &Vec<GoalContribution>) {
+        let key = (Self::STORAGE_CONTRIBUTIONS, goal_id);
+        env.storage().persistent().set(&key, entries);
+        Self::extend_persistent_ttl(env, &key);
+    }
+
+    /// Add `amount` to `contributor`'s deposit total for `goal_id`.
+    fn record_contribution(env: &Env, goal_id: u32, contributor: &Address, amount: i128) {
+        let mut entries = Self::contributions(env, goal_id);
+        match entries
+            .iter()
+            .position(|entry| entry.contributor == *contributor)
+        {
+            Some(i) => {
+                let mut entry = entries.get(i as u32).unwrap();
+                entry.total = entry.total.checked_add(amount).expect("overflow");
+                entries.set(i as u32, entry);
+            }
+            None => entries.push_back(GoalContribution {
+                contributor: contributor.clone(),
+                approved: true,
+                total: amount,
+            }),
+        }
+        Self::set_contributions(env, goal_id, &entries);
+    }
+
+    fn milestones(env: &Env, goal_id: u32) -> Vec<Milestone> {
+        env.storage()
+            .persistent()
+            .get(&(Self::STORAGE_MILESTONES, goal_id))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn set_milestones(env: &Env, goal_id: u32, milestones: &Vec<Milestone>) {
+        let key = (Self::STORAGE_MILESTONES, goal_id);
+        env.storage().persistent().set(&key, milestones);
+        Self::extend_persistent_ttl(env, &key);
+    }
+
+    /// Mark every milestone `goal` has newly passed as reached and emit one
+    /// `MilestoneReached` event for each.
+    fn check_milestones(env: &Env, goal: &SavingsGoal) {
+        let mut milestones = Self::milestones(env, goal.id);
+        let mut changed = false;
+        for i in 0..milestones.len() {
+            let mut milestone = milestones.get(i).unwrap();
+            if milestone.reached_at.is_some() {
+                continue;
+            }
+            let threshold = match milestone.target {
+                MilestoneTarget::Percent(percent) => {
+                    goal.target_amount.saturating_mul(percent as i128) / 100
+                }
+                MilestoneTarget::Amount(amount) => amount,
+            };
+            if goal.current_amount < threshold {
+                continue;
+            }
+            milestone.reached_at = Some(env.ledger().timestamp());
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::MilestoneReached),
+                (goal.id, milestone.target.clone(), goal.current_amount),
+            );
+            milestones.set(i, milestone);
+            changed = true;
+        }
+        if changed {
+            Self::set_milestones(env, goal.id, &milestones);
+        }
+    }
+
+    /// The token's interest index as of now.
+    fn interest_index(env: &Env, token: &Address) -> i128 {
+        let rate = match Self::get_interest_rate(env.clone(), token.clone()) {
+            Some(rate) => rate,
+            None => return 0,
+        };
+        let elapsed = env.ledger().timestamp().saturating_sub(rate.updated_at) as i128;
+        let growth = (rate.apr_bps as i128)
+            .checked_mul(INTEREST_INDEX_SCALE)
+            .and_then(|v| v.checked_mul(elapsed))
+            .expect("overflow")
+            / (MAX_APR_BPS as i128 * SECONDS_PER_YEAR as i128);
+        rate.index.checked_add(growth).expect("overflow")
+    }
+
+    fn set_interest_reserve(env: &Env, token: &Address, reserve: i128) {
+        let key = (Self::STORAGE_RESERVE, token.clone());
+        env.storage().persistent().set(&key, &reserve);
+        Self::extend_persistent_ttl(env, &key);
     }
 
-    fn increment_nonce(env: &Env, address: &Address) {
-        let current = Self::get_nonce(env.clone(), address.clone());
-        let next = current.checked_add(1).expect("nonce overflow");
-        let mut nonces: Map<Address, u64> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("NONCES"))
-            .unwrap_or_else(|| Map::new(env));
-        nonces.set(address.clone(), next);
+    fn stored_interest_account(env: &Env, goal_id: u32) -> Option<InterestAccount> {
         env.storage()
-            .instance()
-            .set(&symbol_short!("NONCES"), &nonces);
+            .persistent()
+            .get(&(Self::STORAGE_INTEREST, goal_id))
     }
 
-    fn compute_goals_checksum(version: u32, next_id: u32, goals: &Vec<SavingsGoal>) -> u64 {
-        let mut c = version as u64 + next_id as u64;
-        for i in 0..goals.len() {
-            if let Some(g) = goals.get(i) {
-                c = c
-                    .wrapping_add(g.id as u64)
-                    .wrapping_add(g.target_amount as u64)
-                    .wrapping_add(g.current_amount as u64);
-            }
+    fn set_interest_account(env: &Env, goal_id: u32, account: &InterestAccount) {
+        let key = (Self::STORAGE_INTEREST, goal_id);
+        env.storage().persistent().set(&key, account);
+        Self::extend_persistent_ttl(env, &key);
+    }
+
+    /// `goal`'s interest account brought up to date, without saving it.
+    fn interest_account(env: &Env, goal: &SavingsGoal) -> InterestAccount {
+        let mut account =
+            Self::stored_interest_account(env, goal.id).unwrap_or(Self::EMPTY_INTEREST);
+        if let Some(token) = &goal.token {
+            let index = Self::interest_index(env, token);
+            let earned = Self::scaled_interest(goal.current_amount, index - account.index);
+            account.pending = account.pending.saturating_add(earned);
+            account.index = index;
         }
-        c.wrapping_mul(31)
+        account
     }
 
-    fn append_audit(env: &Env, operation: Symbol, caller: &Address, success: bool) {
-        let timestamp = env.ledger().timestamp();
-        let mut log: Vec<AuditEntry> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("AUDIT"))
-            .unwrap_or_else(|| Vec::new(env));
-        if log.len() >= MAX_AUDIT_ENTRIES {
-            let mut new_log = Vec::new(env);
-            for i in 1..log.len() {
-                if let Some(entry) = log.get(i) {
-                    new_log.push_back(entry);
+    /// `amount * index_delta / INTEREST_INDEX_SCALE`, rounded down, for
+    /// non-negative inputs. Both factors are split at the scale so no
+    /// intermediate product overflows; a result past `i128::MAX` saturates
+    /// instead of trapping, so a large goal can never get stuck.
+    fn scaled_interest(amount: i128, index_delta: i128) -> i128 {
+        let (whole, fraction) = (
+            index_delta / INTEREST_INDEX_SCALE,
+            index_delta % INTEREST_INDEX_SCALE,
+        );
+        let (high, low) = (amount / INTEREST_INDEX_SCALE, amount % INTEREST_INDEX_SCALE);
+        amount
+            .saturating_mul(whole)
+            .saturating_add(high.saturating_mul(fraction))
+            .saturating_add(low * fraction / INTEREST_INDEX_SCALE)
+    }
+
+    /// Accrue `goal`'s interest at its current balance. Call before changing
+    /// `current_amount`.
+    fn sync_interest(env: &Env, goal: &SavingsGoal) {
+        if goal.token.is_none() {
+            return;
+        }
+        let account = Self::interest_account(env, goal);
+        Self::set_interest_account(env, goal.id, &account);
+    }
+
+    /// Split `amount` across `goals`, all below target, without taking any
+    /// of them past it. Only goals that receive something are returned.
+    fn allocate(
+        env: &Env,
+        goals: &Vec<SavingsGoal>,
+        amount: i128,
+        strategy: FundingStrategy,
+    ) -> Result<Vec<GoalAllocation>, SavingsGoalsError> {
+        // (sort key, goal_id, still needed, weight), ordered by key. Goals
+        // arrive in ID order and equal keys keep it.
+        let mut slots: Vec<(u64, u32, i128, u32)> = Vec::new(env);
+        for goal in goals.iter() {
+            let funding = Self::get_goal_funding(env.clone(), goal.id);
+            let key = match strategy {
+                FundingStrategy::Priority => u64::from(u32::MAX - funding.priority),
+                FundingStrategy::Proportional => u64::from(u32::MAX - funding.weight),
+                FundingStrategy::ClosestDeadline => goal.target_date,
+            };
+            let position = slots
+                .iter()
+                .position(|slot| slot.0 > key)
+                .map_or(slots.len(), |i| i as u32);
+            slots.insert(
+                position,
+                (
+                    key,
+                    goal.id,
+                    goal.target_amount - goal.current_amount,
+                    funding.weight,
+                ),
+            );
+        }
+
+        let mut given: Vec<i128> = Vec::new(env);
+        for _ in slots.iter() {
+            given.push_back(0);
+        }
+        let mut left = amount;
+        if strategy == FundingStrategy::Proportional {
+            // Share out by weight, re-splitting what capped goals could not
+            // take, until only rounding dust is left.
+            while left > 0 {
+                let mut weight_sum: i128 = 0;
+                for (i, slot) in slots.iter().enumerate() {
+                    if given.get(i as u32).unwrap() < slot.2 {
+                        weight_sum += slot.3 as i128;
+                    }
+                }
+                if weight_sum == 0 {
+                    break;
+                }
+                let mut round: i128 = 0;
+                for (i, slot) in slots.iter().enumerate() {
+                    let so_far = given.get(i as u32).unwrap();
+                    let share = left
+                        .checked_mul(slot.3 as i128)
+                        .ok_or(SavingsGoalsError::Overflow)?
+                        / weight_sum;
+                    let take = share.min(slot.2 - so_far);
+                    given.set(i as u32, so_far + take);
+                    round += take;
+                }
+                if round == 0 {
+                    break;
                 }
+                left -= round;
             }
-            log = new_log;
         }
-        log.push_back(AuditEntry {
-            operation,
-            caller: caller.clone(),
-            timestamp,
-            success,
-        });
-        env.storage().instance().set(&symbol_short!("AUDIT"), &log);
+        // Fill in order. For the proportional strategy this only hands out
+        // the dust, largest weight first.
+        for (i, slot) in slots.iter().enumerate() {
+            if left == 0 {
+                break;
+            }
+            if strategy == FundingStrategy::Proportional && slot.3 == 0 {
+                continue;
+            }
+            let so_far = given.get(i as u32).unwrap();
+            let take = left.min(slot.2 - so_far);
+            given.set(i as u32, so_far + take);
+            left -= take;
+        }
+
+        let mut allocations = Vec::new(env);
+        for (i, slot) in slots.iter().enumerate() {
+            let amount = given.get(i as u32).unwrap();
+            if amount > 0 {
+                allocations.push_back(GoalAllocation {
+                    goal_id: slot.1,
+                    amount,
+                });
+            }
+        }
+        Ok(allocations)
     }
 
-    #[allow(dead_code)]
-    fn get_owner_goal_ids_map(env: &Env) -> Option<Map<Address, Vec<u32>>> {
-        env.storage().instance().get(&Self::STORAGE_OWNER_GOAL_IDS)
+    fn record_schedule_failure(
+        env: &Env,
+        schedule_id: u32,
+        reason: ScheduleFailureReason,
+    ) -> ScheduleFailure {
+        let entry = ScheduleFailure {
+            schedule_id,
+            reason,
+            timestamp: env.ledger().timestamp(),
+        };
+        let key = (Self::STORAGE_SCHEDULE_FAILURES, schedule_id);
+        env.storage().persistent().set(&key, &entry);
+        Self::extend_persistent_ttl(env, &key);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::ScheduleFailed),
+            (schedule_id, reason),
+        );
+        entry
     }
 
-    fn append_owner_goal_id(env: &Env, owner: &Address, goal_id: u32) {
-        let mut owner_goal_ids: Map<Address, Vec<u32>> = env
-            .storage()
-            .instance()
-            .get(&Self::STORAGE_OWNER_GOAL_IDS)
-            .unwrap_or_else(|| Map::new(env));
-        let mut ids = owner_goal_ids
-            .get(owner.clone())
-            .unwrap_or_else(|| Vec::new(env));
-        ids.push_back(goal_id);
-        owner_goal_ids.set(owner.clone(), ids);
-        env.storage()
-            .instance()
-            .set(&Self::STORAGE_OWNER_GOAL_IDS, &owner_goal_ids);
+    /// Publish both `GoalCompleted` events for `goal`, which `by` just took
+    /// to its target.
+    fn publish_goal_completed(env: &Env, goal: &SavingsGoal, by: &Address) {
+        let completed_event = GoalCompletedEvent {
+            goal_id: goal.id,
+            name: goal.name.clone(),
+            final_amount: goal.current_amount,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.events().publish((GOAL_COMPLETED,), completed_event);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalCompleted),
+            (goal.id, by.clone()),
+        );
     }
 
-    /// Extend the TTL of instance storage
-    fn extend_instance_ttl(env: &Env) {
+    /// Apply `goal_id`'s completion action if it has reached its target.
+    /// Returns true if it paused the goal's schedules.
+    fn apply_completion_action(env: &Env, goal_id: u32) -> bool {
+        let mut goal = Self::load_goal(env, goal_id).unwrap();
+        let overflow = goal.current_amount - goal.target_amount;
+        if overflow < 0 {
+            return false;
+        }
+        match Self::get_completion_action(env.clone(), goal_id) {
+            CompletionAction::None => false,
+            CompletionAction::SweepTo(next_id) => {
+                let mut next = match Self::load_goal(env, next_id) {
+                    Some(next) if overflow > 0 => next,
+                    _ => return false,
+                };
+                Self::sync_interest(env, &goal);
+                Self::sync_interest(env, &next);
+                goal.current_amount = goal.target_amount;
+                next.current_amount = next.current_amount.checked_add(overflow).expect("overflow");
+                Self::check_milestones(env, &next);
+                Self::save_goal(env, &goal);
+                Self::save_goal(env, &next);
+                env.events().publish(
+                    (symbol_short!("savings"), SavingsEvent::OverflowSwept),
+                    (goal_id, next_id, overflow),
+                );
+                false
+            }
+            CompletionAction::ReturnExcess => {
+                if overflow == 0 {
+                    return false;
+                }
+                Self::sync_interest(env, &goal);
+                goal.current_amount = goal.target_amount;
+                Self::release_custody(env, &goal, &goal.owner, overflow);
+                Self::save_goal(env, &goal);
+                env.events().publish(
+                    (symbol_short!("savings"), SavingsEvent::ExcessReturned),
+                    (goal_id, overflow),
+                );
+                false
+            }
+            CompletionAction::PauseSchedules => {
+                // Only the goal owner can schedule deposits into it.
+                let mut paused = 0u32;
+                for schedule_id in Self::owner_schedule_ids(env, &goal.owner).iter() {
+                    let mut schedule = Self::load_schedule(env, schedule_id).unwrap();
+                    if schedule.goal_id == goal_id && schedule.active {
+                        schedule.active = false;
+                        Self::save_schedule(env, &schedule);
+                        paused += 1;
+                    }
+                }
+                if paused > 0 {
+                    env.events().publish(
+                        (symbol_short!("savings"), SavingsEvent::SchedulesPaused),
+                        (goal_id, paused),
+                    );
+                }
+                paused > 0
+            }
+        }
+    }
+
+    fn custody_total(env: &Env, token: &Address) -> i128 {
         env.storage()
             .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+            .get(&Self::STORAGE_CUSTODY)
+            .and_then(|custody: Map<Address, i128>| custody.get(token.clone()))
+            .unwrap_or(0)
     }
 
     /// Set time-lock on a goal
@@ -1227,13 +2732,7 @@ impl SavingsGoalContract {
         caller.require_auth();
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut goal = match goals.get(goal_id) {
+        let mut goal = match Self::load_goal(&env, goal_id) {
             Some(g) => g,
             None => {
                 Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
@@ -1253,15 +2752,206 @@ impl SavingsGoalContract {
         }
 
         goal.unlock_date = Some(unlock_date);
-        goals.set(goal_id, goal);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        Self::save_goal(&env, &goal);
 
         Self::append_audit(&env, symbol_short!("timelock"), &caller, true);
         true
     }
 
+    /// Set how `withdraw_from_goal` treats early withdrawals from `goal_id`.
+    ///
+    /// New goals start with `LockPolicy::Hard`. A soft policy's penalty is
+    /// capped at 10 000 bps, and a penalty goal must be another existing goal
+    /// holding the same token.
+    pub fn set_lock_policy(env: Env, caller: Address, goal_id: u32, policy: LockPolicy) -> bool {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = match Self::load_goal(&env, goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(&env, symbol_short!("lockpol"), &caller, false);
+                panic!("Goal not found");
+            }
+        };
+
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("lockpol"), &caller, false);
+            panic!("Only the goal owner can set the lock policy");
+        }
+
+        if let LockPolicy::Soft(penalty_bps, destination) = &policy {
+            if *penalty_bps > MAX_PENALTY_BPS {
+                Self::append_audit(&env, symbol_short!("lockpol"), &caller, false);
+                panic!("Penalty cannot exceed 10000 bps");
+            }
+            if let PenaltyDestination::Goal(target_id) = destination {
+                let same_token = *target_id != goal_id
+                    && Self::load_goal(&env, *target_id)
+                        .is_some_and(|target| target.token == goal.token);
+                if !same_token {
+                    Self::append_audit(&env, symbol_short!("lockpol"), &caller, false);
+                    panic!("Penalty goal must be another goal with the same token");
+                }
+            }
+        }
+
+        goal.lock_policy = policy;
+        Self::save_goal(&env, &goal);
+
+        Self::append_audit(&env, symbol_short!("lockpol"), &caller, true);
+        true
+    }
+
+    // -----------------------------------------------------------------------
+    // Joint goals
+    // -----------------------------------------------------------------------
+
+    /// Allow `contributor` to deposit into `goal_id`.
+    ///
+    /// Contributors can add funds directly or in batches; withdrawals, locks
+    /// and schedules stay with the owner. At most 20 contributors per goal.
+    pub fn add_contributor(env: Env, caller: Address, goal_id: u32, contributor: Address) -> bool {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+        Self::require_goal_owner(&env, &caller, goal_id, symbol_short!("add_ctb"));
+        if contributor == caller {
+            Self::append_audit(&env, symbol_short!("add_ctb"), &caller, false);
+            panic!("The goal owner is always a contributor");
+        }
+
+        let mut entries = Self::contributions(&env, goal_id);
+        let mut approved = 0u32;
+        let mut existing = None;
+        for (i, entry) in entries.iter().enumerate() {
+            if entry.contributor == contributor {
+                existing = Some(i as u32);
+            } else if entry.approved && entry.contributor != caller {
+                approved += 1;
+            }
+        }
+        if approved >= MAX_CONTRIBUTORS {
+            Self::append_audit(&env, symbol_short!("add_ctb"), &caller, false);
+            panic!("Too many contributors");
+        }
+        match existing {
+            Some(i) => {
+                let mut entry = entries.get(i).unwrap();
+                entry.approved = true;
+                entries.set(i, entry);
+            }
+            None => entries.push_back(GoalContribution {
+                contributor: contributor.clone(),
+                approved: true,
+                total: 0,
+            }),
+        }
+        Self::set_contributions(&env, goal_id, &entries);
+
+        Self::append_audit(&env, symbol_short!("add_ctb"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::ContributorAdded),
+            (goal_id, contributor),
+        );
+        true
+    }
+
+    /// Stop `contributor` from depositing into `goal_id`. Their deposit total
+    /// is kept for attribution.
+    pub fn remove_contributor(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        contributor: Address,
+    ) -> bool {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+        Self::require_goal_owner(&env, &caller, goal_id, symbol_short!("rm_ctb"));
+        if contributor == caller {
+            Self::append_audit(&env, symbol_short!("rm_ctb"), &caller, false);
+            panic!("Cannot remove the goal owner");
+        }
+
+        let mut entries = Self::contributions(&env, goal_id);
+        let index = entries
+            .iter()
+            .position(|entry| entry.contributor == contributor && entry.approved);
+        let Some(i) = index else {
+            Self::append_audit(&env, symbol_short!("rm_ctb"), &caller, false);
+            panic!("Contributor not found");
+        };
+        let mut entry = entries.get(i as u32).unwrap();
+        entry.approved = false;
+        entries.set(i as u32, entry);
+        Self::set_contributions(&env, goal_id, &entries);
+
+        Self::append_audit(&env, symbol_short!("rm_ctb"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::ContributorRemoved),
+            (goal_id, contributor),
+        );
+        true
+    }
+
+    /// Everyone who has deposited into or been approved for `goal_id`, with
+    /// their deposit totals, in the order they were first recorded.
+    pub fn get_goal_contributions(env: Env, goal_id: u32) -> Vec<GoalContribution> {
+        Self::contributions(&env, goal_id)
+    }
+
+    /// Set what happens once deposits reach the goal's target. Deposits via
+    /// `add_to_goal`, `batch_add_to_goals`, `deposit_to_owner_goals` and
+    /// schedule execution apply it, as do transfers, credited interest and
+    /// soft-lock penalties routed to the goal. `rebalance_goals` does not.
+    ///
+    /// A sweep target must be another existing goal holding the same token
+    /// that the caller may contribute to. Sweeps move one hop only.
+    pub fn set_completion_action(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        action: CompletionAction,
+    ) -> bool {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let goal = match Self::load_goal(&env, goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(&env, symbol_short!("on_done"), &caller, false);
+                panic!("Goal not found");
+            }
+        };
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("on_done"), &caller, false);
+            panic!("Only the goal owner can set the completion action");
+        }
+        if let CompletionAction::SweepTo(next_id) = action {
+            let valid = next_id != goal_id
+                && Self::load_goal(&env, next_id).is_some_and(|next| {
+                    next.token == goal.token && Self::can_contribute(&env, &next, &caller)
+                });
+            if !valid {
+                Self::append_audit(&env, symbol_short!("on_done"), &caller, false);
+                panic!("Sweep target must be another goal with the same token");
+            }
+        }
+
+        let key = (Self::STORAGE_COMPLETION, goal_id);
+        env.storage().persistent().set(&key, &action);
+        Self::extend_persistent_ttl(&env, &key);
+
+        Self::append_audit(&env, symbol_short!("on_done"), &caller, true);
+        true
+    }
+
+    pub fn get_completion_action(env: Env, goal_id: u32) -> CompletionAction {
+        env.storage()
+            .persistent()
+            .get(&(Self::STORAGE_COMPLETION, goal_id))
+            .unwrap_or(CompletionAction::None)
+    }
+
     pub fn create_savings_schedule(
         env: Env,
         owner: Address,
@@ -1276,13 +2966,7 @@ impl SavingsGoalContract {
             panic!("Amount must be positive");
         }
 
-        let goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let goal = goals.get(goal_id).expect("Goal not found");
+        let goal = Self::load_goal(&env, goal_id).expect("Goal not found");
 
         if goal.owner != owner {
             panic!("Only the goal owner can create schedules");
@@ -1295,12 +2979,6 @@ impl SavingsGoalContract {
 
         Self::extend_instance_ttl(&env);
 
-        let mut schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
-
         let next_schedule_id = env
             .storage()
             .instance()
@@ -1322,13 +3000,11 @@ impl SavingsGoalContract {
             missed_count: 0,
         };
 
-        schedules.set(next_schedule_id, schedule);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("SAV_SCH"), &schedules);
+        Self::save_schedule(&env, &schedule);
         env.storage()
             .instance()
             .set(&symbol_short!("NEXT_SSCH"), &next_schedule_id);
+        Self::append_owner_schedule_id(&env, &owner, next_schedule_id);
 
         env.events().publish(
             (symbol_short!("savings"), SavingsEvent::ScheduleCreated),
@@ -1359,13 +3035,7 @@ impl SavingsGoalContract {
 
         Self::extend_instance_ttl(&env);
 
-        let mut schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut schedule = schedules.get(schedule_id).expect("Schedule not found");
+        let mut schedule = Self::load_schedule(&env, schedule_id).expect("Schedule not found");
 
         if schedule.owner != caller {
             panic!("Only the schedule owner can modify it");
@@ -1376,10 +3046,7 @@ impl SavingsGoalContract {
         schedule.interval = interval;
         schedule.recurring = interval > 0;
 
-        schedules.set(schedule_id, schedule);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("SAV_SCH"), &schedules);
+        Self::save_schedule(&env, &schedule);
 
         env.events().publish(
             (symbol_short!("savings"), SavingsEvent::ScheduleModified),
@@ -1394,13 +3061,7 @@ impl SavingsGoalContract {
 
         Self::extend_instance_ttl(&env);
 
-        let mut schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut schedule = schedules.get(schedule_id).expect("Schedule not found");
+        let mut schedule = Self::load_schedule(&env, schedule_id).expect("Schedule not found");
 
         if schedule.owner != caller {
             panic!("Only the schedule owner can cancel it");
@@ -1408,10 +3069,7 @@ impl SavingsGoalContract {
 
         schedule.active = false;
 
-        schedules.set(schedule_id, schedule);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("SAV_SCH"), &schedules);
+        Self::save_schedule(&env, &schedule);
 
         env.events().publish(
             (symbol_short!("savings"), SavingsEvent::ScheduleCancelled),
@@ -1421,37 +3079,75 @@ impl SavingsGoalContract {
         true
     }
 
-    pub fn execute_due_savings_schedules(env: Env) -> Vec<u32> {
+    /// Execute due schedules with IDs after `cursor`, looking at no more
+    /// than `max_items` schedules (0 → DEFAULT_PAGE_LIMIT, capped at
+    /// MAX_PAGE_LIMIT). Pass `next_cursor` back to continue; 0 means the sweep
+    /// reached the last schedule.
+    ///
+    /// A schedule that cannot run is reported in `failed` instead of
+    /// reverting the batch: if its goal is gone it is deactivated, otherwise
+    /// it stays due and is retried on the next sweep. A deposit the owner's
+    /// balance or allowance does not cover counts as a missed slot.
+    pub fn execute_due_savings_schedules(env: Env, cursor: u32, max_items: u32) -> ScheduleRun {
         Self::extend_instance_ttl(&env);
 
         let current_time = env.ledger().timestamp();
         let mut executed = Vec::new(&env);
+        let mut failed = Vec::new(&env);
+        let paused = Self::get_global_paused(&env)
+            || Self::is_function_paused(&env, pause_functions::ADD_TO_GOAL);
 
-        let mut schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut goals: Map<u32, SavingsGoal> = env
+        let last_id: u32 = env
             .storage()
             .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        for (schedule_id, mut schedule) in schedules.iter() {
+            .get(&symbol_short!("NEXT_SSCH"))
+            .unwrap_or(0);
+        let end = cursor
+            .saturating_add(Self::clamp_limit(max_items))
+            .min(last_id);
+
+        for schedule_id in cursor.saturating_add(1)..=end {
+            let mut schedule = match Self::load_schedule(&env, schedule_id) {
+                Some(schedule) => schedule,
+                None => continue,
+            };
             if !schedule.active || schedule.next_due > current_time {
                 continue;
             }
 
-            if let Some(mut goal) = goals.get(schedule.goal_id) {
-                goal.current_amount = goal
-                    .current_amount
-                    .checked_add(schedule.amount)
-                    .expect("overflow");
+            let goal = Self::load_goal(&env, schedule.goal_id);
+            let failure = if paused {
+                Some(ScheduleFailureReason::Paused)
+            } else {
+                match &goal {
+                    None => Some(ScheduleFailureReason::GoalNotFound),
+                    Some(goal) if goal.current_amount.checked_add(schedule.amount).is_none() => {
+                        Some(ScheduleFailureReason::Overflow)
+                    }
+                    Some(_) => None,
+                }
+            };
+            if let Some(reason) = failure {
+                if reason == ScheduleFailureReason::GoalNotFound {
+                    schedule.active = false;
+                    Self::save_schedule(&env, &schedule);
+                }
+                let entry = Self::record_schedule_failure(&env, schedule_id, reason);
+                failed.push_back(entry);
+                continue;
+            }
+
+            let mut goal = goal.unwrap();
+            let collected = Self::collect_scheduled(&env, &goal, schedule.amount);
+            let mut paused_by_goal = false;
+            if collected {
+                Self::sync_interest(&env, &goal);
+                goal.current_amount += schedule.amount;
+                Self::record_contribution(&env, schedule.goal_id, &goal.owner, schedule.amount);
 
                 let is_completed = goal.current_amount >= goal.target_amount;
-                goals.set(schedule.goal_id, goal.clone());
+                Self::save_goal(&env, &goal);
+                Self::check_milestones(&env, &goal);
 
                 env.events().publish(
                     (symbol_short!("savings"), SavingsEvent::FundsAdded),
@@ -1463,20 +3159,29 @@ impl SavingsGoalContract {
                         (symbol_short!("savings"), SavingsEvent::GoalCompleted),
                         (schedule.goal_id, goal.owner),
                     );
+                    paused_by_goal = Self::apply_completion_action(&env, schedule.goal_id);
                 }
+                schedule.last_executed = Some(current_time);
+            } else {
+                // An uncollectable deposit counts as a missed slot; the
+                // schedule still moves on to its next due date.
+                schedule.missed_count = schedule.missed_count.saturating_add(1);
+                env.events().publish(
+                    (symbol_short!("savings"), SavingsEvent::ScheduleMissed),
+                    (schedule_id, 1u32),
+                );
             }
 
-            schedule.last_executed = Some(current_time);
-
             if schedule.recurring && schedule.interval > 0 {
-                let mut missed = 0u32;
-                let mut next = schedule.next_due + schedule.interval;
-                while next <= current_time {
-                    missed += 1;
-                    next += schedule.interval;
-                }
-                schedule.missed_count += missed;
-                schedule.next_due = next;
+                // Every whole interval between the due date and now is a slot
+                // that was never executed.
+                let elapsed = current_time - schedule.next_due;
+                let missed = elapsed / schedule.interval;
+                schedule.next_due = schedule
+                    .next_due
+                    .saturating_add(schedule.interval.saturating_mul(missed + 1));
+                let missed = u32::try_from(missed).unwrap_or(u32::MAX);
+                schedule.missed_count = schedule.missed_count.saturating_add(missed);
 
                 if missed > 0 {
                     env.events().publish(
@@ -1487,49 +3192,45 @@ impl SavingsGoalContract {
             } else {
                 schedule.active = false;
             }
+            if paused_by_goal {
+                schedule.active = false;
+            }
 
-            schedules.set(schedule_id, schedule);
-            executed.push_back(schedule_id);
+            Self::save_schedule(&env, &schedule);
+            if collected {
+                executed.push_back(schedule_id);
 
-            env.events().publish(
-                (symbol_short!("savings"), SavingsEvent::ScheduleExecuted),
-                schedule_id,
-            );
+                env.events().publish(
+                    (symbol_short!("savings"), SavingsEvent::ScheduleExecuted),
+                    schedule_id,
+                );
+            }
         }
 
-        env.storage()
-            .instance()
-            .set(&symbol_short!("SAV_SCH"), &schedules);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        ScheduleRun {
+            executed,
+            failed,
+            next_cursor: if end < last_id { end } else { 0 },
+        }
+    }
 
-        executed
+    /// The most recent failure recorded for `schedule_id`, if any.
+    pub fn get_schedule_failure(env: Env, schedule_id: u32) -> Option<ScheduleFailure> {
+        env.storage()
+            .persistent()
+            .get(&(Self::STORAGE_SCHEDULE_FAILURES, schedule_id))
     }
 
     pub fn get_savings_schedules(env: Env, owner: Address) -> Vec<SavingsSchedule> {
-        let schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
-
         let mut result = Vec::new(&env);
-        for (_, schedule) in schedules.iter() {
-            if schedule.owner == owner {
-                result.push_back(schedule);
-            }
+        for schedule_id in Self::owner_schedule_ids(&env, &owner).iter() {
+            result.push_back(Self::load_schedule(&env, schedule_id).unwrap());
         }
         result
     }
 
     pub fn get_savings_schedule(env: Env, schedule_id: u32) -> Option<SavingsSchedule> {
-        let schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
-        schedules.get(schedule_id)
+        Self::load_schedule(&env, schedule_id)
     }
 }
 
@@ -1,7 +1,7 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::testutils::storage::Instance as _;
+use soroban_sdk::testutils::storage::{Instance as _, Persistent as _};
 use soroban_sdk::{
     testutils::{Address as AddressTrait, Events, Ledger, LedgerInfo},
     Address, Env, String, Symbol, TryFromVal,
@@ -46,7 +46,7 @@ fn test_create_goal_unique_ids() {
 // ============================================================================
 // init() idempotency and NEXT_ID behavior
 //
-// init() bootstraps storage (NEXT_ID and GOALS) only when keys are missing.
+// init() bootstraps storage (NEXT_ID) only when the key is missing.
 // In production or integration, init() may be called more than once (e.g. by
 // different entrypoints or upgrade paths). These tests lock in that:
 // - A second init() must not remove or alter existing goals.
@@ -88,7 +88,11 @@ fn test_init_idempotent_does_not_wipe_goals() {
     assert_eq!(goal_after_second_init.current_amount, 0);
 
     let all_goals = client.get_all_goals(&owner_a);
-    assert_eq!(all_goals.len(), 1, "get_all_goals must still return the one goal");
+    assert_eq!(
+        all_goals.len(),
+        1,
+        "get_all_goals must still return the one goal"
+    );
 
     // Verify NEXT_ID was not reset: next created goal must get goal_id == 2, not 1
     let name2 = String::from_str(&env, "Second Goal");
@@ -137,11 +141,10 @@ fn test_next_id_increments_sequentially() {
     assert_eq!(ids[1], 2, "second goal id must be 2");
     assert_eq!(ids[2], 3, "third goal id must be 3");
 
-    for (i, &id) in ids.iter().enumerate() {
+    for (&id, name) in ids.iter().zip(["G1", "G2", "G3"]) {
         let goal = client.get_goal(&id).unwrap();
         assert_eq!(goal.id, id);
-        let expected_name = String::from_str(&env, &format!("G{}", i + 1));
-        assert_eq!(goal.name, expected_name);
+        assert_eq!(goal.name, String::from_str(&env, name));
     }
 }
 
@@ -397,8 +400,7 @@ fn test_withdraw_from_goal_unauthorized() {
 }
 
 #[test]
-#[should_panic(expected = "Amount must be positive")]
-fn test_withdraw_from_goal_zero_amount_panics() {
+fn test_withdraw_from_goal_zero_amount_fails() {
     let env = Env::default();
     let contract_id = env.register_contract(None, SavingsGoalContract);
     let client = SavingsGoalContractClient::new(&env, &contract_id);
@@ -410,12 +412,12 @@ fn test_withdraw_from_goal_zero_amount_panics() {
 
     client.unlock_goal(&user, &id);
     client.add_to_goal(&user, &id, &500);
-    client.withdraw_from_goal(&user, &id, &0);
+    let res = client.try_withdraw_from_goal(&user, &id, &0);
+    assert!(res.is_err());
 }
 
 #[test]
-#[should_panic(expected = "Goal not found")]
-fn test_withdraw_from_goal_nonexistent_goal_panics() {
+fn test_withdraw_from_goal_nonexistent_goal_fails() {
     let env = Env::default();
     let contract_id = env.register_contract(None, SavingsGoalContract);
     let client = SavingsGoalContractClient::new(&env, &contract_id);
@@ -423,7 +425,8 @@ fn test_withdraw_from_goal_nonexistent_goal_panics() {
 
     client.init();
     env.mock_all_auths();
-    client.withdraw_from_goal(&user, &999, &100);
+    let res = client.try_withdraw_from_goal(&user, &999, &100);
+    assert!(res.is_err());
 }
 
 #[test]
@@ -630,7 +633,7 @@ fn test_execute_due_savings_schedules() {
     let schedule_id = client.create_savings_schedule(&owner, &goal_id, &500, &3000, &0);
 
     set_time(&env, 3500);
-    let executed = client.execute_due_savings_schedules();
+    let executed = client.execute_due_savings_schedules(&0, &0).executed;
 
     assert_eq!(executed.len(), 1);
     assert_eq!(executed.get(0).unwrap(), schedule_id);
@@ -654,7 +657,7 @@ fn test_execute_recurring_savings_schedule() {
     let schedule_id = client.create_savings_schedule(&owner, &goal_id, &500, &3000, &86400);
 
     set_time(&env, 3500);
-    client.execute_due_savings_schedules();
+    client.execute_due_savings_schedules(&0, &0);
 
     let schedule = client.get_savings_schedule(&schedule_id).unwrap();
     assert!(schedule.active);
@@ -679,7 +682,7 @@ fn test_execute_missed_savings_schedules() {
     let schedule_id = client.create_savings_schedule(&owner, &goal_id, &500, &3000, &86400);
 
     set_time(&env, 3000 + 86400 * 3 + 100);
-    client.execute_due_savings_schedules();
+    client.execute_due_savings_schedules(&0, &0);
 
     let schedule = client.get_savings_schedule(&schedule_id).unwrap();
     assert_eq!(schedule.missed_count, 3);
@@ -701,7 +704,7 @@ fn test_savings_schedule_goal_completion() {
     client.create_savings_schedule(&owner, &goal_id, &1000, &3000, &0);
 
     set_time(&env, 3500);
-    client.execute_due_savings_schedules();
+    client.execute_due_savings_schedules(&0, &0);
 
     let goal = client.get_goal(&goal_id).unwrap();
     assert_eq!(goal.current_amount, 1000);
@@ -1326,6 +1329,8 @@ fn test_savings_data_persists_across_ledger_advancements() {
     });
 
     client.add_to_goal(&user, &id1, &3000);
+    // Each goal has its own TTL, so the second one must be written too.
+    client.lock_goal(&user, &id2);
 
     // Phase 3: Advance to seq 1,020,000 (TTL = 8,400 < 17,280)
     env.ledger().set(LedgerInfo {
@@ -1677,3 +1682,1202 @@ fn test_unlock_goal_non_owner_auth_failure() {
     let id = client.create_goal(&user, &String::from_str(&env, "Auth"), &1000, &2000000000);
     client.unlock_goal(&other, &id);
 }
+
+#[test]
+#[should_panic(expected = "Snapshot checksum mismatch")]
+fn test_import_snapshot_rejects_tampered_goals() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let first = client.create_goal(&user, &String::from_str(&env, "A"), &1000, &2000000000);
+    let second = client.create_goal(&user, &String::from_str(&env, "B"), &1000, &2000000000);
+    client.add_to_goal(&user, &first, &100);
+    client.add_to_goal(&user, &second, &300);
+
+    let mut snapshot = client.export_snapshot(&user);
+    assert_eq!(snapshot.version, 4);
+    let payload = (
+        snapshot.next_id,
+        snapshot.goals.clone(),
+        snapshot.states.clone(),
+    )
+        .to_xdr(&env);
+    assert_eq!(snapshot.checksum, env.crypto().sha256(&payload).to_bytes());
+
+    // Swapping balances keeps every total the same but changes the hash.
+    let mut a = snapshot.goals.get(0).unwrap();
+    let mut b = snapshot.goals.get(1).unwrap();
+    core::mem::swap(&mut a.current_amount, &mut b.current_amount);
+    snapshot.goals.set(0, a);
+    snapshot.goals.set(1, b);
+    client.import_snapshot(&user, &0, &snapshot);
+}
+
+#[test]
+fn test_import_snapshot_restores_goal_state() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let first = client.create_goal(&user, &String::from_str(&env, "A"), &1000, &2000000000);
+    let second = client.create_goal(&user, &String::from_str(&env, "B"), &1000, &2000000000);
+    client.add_to_goal(&user, &first, &100);
+    client.set_goal_funding(&user, &first, &2, &5);
+    client.set_goal_milestones(
+        &user,
+        &first,
+        &soroban_sdk::vec![
+            &env,
+            MilestoneTarget::Percent(10),
+            MilestoneTarget::Amount(500),
+        ],
+    );
+    client.set_completion_action(&user, &first, &CompletionAction::SweepTo(second));
+
+    let snapshot = client.export_snapshot(&user);
+    let state = snapshot.states.get(0).unwrap();
+    assert_eq!(state.goal_id, first);
+    assert_eq!(state.contributions.len(), 1);
+    assert_eq!(state.milestones.len(), 2);
+    assert_eq!(
+        state.funding,
+        GoalFunding {
+            priority: 2,
+            weight: 5
+        }
+    );
+    assert_eq!(state.completion, CompletionAction::SweepTo(second));
+
+    assert!(client.import_snapshot(&user, &0, &snapshot));
+    assert_eq!(client.export_snapshot(&user).states, snapshot.states);
+    assert_eq!(client.get_goal_contributions(&first), state.contributions);
+    assert_eq!(
+        client.get_goal_funding(&first),
+        GoalFunding {
+            priority: 2,
+            weight: 5
+        }
+    );
+    assert_eq!(
+        client.get_completion_action(&first),
+        CompletionAction::SweepTo(second)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Snapshot goal states do not match its goals")]
+fn test_import_snapshot_rejects_misaligned_states() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    client.create_goal(&user, &String::from_str(&env, "A"), &1000, &2000000000);
+    client.create_goal(&user, &String::from_str(&env, "B"), &1000, &2000000000);
+
+    let mut snapshot = client.export_snapshot(&user);
+    snapshot.states.pop_back();
+    let payload = (
+        snapshot.next_id,
+        snapshot.goals.clone(),
+        snapshot.states.clone(),
+    )
+        .to_xdr(&env);
+    snapshot.checksum = env.crypto().sha256(&payload).to_bytes();
+    client.import_snapshot(&user, &0, &snapshot);
+}
+
+fn create_token(env: &Env, holder: &Address, amount: i128) -> Address {
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    soroban_sdk::token::StellarAssetClient::new(env, &token).mint(holder, &amount);
+    token
+}
+
+#[test]
+fn test_token_goal_holds_deposits_in_custody() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let token = create_token(&env, &user, 5000);
+    let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+    let id = client.create_token_goal(
+        &user,
+        &String::from_str(&env, "Rent"),
+        &3000,
+        &2000000000,
+        &token,
+    );
+    assert_eq!(client.get_goal(&id).unwrap().token, Some(token.clone()));
+
+    client.add_to_goal(&user, &id, &2000);
+    assert_eq!(token_client.balance(&user), 3000);
+    assert_eq!(token_client.balance(&contract_id), 2000);
+
+    client.unlock_goal(&user, &id);
+    client.withdraw_from_goal(&user, &id, &500);
+    assert_eq!(token_client.balance(&user), 3500);
+
+    let report = client.get_solvency(&token);
+    assert_eq!(report.balance, 1500);
+    assert_eq!(report.liabilities, 1500);
+    assert!(report.solvent);
+
+    // Bookkeeping-only goals never touch the token.
+    let legacy = client.create_goal(&user, &String::from_str(&env, "Notes"), &100, &2000000000);
+    client.add_to_goal(&user, &legacy, &100);
+    assert_eq!(client.get_solvency(&token).liabilities, 1500);
+}
+
+#[test]
+fn test_token_schedule_pulls_through_allowance() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    let token = create_token(&env, &user, 1000);
+    let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+    let id = client.create_token_goal(&user, &String::from_str(&env, "Fees"), &5000, &5000, &token);
+    let schedule_id = client.create_savings_schedule(&user, &id, &400, &2000, &1000);
+
+    // No allowance yet: the slot is missed and nothing is credited.
+    set_time(&env, 2000);
+    assert_eq!(
+        client.execute_due_savings_schedules(&0, &0).executed.len(),
+        0
+    );
+    let schedule = client.get_savings_schedule(&schedule_id).unwrap();
+    assert_eq!(schedule.missed_count, 1);
+    assert_eq!(schedule.next_due, 3000);
+    assert_eq!(client.get_goal(&id).unwrap().current_amount, 0);
+
+    token_client.approve(&user, &contract_id, &1000, &100);
+    set_time(&env, 3000);
+    assert_eq!(
+        client.execute_due_savings_schedules(&0, &0).executed.len(),
+        1
+    );
+    assert_eq!(client.get_goal(&id).unwrap().current_amount, 400);
+    assert_eq!(token_client.balance(&contract_id), 400);
+    assert_eq!(token_client.allowance(&user, &contract_id), 600);
+    assert!(client.get_solvency(&token).solvent);
+}
+
+#[test]
+#[should_panic(expected = "Cannot import over custodial balances")]
+fn test_import_snapshot_rejects_custodial_balances() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let token = create_token(&env, &user, 1000);
+    let id = client.create_token_goal(
+        &user,
+        &String::from_str(&env, "Held"),
+        &1000,
+        &2000000000,
+        &token,
+    );
+    client.add_to_goal(&user, &id, &250);
+
+    let snapshot = client.export_snapshot(&user);
+    client.import_snapshot(&user, &0, &snapshot);
+}
+
+#[test]
+fn test_soft_lock_charges_penalty_on_early_withdrawal() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    let family = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    let token = create_token(&env, &user, 10_000);
+    let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+    let id = client.create_token_goal(&user, &String::from_str(&env, "Rent"), &5000, &5000, &token);
+    client.add_to_goal(&user, &id, &4000);
+
+    // Hard (the default) still refuses while locked.
+    assert!(client.try_withdraw_from_goal(&user, &id, &1000).is_err());
+
+    client.set_lock_policy(
+        &user,
+        &id,
+        &LockPolicy::Soft(500, PenaltyDestination::Recipient(family.clone())),
+    );
+    assert_eq!(client.withdraw_from_goal(&user, &id, &1000), 3000);
+    assert_eq!(token_client.balance(&user), 6950);
+    assert_eq!(token_client.balance(&family), 50);
+
+    let event = env.events().all().last().unwrap();
+    let data: (u32, Address, i128, i128) = TryFromVal::try_from_val(&env, &event.2).unwrap();
+    assert_eq!(data, (id, user.clone(), 1000, 50));
+    assert!(client.get_solvency(&token).solvent);
+
+    // Past the target date a soft lock no longer charges.
+    set_time(&env, 5000);
+    client.withdraw_from_goal(&user, &id, &1000);
+    assert_eq!(token_client.balance(&user), 7950);
+    assert_eq!(token_client.balance(&family), 50);
+}
+
+#[test]
+fn test_penalty_can_be_credited_to_another_goal() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    let id = client.create_goal(&user, &String::from_str(&env, "Trip"), &5000, &9000);
+    let fund = client.create_goal(&user, &String::from_str(&env, "Family"), &5000, &9000);
+    client.add_to_goal(&user, &id, &2000);
+
+    client.set_lock_policy(
+        &user,
+        &id,
+        &LockPolicy::Soft(1000, PenaltyDestination::Goal(fund)),
+    );
+    client.withdraw_from_goal(&user, &id, &1000);
+    assert_eq!(client.get_goal(&id).unwrap().current_amount, 1000);
+    assert_eq!(client.get_goal(&fund).unwrap().current_amount, 100);
+
+    // With no policy the lock and the target date are ignored.
+    client.set_lock_policy(&user, &id, &LockPolicy::None);
+    client.withdraw_from_goal(&user, &id, &1000);
+    assert_eq!(client.get_goal(&fund).unwrap().current_amount, 100);
+}
+
+#[test]
+#[should_panic(expected = "Penalty goal must be another goal with the same token")]
+fn test_set_lock_policy_rejects_mismatched_penalty_goal() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+    let token = create_token(&env, &user, 0);
+    let held =
+        client.create_token_goal(&user, &String::from_str(&env, "Held"), &100, &9000, &token);
+    let plain = client.create_goal(&user, &String::from_str(&env, "Plain"), &100, &9000);
+    client.set_lock_policy(
+        &user,
+        &held,
+        &LockPolicy::Soft(100, PenaltyDestination::Goal(plain)),
+    );
+}
+
+#[test]
+fn test_joint_goal_tracks_contributions() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let sibling = Address::generate(&env);
+
+    env.mock_all_auths();
+    let token = create_token(&env, &owner, 1000);
+    soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&sibling, &1000);
+    let id = client.create_token_goal(
+        &owner,
+        &String::from_str(&env, "School Fees"),
+        &2000,
+        &2000000000,
+        &token,
+    );
+
+    assert!(client.try_add_to_goal(&sibling, &id, &100).is_err());
+
+    client.add_contributor(&owner, &id, &sibling);
+    client.add_to_goal(&sibling, &id, &300);
+    client.add_to_goal(&owner, &id, &200);
+    let item = ContributionItem {
+        goal_id: id,
+        amount: 100,
+    };
+    client.batch_add_to_goals(&sibling, &soroban_sdk::vec![&env, item]);
+    assert_eq!(client.get_goal(&id).unwrap().current_amount, 600);
+    assert_eq!(
+        soroban_sdk::token::TokenClient::new(&env, &token).balance(&sibling),
+        600
+    );
+
+    // Contributors cannot withdraw.
+    client.unlock_goal(&owner, &id);
+    assert!(client.try_withdraw_from_goal(&sibling, &id, &100).is_err());
+
+    client.remove_contributor(&owner, &id, &sibling);
+    assert!(client.try_add_to_goal(&sibling, &id, &100).is_err());
+
+    let contributions = client.get_goal_contributions(&id);
+    assert_eq!(contributions.len(), 2);
+    assert_eq!(
+        contributions.get(0).unwrap(),
+        GoalContribution {
+            contributor: sibling,
+            approved: false,
+            total: 400,
+        }
+    );
+    assert_eq!(
+        contributions.get(1).unwrap(),
+        GoalContribution {
+            contributor: owner,
+            approved: true,
+            total: 200,
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "Only the goal owner can manage contributors")]
+fn test_only_owner_can_add_contributors() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    env.mock_all_auths();
+    let id = client.create_goal(&owner, &String::from_str(&env, "Rent"), &1000, &2000000000);
+    client.add_contributor(&other, &id, &other);
+}
+
+fn milestone_events(env: &Env) -> u32 {
+    let mut count = 0;
+    for event in env.events().all().iter() {
+        let topic = event.1.get(1).map(|t| SavingsEvent::try_from_val(env, &t));
+        if let Some(Ok(SavingsEvent::MilestoneReached)) = topic {
+            count += 1;
+        }
+    }
+    count
+}
+
+#[test]
+fn test_milestones_are_reached_once() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    let id = client.create_goal(&user, &String::from_str(&env, "Fees"), &1000, &2000000000);
+    client.add_to_goal(&user, &id, &300);
+
+    // 25% is already passed, so it is reached as soon as it is set.
+    client.set_goal_milestones(
+        &user,
+        &id,
+        &soroban_sdk::vec![
+            &env,
+            MilestoneTarget::Percent(25),
+            MilestoneTarget::Percent(50),
+            MilestoneTarget::Amount(900),
+        ],
+    );
+    assert_eq!(milestone_events(&env), 1);
+
+    set_time(&env, 2000);
+    client.add_to_goal(&user, &id, &250);
+    assert_eq!(milestone_events(&env), 2);
+    client.add_to_goal(&user, &id, &10);
+    assert_eq!(milestone_events(&env), 2);
+
+    client.create_savings_schedule(&user, &id, &400, &3000, &0);
+    set_time(&env, 3000);
+    client.execute_due_savings_schedules(&0, &0);
+    assert_eq!(milestone_events(&env), 3);
+
+    let milestones = client.get_goal_progress(&id).unwrap().milestones;
+    assert_eq!(milestones.get(0).unwrap().reached_at, Some(1000));
+    assert_eq!(milestones.get(1).unwrap().reached_at, Some(2000));
+    assert_eq!(milestones.get(2).unwrap().reached_at, Some(3000));
+
+    // Re-setting keeps what was already reached and fires nothing.
+    client.set_goal_milestones(
+        &user,
+        &id,
+        &soroban_sdk::vec![&env, MilestoneTarget::Percent(50)],
+    );
+    assert_eq!(milestone_events(&env), 3);
+    let milestones = client.get_goal_progress(&id).unwrap().milestones;
+    assert_eq!(milestones.get(0).unwrap().reached_at, Some(2000));
+}
+
+#[test]
+fn test_goal_progress_rates() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 0);
+    // Ten days out, 700 still to save.
+    let id = client.create_goal(&user, &String::from_str(&env, "Rent"), &1000, &864000);
+    client.add_to_goal(&user, &id, &300);
+
+    let progress = client.get_goal_progress(&id).unwrap();
+    assert_eq!(progress.percent, 30);
+    assert_eq!(progress.seconds_remaining, 864000);
+    assert_eq!(progress.required_daily, 70);
+    assert_eq!(progress.required_weekly, 350);
+
+    set_time(&env, 900000);
+    let progress = client.get_goal_progress(&id).unwrap();
+    assert_eq!(progress.seconds_remaining, 0);
+    assert_eq!(progress.required_daily, 700);
+    assert!(client.get_goal_progress(&99).is_none());
+}
+
+#[test]
+fn test_transfer_between_goals_respects_lock_policy() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    let token = create_token(&env, &user, 1000);
+    let from = client.create_token_goal(&user, &String::from_str(&env, "A"), &1000, &9000, &token);
+    let to = client.create_token_goal(&user, &String::from_str(&env, "B"), &1000, &9000, &token);
+    let plain = client.create_goal(&user, &String::from_str(&env, "C"), &1000, &9000);
+    client.add_to_goal(&user, &from, &600);
+
+    // Hard lock (default) blocks; different tokens never mix.
+    assert!(client
+        .try_transfer_between_goals(&user, &from, &to, &100)
+        .is_err());
+    client.unlock_goal(&user, &from);
+    assert!(client
+        .try_transfer_between_goals(&user, &from, &plain, &100)
+        .is_err());
+
+    assert_eq!(client.transfer_between_goals(&user, &from, &to, &100), 100);
+
+    // A soft lock's penalty is kept out of the transfer.
+    client.set_lock_policy(
+        &user,
+        &from,
+        &LockPolicy::Soft(1000, PenaltyDestination::Goal(to)),
+    );
+    assert_eq!(client.transfer_between_goals(&user, &from, &to, &200), 180);
+    assert_eq!(client.get_goal(&from).unwrap().current_amount, 300);
+    assert_eq!(client.get_goal(&to).unwrap().current_amount, 300);
+
+    let event = env.events().all().last().unwrap();
+    let data: (u32, u32, i128, i128) = TryFromVal::try_from_val(&env, &event.2).unwrap();
+    assert_eq!(data, (from, to, 200, 20));
+    let report = client.get_solvency(&token);
+    assert_eq!(report.balance, 600);
+    assert_eq!(report.liabilities, 600);
+}
+
+#[test]
+fn test_transfer_between_goals_counts_as_deposit() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+    let from = client.create_goal(&user, &String::from_str(&env, "A"), &5000, &9000);
+    let to = client.create_goal(&user, &String::from_str(&env, "B"), &1000, &9000);
+    let spill = client.create_goal(&user, &String::from_str(&env, "C"), &5000, &9000);
+    client.add_to_goal(&user, &from, &1500);
+    client.unlock_goal(&user, &from);
+    client.set_completion_action(&user, &to, &CompletionAction::SweepTo(spill));
+
+    assert_eq!(
+        client.transfer_between_goals(&user, &from, &to, &1200),
+        1200
+    );
+    assert_eq!(client.get_goal(&to).unwrap().current_amount, 1000);
+    assert_eq!(client.get_goal(&spill).unwrap().current_amount, 200);
+
+    let contributions = client.get_goal_contributions(&to);
+    assert_eq!(contributions.len(), 1);
+    assert_eq!(contributions.get(0).unwrap().contributor, user);
+    assert_eq!(contributions.get(0).unwrap().total, 1200);
+}
+
+#[test]
+fn test_rebalance_goals_by_weight() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+    let a = client.create_goal(&user, &String::from_str(&env, "A"), &1000, &9000);
+    let b = client.create_goal(&user, &String::from_str(&env, "B"), &1000, &9000);
+    let c = client.create_goal(&user, &String::from_str(&env, "C"), &1000, &9000);
+    client.add_to_goal(&user, &a, &1000);
+    client.unlock_goal(&user, &a);
+
+    let weights = soroban_sdk::vec![
+        &env,
+        RebalanceWeight {
+            goal_id: a,
+            weight: 1
+        },
+        RebalanceWeight {
+            goal_id: b,
+            weight: 3
+        },
+        RebalanceWeight {
+            goal_id: c,
+            weight: 2
+        },
+    ];
+    let amounts = client.rebalance_goals(&user, &weights);
+    // 1000 * 3 / 6 = 500 takes the rounding dust: 166 + 501 + 333.
+    assert_eq!(amounts, soroban_sdk::vec![&env, 166, 501, 333]);
+    assert_eq!(client.get_goal(&b).unwrap().current_amount, 501);
+
+    // B is still hard-locked, so it cannot be drawn down.
+    let weights = soroban_sdk::vec![
+        &env,
+        RebalanceWeight {
+            goal_id: a,
+            weight: 1
+        },
+        RebalanceWeight {
+            goal_id: b,
+            weight: 0
+        },
+    ];
+    assert!(client.try_rebalance_goals(&user, &weights).is_err());
+}
+
+#[test]
+fn test_interest_accrues_lazily_and_is_claimable() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let year = 365 * 86400;
+
+    client.init();
+    env.mock_all_auths();
+    client.set_upgrade_admin(&admin, &admin);
+    client.set_rate_admin(&admin, &admin);
+    set_time(&env, 0);
+    let token = create_token(&env, &user, 10_000);
+    let id = client.create_token_goal(
+        &user,
+        &String::from_str(&env, "Fees"),
+        &20_000,
+        &year,
+        &token,
+    );
+    client.add_to_goal(&user, &id, &10_000);
+
+    client.set_interest_rate(&admin, &token, &1000);
+
+    // Half a year at 10%, then the rate halves for the second half.
+    set_time(&env, year / 2);
+    assert_eq!(client.get_goal_interest(&id).unwrap().pending, 500);
+    client.set_interest_rate(&admin, &token, &500);
+    set_time(&env, year);
+    let interest = client.get_goal_interest(&id).unwrap();
+    assert_eq!(interest.apr_bps, 500);
+    assert_eq!(interest.pending, 750);
+
+    // Nothing is credited without a reserve to pay it from.
+    assert_eq!(client.accrue_interest(&id), 0);
+    assert_eq!(client.get_goal(&id).unwrap().current_amount, 10_000);
+
+    // A short reserve pays what it holds; the rest stays pending.
+    soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&admin, &1000);
+    assert_eq!(client.fund_interest(&admin, &token, &500), 500);
+    assert_eq!(client.accrue_interest(&id), 500);
+    assert_eq!(client.get_interest_reserve(&token), 0);
+    let interest = client.get_goal_interest(&id).unwrap();
+    assert_eq!((interest.pending, interest.claimed), (250, 500));
+    assert!(client.get_solvency(&token).solvent);
+
+    client.fund_interest(&admin, &token, &500);
+    assert_eq!(client.accrue_interest(&id), 250);
+    assert_eq!(client.accrue_interest(&id), 0);
+    assert_eq!(client.get_goal(&id).unwrap().current_amount, 10_750);
+    assert_eq!(client.get_interest_reserve(&token), 250);
+    let interest = client.get_goal_interest(&id).unwrap();
+    assert_eq!((interest.pending, interest.claimed), (0, 750));
+
+    let report = client.get_solvency(&token);
+    assert_eq!(report.liabilities, 10_750);
+    assert_eq!(report.balance, 11_000);
+}
+
+#[test]
+fn test_interest_on_large_balances_does_not_overflow() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let year = 365 * 86400;
+    let balance = 1_000_000_000_000_000_000_000_000_000i128;
+
+    client.init();
+    env.mock_all_auths();
+    client.set_upgrade_admin(&admin, &admin);
+    client.set_rate_admin(&admin, &admin);
+    set_time(&env, 0);
+    let token = create_token(&env, &user, balance);
+    let id = client.create_token_goal(
+        &user,
+        &String::from_str(&env, "Vault"),
+        &(balance * 4),
+        &(10 * year),
+        &token,
+    );
+    client.add_to_goal(&user, &id, &balance);
+    client.set_interest_rate(&admin, &token, &10_000);
+
+    // balance * index would not fit in an i128 after a year at 100% APR.
+    set_time(&env, year);
+    assert_eq!(client.get_goal_interest(&id).unwrap().pending, balance);
+    set_time(&env, year + year / 2);
+    assert_eq!(
+        client.get_goal_interest(&id).unwrap().pending,
+        balance + balance / 2
+    );
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_only_rate_admin_sets_interest_rate() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    client.set_upgrade_admin(&admin, &admin);
+    client.set_rate_admin(&admin, &admin);
+    let token = create_token(&env, &admin, 0);
+    client.set_interest_rate(&other, &token, &100);
+}
+
+#[test]
+fn test_rate_admin_is_appointed_by_upgrade_admin_and_handed_over() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let rate_admin = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let token = create_token(&env, &admin, 0);
+
+    // Nobody can claim the role before there is an upgrade admin.
+    assert!(client.try_set_rate_admin(&other, &other).is_err());
+    client.set_upgrade_admin(&admin, &admin);
+    assert!(client.try_set_rate_admin(&other, &other).is_err());
+    client.set_rate_admin(&admin, &rate_admin);
+    client.set_interest_rate(&rate_admin, &token, &100);
+
+    // Once appointed, only the rate admin hands the role on.
+    assert!(client.try_set_rate_admin(&admin, &admin).is_err());
+    client.set_rate_admin(&rate_admin, &other);
+    assert!(client
+        .try_set_interest_rate(&rate_admin, &token, &200)
+        .is_err());
+    client.set_interest_rate(&other, &token, &200);
+    assert_eq!(client.get_interest_rate(&token).unwrap().apr_bps, 200);
+}
+
+#[test]
+fn test_completion_action_sweeps_or_returns_overflow() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+    let token = create_token(&env, &user, 5000);
+    let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+    let first = client.create_token_goal(&user, &String::from_str(&env, "A"), &1000, &9000, &token);
+    let next = client.create_token_goal(&user, &String::from_str(&env, "B"), &1000, &9000, &token);
+
+    client.set_completion_action(&user, &first, &CompletionAction::SweepTo(next));
+    assert_eq!(client.add_to_goal(&user, &first, &1300), 1000);
+    assert_eq!(client.get_goal(&next).unwrap().current_amount, 300);
+
+    client.set_completion_action(&user, &next, &CompletionAction::ReturnExcess);
+    let item = ContributionItem {
+        goal_id: next,
+        amount: 900,
+    };
+    client.batch_add_to_goals(&user, &soroban_sdk::vec![&env, item]);
+    assert_eq!(client.get_goal(&next).unwrap().current_amount, 1000);
+    assert_eq!(token_client.balance(&user), 3000);
+    assert_eq!(token_client.balance(&contract_id), 2000);
+    assert!(client.get_solvency(&token).solvent);
+}
+
+#[test]
+fn test_interest_and_penalties_apply_completion_actions() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let year = 365 * 86400;
+
+    client.init();
+    env.mock_all_auths();
+    client.set_upgrade_admin(&admin, &admin);
+    client.set_rate_admin(&admin, &admin);
+    set_time(&env, 0);
+    let token = create_token(&env, &user, 5000);
+    let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+    let name = String::from_str(&env, "Goal");
+    let first = client.create_token_goal(&user, &name, &1000, &(10 * year), &token);
+    let next = client.create_token_goal(&user, &name, &1000, &(10 * year), &token);
+    client.add_to_goal(&user, &first, &950);
+    client.set_completion_action(&user, &first, &CompletionAction::SweepTo(next));
+
+    // Half a year at 100% takes the goal past its target; the excess moves on.
+    client.set_interest_rate(&admin, &token, &10_000);
+    set_time(&env, year / 2);
+    soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&admin, &1000);
+    client.fund_interest(&admin, &token, &1000);
+    assert_eq!(client.accrue_interest(&first), 475);
+    assert_eq!(client.get_goal(&first).unwrap().current_amount, 1000);
+    assert_eq!(client.get_goal(&next).unwrap().current_amount, 425);
+
+    // A penalty that completes its destination goal returns the excess.
+    let trip = client.create_token_goal(&user, &name, &5000, &(10 * year), &token);
+    let fund = client.create_token_goal(&user, &name, &100, &(10 * year), &token);
+    client.add_to_goal(&user, &trip, &1000);
+    client.set_completion_action(&user, &fund, &CompletionAction::ReturnExcess);
+    client.set_lock_policy(
+        &user,
+        &trip,
+        &LockPolicy::Soft(5000, PenaltyDestination::Goal(fund)),
+    );
+    client.withdraw_from_goal(&user, &trip, &400);
+    assert_eq!(client.get_goal(&fund).unwrap().current_amount, 100);
+    assert_eq!(token_client.balance(&user), 5000 - 950 - 1000 + 200 + 100);
+    assert!(client.get_solvency(&token).solvent);
+
+    // Rebalancing keeps the requested balances instead of sweeping.
+    client.unlock_goal(&user, &next);
+    let weights = soroban_sdk::vec![
+        &env,
+        RebalanceWeight {
+            goal_id: first,
+            weight: 3
+        },
+        RebalanceWeight {
+            goal_id: next,
+            weight: 1
+        },
+    ];
+    let amounts = client.rebalance_goals(&user, &weights);
+    assert_eq!(amounts, soroban_sdk::vec![&env, 1069, 356]);
+    assert_eq!(client.get_goal(&first).unwrap().current_amount, 1069);
+}
+
+#[test]
+fn test_completion_action_pauses_schedules() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    let id = client.create_goal(&user, &String::from_str(&env, "Fees"), &1000, &9000);
+    client.add_to_goal(&user, &id, &600);
+    client.set_completion_action(&user, &id, &CompletionAction::PauseSchedules);
+    let weekly = client.create_savings_schedule(&user, &id, &400, &2000, &604800);
+    let monthly = client.create_savings_schedule(&user, &id, &400, &2000, &2592000);
+
+    set_time(&env, 2000);
+    let executed = client.execute_due_savings_schedules(&0, &0).executed;
+    assert_eq!(executed, soroban_sdk::vec![&env, weekly]);
+    assert_eq!(client.get_goal(&id).unwrap().current_amount, 1000);
+    assert!(!client.get_savings_schedule(&weekly).unwrap().active);
+    assert!(!client.get_savings_schedule(&monthly).unwrap().active);
+}
+
+#[test]
+#[should_panic(expected = "Sweep target must be another goal with the same token")]
+fn test_completion_sweep_rejects_self() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+    let id = client.create_goal(&user, &String::from_str(&env, "A"), &1000, &9000);
+    client.set_completion_action(&user, &id, &CompletionAction::SweepTo(id));
+}
+
+#[test]
+fn test_execute_schedules_pages_with_cursor() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    let id = client.create_goal(&user, &String::from_str(&env, "Fees"), &100_000, &9000);
+    let first = client.create_savings_schedule(&user, &id, &100, &2000, &0);
+    let second = client.create_savings_schedule(&user, &id, &100, &2000, &0);
+    // Ticks every second; a long gap must not cost one iteration per slot.
+    let third = client.create_savings_schedule(&user, &id, &100, &2000, &1);
+
+    set_time(&env, 1_002_000);
+    let run = client.execute_due_savings_schedules(&0, &2);
+    assert_eq!(run.executed, soroban_sdk::vec![&env, first, second]);
+    assert_eq!(run.next_cursor, second);
+
+    let run = client.execute_due_savings_schedules(&run.next_cursor, &2);
+    assert_eq!(run.executed, soroban_sdk::vec![&env, third]);
+    assert_eq!(run.next_cursor, 0);
+    assert_eq!(client.get_goal(&id).unwrap().current_amount, 300);
+
+    let schedule = client.get_savings_schedule(&third).unwrap();
+    assert_eq!(schedule.missed_count, 1_000_000);
+    assert_eq!(schedule.next_due, 1_002_001);
+}
+
+#[test]
+fn test_execute_schedules_records_failures() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    let admin = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    let full = client.create_goal(&user, &String::from_str(&env, "Full"), &i128::MAX, &9000);
+    client.add_to_goal(&user, &full, &(i128::MAX - 10));
+    let id = client.create_goal(&user, &String::from_str(&env, "Fees"), &5000, &9000);
+    let overflowing = client.create_savings_schedule(&user, &full, &100, &2000, &0);
+    let ok = client.create_savings_schedule(&user, &id, &100, &2000, &0);
+
+    client.set_pause_admin(&admin, &admin);
+    client.pause(&admin);
+    set_time(&env, 2000);
+    let run = client.execute_due_savings_schedules(&0, &0);
+    assert_eq!(run.executed.len(), 0);
+    assert_eq!(run.failed.len(), 2);
+    assert_eq!(
+        run.failed.get(1).unwrap().reason,
+        ScheduleFailureReason::Paused
+    );
+    assert!(client.get_savings_schedule(&ok).unwrap().active);
+
+    // One bad schedule no longer reverts the rest of the batch.
+    client.unpause(&admin);
+    let run = client.execute_due_savings_schedules(&0, &0);
+    assert_eq!(run.executed, soroban_sdk::vec![&env, ok]);
+    assert_eq!(
+        run.failed,
+        soroban_sdk::vec![
+            &env,
+            ScheduleFailure {
+                schedule_id: overflowing,
+                reason: ScheduleFailureReason::Overflow,
+                timestamp: 2000,
+            }
+        ]
+    );
+    assert_eq!(
+        client.get_schedule_failure(&overflowing),
+        Some(run.failed.get(0).unwrap())
+    );
+    assert!(client.get_schedule_failure(&ok).is_some());
+    assert_eq!(client.get_goal(&id).unwrap().current_amount, 100);
+    assert_eq!(
+        client.get_goal(&full).unwrap().current_amount,
+        i128::MAX - 10
+    );
+}
+
+#[test]
+fn test_migrate_storage_moves_legacy_maps() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+    let admin = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    let legacy_goal = |id: u32, owner: &Address| LegacySavingsGoal {
+        id,
+        owner: owner.clone(),
+        name: String::from_str(&env, "Legacy"),
+        target_amount: 5000,
+        current_amount: 100 * id as i128,
+        target_date: 9000,
+        locked: true,
+        unlock_date: None,
+        tags: Vec::new(&env),
+    };
+    let mut goals = Map::new(&env);
+    goals.set(1u32, legacy_goal(1, &user));
+    goals.set(2u32, legacy_goal(2, &other));
+    let mut schedules = Map::new(&env);
+    schedules.set(
+        1u32,
+        SavingsSchedule {
+            id: 1,
+            owner: user.clone(),
+            goal_id: 1,
+            amount: 50,
+            next_due: 2000,
+            interval: 0,
+            recurring: false,
+            active: true,
+            created_at: 1000,
+            last_executed: None,
+            missed_count: 0,
+        },
+    );
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        storage.set(&symbol_short!("GOALS"), &goals);
+        storage.set(&symbol_short!("SAV_SCH"), &schedules);
+        storage.set(&symbol_short!("NEXT_ID"), &2u32);
+        storage.set(&symbol_short!("NEXT_SSCH"), &1u32);
+    });
+
+    client.set_upgrade_admin(&admin, &admin);
+    assert_eq!(client.migrate_storage(&admin), 3);
+    assert_eq!(client.migrate_storage(&admin), 0);
+
+    let migrated = client.get_goal(&2).unwrap();
+    assert_eq!(migrated.current_amount, 200);
+    assert_eq!(migrated.token, None);
+    assert_eq!(migrated.lock_policy, LockPolicy::Hard);
+    assert_eq!(client.get_all_goals(&user).len(), 1);
+    assert_eq!(client.get_all_goals(&other).len(), 1);
+    assert_eq!(client.get_savings_schedules(&user).len(), 1);
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&symbol_short!("GOALS")));
+        assert!(!env.storage().instance().has(&symbol_short!("SAV_SCH")));
+    });
+
+    let id = client.create_goal(&user, &String::from_str(&env, "New"), &5000, &9000);
+    assert_eq!(id, 3);
+    assert_eq!(client.get_goals(&user, &0, &0).items.len(), 2);
+    set_time(&env, 2000);
+    assert_eq!(
+        client.execute_due_savings_schedules(&0, &0).executed.len(),
+        1
+    );
+    assert_eq!(client.get_goal(&1).unwrap().current_amount, 150);
+    assert_eq!(client.get_goal_contributions(&1).get(0).unwrap().total, 50);
+}
+
+#[test]
+fn test_goal_entries_have_their_own_ttl() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+    let id = client.create_goal(&user, &String::from_str(&env, "Fees"), &5000, &2000000000);
+    let schedule_id = client.create_savings_schedule(&user, &id, &100, &2000000000, &0);
+
+    let keys: [Val; 4] = [
+        (symbol_short!("GOALS"), id).into_val(&env),
+        (symbol_short!("SAV_SCH"), schedule_id).into_val(&env),
+        (symbol_short!("OWN_GOAL"), user.clone()).into_val(&env),
+        (symbol_short!("OWN_SCH"), user).into_val(&env),
+    ];
+    env.as_contract(&contract_id, || {
+        for key in keys.iter() {
+            assert!(env.storage().persistent().get_ttl(key) >= 518_400);
+        }
+    });
+}
+
+#[test]
+fn test_deposit_to_owner_goals_by_priority_and_deadline() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    let rent = client.create_goal(&user, &String::from_str(&env, "Rent"), &1000, &9000);
+    let school = client.create_goal(&user, &String::from_str(&env, "School"), &500, &5000);
+    let trip = client.create_goal(&user, &String::from_str(&env, "Trip"), &800, &7000);
+    let done = client.create_goal(&user, &String::from_str(&env, "Done"), &100, &3000);
+    client.add_to_goal(&user, &done, &100);
+    client.add_to_goal(&user, &rent, &600);
+    client.set_goal_funding(&user, &school, &5, &1);
+    client.set_goal_funding(&user, &rent, &5, &1);
+
+    // Equal priorities keep goal order; unprioritised goals come last.
+    let allocations =
+        client.deposit_to_owner_goals(&user, &None, &1200, &FundingStrategy::Priority);
+    assert_eq!(
+        allocations,
+        soroban_sdk::vec![
+            &env,
+            GoalAllocation {
+                goal_id: rent,
+                amount: 400
+            },
+            GoalAllocation {
+                goal_id: school,
+                amount: 500
+            },
+            GoalAllocation {
+                goal_id: trip,
+                amount: 300
+            },
+        ]
+    );
+    assert!(client.is_goal_completed(&rent));
+    assert_eq!(client.get_goal(&done).unwrap().current_amount, 100);
+
+    // Only what the goals still need is deposited.
+    let allocations =
+        client.deposit_to_owner_goals(&user, &None, &10_000, &FundingStrategy::ClosestDeadline);
+    assert_eq!(
+        allocations,
+        soroban_sdk::vec![
+            &env,
+            GoalAllocation {
+                goal_id: trip,
+                amount: 500
+            }
+        ]
+    );
+    assert_eq!(
+        client
+            .deposit_to_owner_goals(&user, &None, &10, &FundingStrategy::Priority)
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_deposit_to_owner_goals_proportional() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    let token = create_token(&env, &user, 10_000);
+    let small = client.create_token_goal(&user, &String::from_str(&env, "A"), &100, &9000, &token);
+    let big = client.create_token_goal(&user, &String::from_str(&env, "B"), &5000, &9000, &token);
+    let other = client.create_token_goal(&user, &String::from_str(&env, "C"), &5000, &9000, &token);
+    let skipped =
+        client.create_token_goal(&user, &String::from_str(&env, "D"), &5000, &9000, &token);
+    client.set_goal_funding(&user, &small, &0, &2);
+    client.set_goal_funding(&user, &big, &0, &2);
+    client.set_goal_funding(&user, &skipped, &0, &0);
+
+    // 1001 by weights 2:2:1 caps A at 100; its excess is re-split 2:1 and the
+    // rounding dust goes to the heaviest goal with room left.
+    let allocations = client.deposit_to_owner_goals(
+        &user,
+        &Some(token.clone()),
+        &1001,
+        &FundingStrategy::Proportional,
+    );
+    assert_eq!(
+        allocations,
+        soroban_sdk::vec![
+            &env,
+            GoalAllocation {
+                goal_id: small,
+                amount: 100
+            },
+            GoalAllocation {
+                goal_id: big,
+                amount: 601
+            },
+            GoalAllocation {
+                goal_id: other,
+                amount: 300
+            },
+        ]
+    );
+    assert_eq!(client.get_goal(&skipped).unwrap().current_amount, 0);
+    let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&contract_id), 1001);
+    assert!(client.get_solvency(&token).solvent);
+
+    // Goals in another token, or without one, are left out.
+    let plain = client.create_goal(&user, &String::from_str(&env, "E"), &100, &9000);
+    let allocations = client.deposit_to_owner_goals(
+        &user,
+        &Some(token.clone()),
+        &10,
+        &FundingStrategy::Proportional,
+    );
+    assert_eq!(allocations.len(), 2);
+    assert!(allocations.iter().all(|a| a.goal_id != plain));
+    assert_eq!(client.get_goal(&plain).unwrap().current_amount, 0);
+    assert_eq!(
+        client.get_goal_funding(&plain),
+        GoalFunding {
+            priority: 0,
+            weight: 1
+        }
+    );
+    let allocations =
+        client.deposit_to_owner_goals(&user, &None, &10, &FundingStrategy::Proportional);
+    assert_eq!(
+        allocations,
+        soroban_sdk::vec![
+            &env,
+            GoalAllocation {
+                goal_id: plain,
+                amount: 10
+            }
+        ]
+    );
+}